@@ -0,0 +1,330 @@
+# Section titles
+section-summary-system = Systemübersicht
+section-summary-memory = Speicherübersicht
+section-summary-cpu = CPU-Übersicht
+section-system = System
+section-memory = Arbeitsspeicher
+section-cpus = CPUs
+section-load = Last
+section-disks = Datenträger
+section-disk-usage = Speicherbelegung
+section-networks = Netzwerke
+section-components = Komponenten
+section-container = Container
+section-docker = Docker-Container
+section-k8s-node = Kubernetes-Knoten
+section-pod-processes = Pod-Prozesse
+section-hosts = Hosts
+section-sensors = Sensoren
+section-kernel-resources = Kernel-Ressourcen
+section-file-holders = Geöffnet von
+section-connections = Verbindungen
+section-port-owners = Sockets auf dem Port
+section-self-stats = Eigenverbrauch
+section-profile-collection = Erfassungszeiten
+section-capabilities = Fähigkeiten
+section-processes = Prozesse
+section-bench = Benchmarks
+section-thermal = Thermik
+section-throttle-summary = Drosselungsübersicht
+section-watch-summary = Zusammenfassung der Beobachtung
+section-kill = Signale
+section-process = Prozess
+section-environment = Umgebung
+section-threads = Threads
+section-users = Benutzer
+section-sessions = Sitzungen
+section-history = Verlauf
+section-diff = Änderungen
+section-disk-changes = Speicherplatz
+section-started = Gestartete Prozesse
+section-exited = Beendete Prozesse
+
+# Labels
+system-name = Systemname:
+kernel-version = Kernel-Version:
+os-version = OS-Version:
+host-name = Hostname:
+uptime = Betriebszeit:
+boot-time = Startzeit:
+taken-at = Aufgenommen:
+diff-from = Von:
+diff-to = Bis:
+load-average = Durchschnittslast:
+load-per-cpu = Last pro CPU:
+load-threshold = Schwellenwert:
+load-exceeded = ÜBERSCHRITTEN
+load-ok = OK
+total-cpus = CPUs gesamt:
+physical-cores = Physische Kerne:
+sockets = Sockel:
+cpu-vendor = Hersteller:
+cpu-brand = Modell:
+nb-cpus = Anzahl CPUs:
+cpu-scale = CPU %:
+cpu-scale-per-core = eines Kerns (kann 100 % übersteigen)
+cpu-scale-total = aller CPUs
+global-usage = Gesamtauslastung:
+cpu-time-split = Zeitaufteilung:
+time-user = Benutzer
+time-system = System
+time-iowait = E/A-Wartezeit
+time-irq = IRQ
+time-steal = Steal
+core-voltage = Kernspannung:
+total-cpu-usage = CPU-Auslastung gesamt:
+total-memory = Arbeitsspeicher gesamt:
+used-memory = Belegter Speicher:
+available-memory = Verfügbarer Speicher:
+free-memory = Freier Speicher:
+buffers = Puffer:
+cached = Cache:
+cgroup-memory = Cgroup-Speicher:
+cgroup-rss = Cgroup-RSS:
+total-swap = Auslagerung gesamt:
+used-swap = Belegte Auslagerung:
+scanned-path = Pfad:
+total-size = Gesamtgröße:
+total-files = Dateien:
+skipped-unreadable = Nicht lesbare Einträge:
+file-handles = Dateihandles:
+epoll-instances = Epoll-Instanzen:
+inotify-instances = Inotify-Instanzen:
+inotify-max-instances = Inotify max. Instanzen:
+inotify-max-watches = Inotify max. Watches:
+epoll-max-watches = Epoll max. Watches:
+no-file-holders = Kein Prozess hat sie geöffnet.
+no-connections = Keine passenden Sockets.
+no-port-owners = Dieser Port wird nicht verwendet.
+no-containers = Keine laufenden Container.
+no-pod-processes = Keine Prozesse in Kubernetes-Pods.
+no-sensors = Keine Hardwaresensoren gefunden.
+no-sessions = Keine aktiven Anmeldesitzungen.
+no-history = Keine Messwerte für diese Metrik in diesem Zeitraum.
+no-changes = Keine Änderungen.
+diff-rebooted = Das System wurde zwischen den Snapshots neu gestartet.
+deleted = gelöscht
+iterations = Durchläufe:
+cpu-time = CPU-Zeit:
+peak-memory = Spitzenspeicher:
+status-supported = unterstützt
+status-unsupported = nicht unterstützt
+status-needs-privilege = benötigt Rechte
+outcome-matched = gefunden (nicht gesendet)
+outcome-sent = gesendet
+outcome-failed = fehlgeschlagen
+outcome-unsupported = hier nicht unterstützt
+alert-ok = OK: alle Schwellenwerte eingehalten
+alert-critical = CRITICAL: ausgelöst:
+alert-unknown = UNKNOWN: nicht lesbar:
+alert-status-ok = ok
+alert-status-fired = AUSGELÖST
+alert-status-unknown = unbekannt
+scanning = Durchsuche…
+scanned-files = Dateien
+committed-memory = Zugesicherter Speicher:
+commit-limit = Zusicherungsgrenze:
+container-runtime = Laufzeit:
+not-in-container = keine (nicht in einem Container)
+container-id = Container-ID:
+cgroup = Cgroup:
+cpu-limit = CPU-Limit:
+cpus-used = Genutzte CPUs:
+memory-limit = Speicherlimit:
+memory-used = Genutzter Speicher:
+unlimited = unbegrenzt
+not-available = k. A.
+cpu-frequency = CPU-Frequenz:
+peak = Spitze
+temperature = Temperatur:
+critical = kritisch
+throttling = Drosselung:
+throttle-under-voltage = Unterspannung
+throttle-freq-capped = Frequenz begrenzt
+throttle-throttled = gedrosselt
+throttle-soft-temp-limit = weiches Temperaturlimit
+since-boot = seit Start
+event-started = gestartet
+event-exited = beendet
+event-parent = Eltern
+event-ran-for = lief
+event-disk-attached = Datenträger angeschlossen
+event-disk-detached = Datenträger entfernt
+event-interface-added = Schnittstelle hinzugefügt
+event-interface-removed = Schnittstelle entfernt
+event-power-changed = Stromversorgung geändert
+yes = ja
+no = nein
+samples = Messungen:
+elapsed = Dauer:
+metric-cpu-usage = CPU-Auslastung
+metric-memory-used = Belegter Speicher
+network-received = Netzwerk empfangen:
+network-transmitted = Netzwerk gesendet:
+fetch-os = OS
+fetch-kernel = Kernel
+fetch-uptime = Betriebszeit
+fetch-cpu = CPU
+fetch-memory = Speicher
+fetch-disk = Datenträger
+throttled-samples = Gedrosselte Messungen:
+frequency-range = Frequenzbereich:
+process-pid = PID:
+process-name = Name:
+process-cmd = Befehlszeile:
+process-exe = Programmdatei:
+process-cwd = Arbeitsverzeichnis:
+process-parent = Eltern-PID:
+process-status = Status:
+process-user = Benutzer:
+process-started = Gestartet:
+process-run-time = Laufzeit:
+process-cpu = CPU-Auslastung:
+process-memory = Speicher:
+process-virtual-memory = Virtueller Speicher:
+process-disk-read = Vom Datenträger gelesen:
+process-disk-written = Auf Datenträger geschrieben:
+process-threads = Threads:
+max-temperature = Höchsttemperatur:
+
+# Table headers
+col-id = ID
+col-usage-pct = Auslastung %
+col-avg-pct = Mittel %
+col-peak-pct = Spitze %
+col-user-pct = Benutzer %
+col-system-pct = System %
+col-iowait-pct = E/A-Wartezeit %
+col-irq-pct = IRQ %
+col-steal-pct = Steal %
+col-vendor = Hersteller
+col-brand = Modell
+col-mhz = MHz
+col-min-mhz = Min. MHz
+col-max-mhz = Max. MHz
+col-socket = Sockel
+col-core = Kern
+col-name = Name
+col-kind = Typ
+col-fs = Dateisystem
+col-mount = Einhängepunkt
+col-removable = Wechselmedium
+col-read-only = Schreibgeschützt
+col-available = Verfügbar
+col-total = Gesamt
+col-used-pct = Belegt %
+col-change-per-min = Änderung/min
+col-full-in = Voll in
+col-read = Gelesen
+col-written = Geschrieben
+col-read-per-sec = Gelesen/s
+col-written-per-sec = Geschrieben/s
+col-path = Pfad
+col-size = Größe
+col-share-pct = Anteil %
+col-files = Dateien
+col-protocol = Protokoll
+col-state = Zustand
+col-local-address = Lokale Adresse
+col-remote-address = Entfernte Adresse
+col-count = Anzahl
+col-held-as = Art
+col-section = Abschnitt
+col-samples = Messungen
+col-avg-ms = Ø ms
+col-max-ms = Max. ms
+col-stage = Stufe
+col-items = Einträge
+col-first-ms = Erstes ms
+col-refreshes = Aktualisierungen
+col-metric = Metrik
+col-status = Status
+col-image = Image
+col-net-io = Netz-E/A
+col-block-io = Block-E/A
+col-pids = PIDs
+col-cpu-cores = CPU (Kerne)
+col-memory-bytes = Speicher (Bytes)
+col-pod = Pod
+col-container = Container
+col-qos = QoS
+col-detail = Hinweis
+col-queue = Warteschlange
+col-namespace = Namensraum
+col-interface = Schnittstelle
+col-mac = MAC-Adresse
+col-mtu = MTU
+col-addresses = Adressen
+col-packets = Pakete (rx / tx)
+col-errors = Fehler (rx / tx)
+col-drops = Verworfen (rx / tx)
+col-received-delta = Δ Empfangen
+col-transmitted-delta = Δ Gesendet
+col-packets-delta = Δ Pakete (rx / tx)
+col-errors-delta = Δ Fehler (rx / tx)
+col-received = Empfangen
+col-transmitted = Gesendet
+col-received-per-sec = Empfangen/s
+col-transmitted-per-sec = Gesendet/s
+col-label = Bezeichnung
+col-temp = Temp.
+col-max = Max.
+col-min = Min.
+col-avg = Mittel
+col-p95 = P95
+col-pct-of-max = % vom Max.
+col-critical = Kritisch
+col-pid = PID
+col-tid = TID
+col-cpu-pct = CPU %
+col-memory = Speicher
+col-run-time = Laufzeit
+col-handles = Handles
+col-benchmark = Benchmark
+col-result = Ergebnis
+col-time = Zeit
+col-signal = Signal
+col-outcome = Ergebnis
+col-uid = UID
+col-gid = GID
+col-groups = Gruppen
+col-sessions = Sitzungen
+col-user = Benutzer
+col-tty = TTY
+col-host = Host
+col-address = Adresse
+col-load = Last
+col-disk-alerts = Volle Datenträger
+col-login = Anmeldung
+col-rule = Regel
+col-target = Ziel
+col-value = Wert
+col-before = Vorher
+col-after = Nachher
+col-change = Änderung
+col-mem-pct = Speicher %
+col-virtual = Virtuell
+col-started = Gestartet
+col-threads = Threads
+col-command = Befehl
+
+# Benchmark names
+bench-cpu-single = CPU-Hash (1 Thread)
+bench-cpu-multi = CPU-Hash (alle Threads)
+bench-memory-copy = Speicherkopie
+bench-disk-seq-write = Datenträger sequenziell schreiben
+bench-disk-seq-read = Datenträger sequenziell lesen
+bench-disk-seq-read-cached = Datenträger sequenziell lesen (zwischengespeichert)
+bench-disk-rand-read = Datenträger zufällig 4K lesen
+bench-disk-rand-read-cached = Datenträger zufällig 4K lesen (zwischengespeichert)
+bench-disk-rand-write = Datenträger zufällig 4K schreiben
+
+# Interaktive Ansicht
+tui-cpu = CPU
+tui-memory = Speicher
+tui-swap = Auslagerung
+tui-filter = Filter:
+tui-help = q beenden · ↑↓ Bild↑ Bild↓ blättern · c/m/p/n sortieren · / filtern · k beenden
+tui-confirm-kill = Beenden:
+tui-signal-sent = Beendigung angefordert für
+tui-signal-failed = Konnte nicht beendet werden:
@@ -0,0 +1,330 @@
+# Section titles
+section-summary-system = System Summary
+section-summary-memory = Memory Summary
+section-summary-cpu = CPU Summary
+section-system = System
+section-memory = Memory
+section-cpus = CPUs
+section-load = Load
+section-disks = Disks
+section-disk-usage = Disk usage
+section-networks = Networks
+section-components = Components
+section-container = Container
+section-docker = Docker containers
+section-k8s-node = Kubernetes node
+section-pod-processes = Pod processes
+section-hosts = Hosts
+section-sensors = Sensors
+section-kernel-resources = Kernel resources
+section-file-holders = Open by
+section-connections = Connections
+section-port-owners = Sockets on the port
+section-self-stats = Tool overhead
+section-profile-collection = Collection timings
+section-capabilities = Capabilities
+section-processes = Processes
+section-bench = Benchmarks
+section-thermal = Thermal
+section-throttle-summary = Throttling summary
+section-watch-summary = Watch summary
+section-kill = Signals
+section-process = Process
+section-environment = Environment
+section-threads = Threads
+section-users = Users
+section-sessions = Sessions
+section-history = History
+section-diff = Changes
+section-disk-changes = Disk space
+section-started = Started processes
+section-exited = Exited processes
+
+# Labels
+system-name = System name:
+kernel-version = Kernel version:
+os-version = OS version:
+host-name = Host name:
+uptime = Uptime:
+boot-time = Boot time:
+taken-at = Taken at:
+diff-from = From:
+diff-to = To:
+load-average = Load average:
+load-per-cpu = Load per CPU:
+load-threshold = Threshold:
+load-exceeded = EXCEEDED
+load-ok = OK
+total-cpus = Total CPUs:
+physical-cores = Physical cores:
+sockets = Sockets:
+cpu-vendor = Vendor:
+cpu-brand = Brand:
+nb-cpus = NB CPUs:
+cpu-scale = CPU %:
+cpu-scale-per-core = of one core (can exceed 100%)
+cpu-scale-total = of all CPUs
+global-usage = Global usage:
+cpu-time-split = Time split:
+time-user = user
+time-system = system
+time-iowait = I/O wait
+time-irq = IRQ
+time-steal = steal
+core-voltage = Core voltage:
+total-cpu-usage = Total CPU usage:
+total-memory = Total memory:
+used-memory = Used memory:
+available-memory = Available memory:
+free-memory = Free memory:
+buffers = Buffers:
+cached = Cached:
+cgroup-memory = Cgroup memory:
+cgroup-rss = Cgroup RSS:
+total-swap = Total swap:
+used-swap = Used swap:
+scanned-path = Path:
+total-size = Total size:
+total-files = Files:
+skipped-unreadable = Unreadable entries:
+file-handles = File handles:
+epoll-instances = Epoll instances:
+inotify-instances = Inotify instances:
+inotify-max-instances = Inotify max instances:
+inotify-max-watches = Inotify max watches:
+epoll-max-watches = Epoll max watches:
+no-file-holders = No process has it open.
+no-connections = No matching sockets.
+no-port-owners = Nothing is using this port.
+no-containers = No running containers.
+no-pod-processes = No processes running in Kubernetes pods.
+no-sensors = No hardware sensors found.
+no-sessions = No active login sessions.
+no-history = No samples recorded for this metric in that period.
+no-changes = No changes.
+diff-rebooted = The system rebooted between the snapshots.
+deleted = deleted
+iterations = Iterations:
+cpu-time = CPU time:
+peak-memory = Peak memory:
+status-supported = supported
+status-unsupported = unsupported
+status-needs-privilege = needs privilege
+outcome-matched = matched (not sent)
+outcome-sent = sent
+outcome-failed = failed
+outcome-unsupported = unsupported here
+alert-ok = OK: all thresholds passed
+alert-critical = CRITICAL: fired:
+alert-unknown = UNKNOWN: could not read:
+alert-status-ok = ok
+alert-status-fired = FIRED
+alert-status-unknown = unknown
+scanning = Scanning…
+scanned-files = files
+committed-memory = Committed memory:
+commit-limit = Commit limit:
+container-runtime = Runtime:
+not-in-container = none (not in a container)
+container-id = Container ID:
+cgroup = Cgroup:
+cpu-limit = CPU limit:
+cpus-used = CPUs in use:
+memory-limit = Memory limit:
+memory-used = Memory in use:
+unlimited = unlimited
+not-available = N/A
+cpu-frequency = CPU frequency:
+peak = peak
+temperature = Temperature:
+critical = critical
+throttling = Throttling:
+throttle-under-voltage = under-voltage
+throttle-freq-capped = frequency capped
+throttle-throttled = throttled
+throttle-soft-temp-limit = soft temperature limit
+since-boot = since boot
+event-started = started
+event-exited = exited
+event-parent = parent
+event-ran-for = ran for
+event-disk-attached = disk attached
+event-disk-detached = disk detached
+event-interface-added = interface added
+event-interface-removed = interface removed
+event-power-changed = power changed
+yes = yes
+no = no
+samples = Samples:
+elapsed = Elapsed:
+metric-cpu-usage = CPU usage
+metric-memory-used = Memory used
+network-received = Network received:
+network-transmitted = Network transmitted:
+fetch-os = OS
+fetch-kernel = Kernel
+fetch-uptime = Uptime
+fetch-cpu = CPU
+fetch-memory = Memory
+fetch-disk = Disk
+throttled-samples = Throttled samples:
+frequency-range = Frequency range:
+process-pid = PID:
+process-name = Name:
+process-cmd = Command line:
+process-exe = Executable:
+process-cwd = Working directory:
+process-parent = Parent PID:
+process-status = Status:
+process-user = User:
+process-started = Started:
+process-run-time = Run time:
+process-cpu = CPU usage:
+process-memory = Memory:
+process-virtual-memory = Virtual memory:
+process-disk-read = Disk read:
+process-disk-written = Disk written:
+process-threads = Threads:
+max-temperature = Max temperature:
+
+# Table headers
+col-id = ID
+col-usage-pct = Usage %
+col-avg-pct = Avg %
+col-peak-pct = Peak %
+col-user-pct = User %
+col-system-pct = System %
+col-iowait-pct = I/O wait %
+col-irq-pct = IRQ %
+col-steal-pct = Steal %
+col-vendor = Vendor
+col-brand = Brand
+col-mhz = MHz
+col-min-mhz = Min MHz
+col-max-mhz = Max MHz
+col-socket = Socket
+col-core = Core
+col-name = Name
+col-kind = Kind
+col-fs = FS
+col-mount = Mount point
+col-removable = Removable
+col-read-only = Read-only
+col-available = Available
+col-total = Total
+col-used-pct = Used %
+col-change-per-min = Change/min
+col-full-in = Full in
+col-read = Read
+col-written = Written
+col-read-per-sec = Read/s
+col-written-per-sec = Written/s
+col-path = Path
+col-size = Size
+col-share-pct = Share %
+col-files = Files
+col-protocol = Protocol
+col-state = State
+col-local-address = Local address
+col-remote-address = Remote address
+col-count = Count
+col-held-as = Held as
+col-section = Section
+col-samples = Samples
+col-avg-ms = Avg ms
+col-max-ms = Max ms
+col-stage = Stage
+col-items = Items
+col-first-ms = First ms
+col-refreshes = Refreshes
+col-metric = Metric
+col-status = Status
+col-image = Image
+col-net-io = Net I/O
+col-block-io = Block I/O
+col-pids = PIDs
+col-cpu-cores = CPU (cores)
+col-memory-bytes = Memory (bytes)
+col-pod = Pod
+col-container = Container
+col-qos = QoS
+col-detail = Detail
+col-queue = Queue
+col-namespace = Namespace
+col-interface = Interface
+col-mac = MAC address
+col-mtu = MTU
+col-addresses = Addresses
+col-packets = Packets (rx / tx)
+col-errors = Errors (rx / tx)
+col-drops = Drops (rx / tx)
+col-received-delta = Δ Received
+col-transmitted-delta = Δ Transmitted
+col-packets-delta = Δ Packets (rx / tx)
+col-errors-delta = Δ Errors (rx / tx)
+col-received = Received
+col-transmitted = Transmitted
+col-received-per-sec = Received/s
+col-transmitted-per-sec = Transmitted/s
+col-label = Label
+col-temp = Temp
+col-max = Max
+col-min = Min
+col-avg = Avg
+col-p95 = P95
+col-pct-of-max = % of max
+col-critical = Critical
+col-pid = PID
+col-tid = TID
+col-cpu-pct = CPU %
+col-memory = Memory
+col-run-time = Run time
+col-handles = Handles
+col-benchmark = Benchmark
+col-result = Result
+col-time = Time
+col-signal = Signal
+col-outcome = Outcome
+col-uid = UID
+col-gid = GID
+col-groups = Groups
+col-sessions = Sessions
+col-user = User
+col-tty = TTY
+col-host = Host
+col-address = Address
+col-load = Load
+col-disk-alerts = Disk alerts
+col-login = Login
+col-rule = Rule
+col-target = Target
+col-value = Value
+col-before = Before
+col-after = After
+col-change = Change
+col-mem-pct = Mem %
+col-virtual = Virtual
+col-started = Started
+col-threads = Threads
+col-command = Command
+
+# Benchmark names
+bench-cpu-single = CPU hash (1 thread)
+bench-cpu-multi = CPU hash (all threads)
+bench-memory-copy = Memory copy
+bench-disk-seq-write = Disk sequential write
+bench-disk-seq-read = Disk sequential read
+bench-disk-seq-read-cached = Disk sequential read (cached)
+bench-disk-rand-read = Disk random 4K read
+bench-disk-rand-read-cached = Disk random 4K read (cached)
+bench-disk-rand-write = Disk random 4K write
+
+# Interactive view
+tui-cpu = CPU
+tui-memory = Memory
+tui-swap = Swap
+tui-filter = Filter:
+tui-help = q quit · ↑↓ PgUp PgDn scroll · c/m/p/n sort · / filter · k kill
+tui-confirm-kill = Terminate
+tui-signal-sent = Sent termination request to
+tui-signal-failed = Could not terminate
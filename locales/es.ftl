@@ -0,0 +1,330 @@
+# Section titles
+section-summary-system = Resumen del sistema
+section-summary-memory = Resumen de memoria
+section-summary-cpu = Resumen de CPU
+section-system = Sistema
+section-memory = Memoria
+section-cpus = CPUs
+section-load = Carga
+section-disks = Discos
+section-disk-usage = Uso del disco
+section-networks = Redes
+section-components = Componentes
+section-container = Contenedor
+section-docker = Contenedores Docker
+section-k8s-node = Nodo de Kubernetes
+section-pod-processes = Procesos de pods
+section-hosts = Equipos
+section-sensors = Sensores
+section-kernel-resources = Recursos del núcleo
+section-file-holders = Abierto por
+section-connections = Conexiones
+section-port-owners = Sockets en el puerto
+section-self-stats = Sobrecarga de la herramienta
+section-profile-collection = Tiempos de recogida
+section-capabilities = Capacidades
+section-processes = Procesos
+section-bench = Pruebas de rendimiento
+section-thermal = Térmica
+section-throttle-summary = Resumen de estrangulamiento
+section-watch-summary = Resumen de la observación
+section-kill = Señales
+section-process = Proceso
+section-environment = Entorno
+section-threads = Hilos
+section-users = Usuarios
+section-sessions = Sesiones
+section-history = Historial
+section-diff = Cambios
+section-disk-changes = Espacio en disco
+section-started = Procesos iniciados
+section-exited = Procesos terminados
+
+# Labels
+system-name = Nombre del sistema:
+kernel-version = Versión del kernel:
+os-version = Versión del SO:
+host-name = Nombre del host:
+uptime = Tiempo activo:
+boot-time = Hora de arranque:
+taken-at = Tomada el:
+diff-from = Desde:
+diff-to = Hasta:
+load-average = Carga media:
+load-per-cpu = Carga por CPU:
+load-threshold = Umbral:
+load-exceeded = SUPERADO
+load-ok = OK
+total-cpus = CPUs totales:
+physical-cores = Núcleos físicos:
+sockets = Zócalos:
+cpu-vendor = Fabricante:
+cpu-brand = Modelo:
+nb-cpus = N.º de CPUs:
+cpu-scale = CPU %:
+cpu-scale-per-core = de un núcleo (puede superar el 100 %)
+cpu-scale-total = de todas las CPU
+global-usage = Uso global:
+cpu-time-split = Reparto del tiempo:
+time-user = usuario
+time-system = sistema
+time-iowait = espera de E/S
+time-irq = IRQ
+time-steal = robado
+core-voltage = Voltaje del núcleo:
+total-cpu-usage = Uso total de CPU:
+total-memory = Memoria total:
+used-memory = Memoria usada:
+available-memory = Memoria disponible:
+free-memory = Memoria libre:
+buffers = Búferes:
+cached = Caché:
+cgroup-memory = Memoria del cgroup:
+cgroup-rss = RSS del cgroup:
+total-swap = Swap total:
+used-swap = Swap usado:
+scanned-path = Ruta:
+total-size = Tamaño total:
+total-files = Archivos:
+skipped-unreadable = Entradas ilegibles:
+file-handles = Descriptores de archivo:
+epoll-instances = Instancias de epoll:
+inotify-instances = Instancias de inotify:
+inotify-max-instances = Máx. instancias de inotify:
+inotify-max-watches = Máx. vigilancias de inotify:
+epoll-max-watches = Máx. vigilancias de epoll:
+no-file-holders = Ningún proceso lo tiene abierto.
+no-connections = Ningún socket coincide.
+no-port-owners = Nada está usando este puerto.
+no-containers = No hay contenedores en ejecución.
+no-pod-processes = No hay procesos en pods de Kubernetes.
+no-sensors = No se encontraron sensores de hardware.
+no-sessions = No hay sesiones activas.
+no-history = No hay muestras de esta métrica en ese periodo.
+no-changes = Sin cambios.
+diff-rebooted = El sistema se reinició entre las instantáneas.
+deleted = eliminado
+iterations = Iteraciones:
+cpu-time = Tiempo de CPU:
+peak-memory = Memoria máxima:
+status-supported = compatible
+status-unsupported = no compatible
+status-needs-privilege = requiere privilegios
+outcome-matched = encontrado (no enviado)
+outcome-sent = enviada
+outcome-failed = fallida
+outcome-unsupported = no disponible aquí
+alert-ok = OK: todos los umbrales cumplidos
+alert-critical = CRITICAL: disparados:
+alert-unknown = UNKNOWN: no legibles:
+alert-status-ok = ok
+alert-status-fired = DISPARADO
+alert-status-unknown = desconocido
+scanning = Analizando…
+scanned-files = archivos
+committed-memory = Memoria confirmada:
+commit-limit = Límite de confirmación:
+container-runtime = Entorno:
+not-in-container = ninguno (fuera de un contenedor)
+container-id = ID del contenedor:
+cgroup = Cgroup:
+cpu-limit = Límite de CPU:
+cpus-used = CPU en uso:
+memory-limit = Límite de memoria:
+memory-used = Memoria en uso:
+unlimited = sin límite
+not-available = N/D
+cpu-frequency = Frecuencia de CPU:
+peak = pico
+temperature = Temperatura:
+critical = crítica
+throttling = Estrangulamiento:
+throttle-under-voltage = subtensión
+throttle-freq-capped = frecuencia limitada
+throttle-throttled = limitado
+throttle-soft-temp-limit = límite suave de temperatura
+since-boot = desde el arranque
+event-started = iniciado
+event-exited = terminado
+event-parent = padre
+event-ran-for = duró
+event-disk-attached = disco conectado
+event-disk-detached = disco desconectado
+event-interface-added = interfaz añadida
+event-interface-removed = interfaz eliminada
+event-power-changed = alimentación cambiada
+yes = sí
+no = no
+samples = Muestras:
+elapsed = Duración:
+metric-cpu-usage = Uso de CPU
+metric-memory-used = Memoria usada
+network-received = Red recibido:
+network-transmitted = Red enviado:
+fetch-os = SO
+fetch-kernel = Kernel
+fetch-uptime = Tiempo activo
+fetch-cpu = CPU
+fetch-memory = Memoria
+fetch-disk = Disco
+throttled-samples = Muestras estranguladas:
+frequency-range = Rango de frecuencia:
+process-pid = PID:
+process-name = Nombre:
+process-cmd = Línea de órdenes:
+process-exe = Ejecutable:
+process-cwd = Directorio de trabajo:
+process-parent = PID padre:
+process-status = Estado:
+process-user = Usuario:
+process-started = Iniciado:
+process-run-time = Tiempo de ejecución:
+process-cpu = Uso de CPU:
+process-memory = Memoria:
+process-virtual-memory = Memoria virtual:
+process-disk-read = Leído del disco:
+process-disk-written = Escrito en disco:
+process-threads = Hilos:
+max-temperature = Temperatura máx.:
+
+# Table headers
+col-id = ID
+col-usage-pct = Uso %
+col-avg-pct = Media %
+col-peak-pct = Pico %
+col-user-pct = Usuario %
+col-system-pct = Sistema %
+col-iowait-pct = Espera E/S %
+col-irq-pct = IRQ %
+col-steal-pct = Robado %
+col-vendor = Fabricante
+col-brand = Modelo
+col-mhz = MHz
+col-min-mhz = MHz mín.
+col-max-mhz = MHz máx.
+col-socket = Zócalo
+col-core = Núcleo
+col-name = Nombre
+col-kind = Tipo
+col-fs = SA
+col-mount = Punto de montaje
+col-removable = Extraíble
+col-read-only = Solo lectura
+col-available = Disponible
+col-total = Total
+col-used-pct = Usado %
+col-change-per-min = Cambio/min
+col-full-in = Lleno en
+col-read = Leído
+col-written = Escrito
+col-read-per-sec = Leído/s
+col-written-per-sec = Escrito/s
+col-path = Ruta
+col-size = Tamaño
+col-share-pct = Porción %
+col-files = Archivos
+col-protocol = Protocolo
+col-state = Estado
+col-local-address = Dirección local
+col-remote-address = Dirección remota
+col-count = Cantidad
+col-held-as = Acceso
+col-section = Sección
+col-samples = Muestras
+col-avg-ms = Prom. ms
+col-max-ms = Máx. ms
+col-stage = Etapa
+col-items = Elementos
+col-first-ms = Primera ms
+col-refreshes = Actualizaciones
+col-metric = Métrica
+col-status = Estado
+col-image = Imagen
+col-net-io = E/S de red
+col-block-io = E/S de bloques
+col-pids = PID
+col-cpu-cores = CPU (núcleos)
+col-memory-bytes = Memoria (bytes)
+col-pod = Pod
+col-container = Contenedor
+col-qos = QoS
+col-detail = Detalle
+col-queue = Cola
+col-namespace = Espacio de nombres
+col-interface = Interfaz
+col-mac = Dirección MAC
+col-mtu = MTU
+col-addresses = Direcciones
+col-packets = Paquetes (rx / tx)
+col-errors = Errores (rx / tx)
+col-drops = Descartes (rx / tx)
+col-received-delta = Δ Recibido
+col-transmitted-delta = Δ Transmitido
+col-packets-delta = Δ Paquetes (rx / tx)
+col-errors-delta = Δ Errores (rx / tx)
+col-received = Recibido
+col-transmitted = Transmitido
+col-received-per-sec = Recibido/s
+col-transmitted-per-sec = Transmitido/s
+col-label = Etiqueta
+col-temp = Temp.
+col-max = Máx.
+col-min = Mín.
+col-avg = Media
+col-p95 = P95
+col-pct-of-max = % del máx.
+col-critical = Crítica
+col-pid = PID
+col-tid = TID
+col-cpu-pct = CPU %
+col-memory = Memoria
+col-run-time = Tiempo de ejecución
+col-handles = Identificadores
+col-benchmark = Prueba
+col-result = Resultado
+col-time = Tiempo
+col-signal = Señal
+col-outcome = Resultado
+col-uid = UID
+col-gid = GID
+col-groups = Grupos
+col-sessions = Sesiones
+col-user = Usuario
+col-tty = TTY
+col-host = Equipo
+col-address = Dirección
+col-load = Carga
+col-disk-alerts = Discos llenos
+col-login = Inicio de sesión
+col-rule = Regla
+col-target = Destino
+col-value = Valor
+col-before = Antes
+col-after = Después
+col-change = Cambio
+col-mem-pct = Mem. %
+col-virtual = Virtual
+col-started = Inicio
+col-threads = Hilos
+col-command = Comando
+
+# Benchmark names
+bench-cpu-single = Hash de CPU (1 hilo)
+bench-cpu-multi = Hash de CPU (todos los hilos)
+bench-memory-copy = Copia de memoria
+bench-disk-seq-write = Escritura secuencial en disco
+bench-disk-seq-read = Lectura secuencial de disco
+bench-disk-seq-read-cached = Lectura secuencial de disco (en caché)
+bench-disk-rand-read = Lectura aleatoria 4K de disco
+bench-disk-rand-read-cached = Lectura aleatoria 4K de disco (en caché)
+bench-disk-rand-write = Escritura aleatoria 4K en disco
+
+# Vista interactiva
+tui-cpu = CPU
+tui-memory = Memoria
+tui-swap = Intercambio
+tui-filter = Filtro:
+tui-help = q salir · ↑↓ RePág AvPág desplazar · c/m/p/n ordenar · / filtrar · k terminar
+tui-confirm-kill = ¿Terminar
+tui-signal-sent = Solicitud de terminación enviada a
+tui-signal-failed = No se pudo terminar
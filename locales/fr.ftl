@@ -0,0 +1,330 @@
+# Section titles
+section-summary-system = Résumé du système
+section-summary-memory = Résumé de la mémoire
+section-summary-cpu = Résumé du processeur
+section-system = Système
+section-memory = Mémoire
+section-cpus = Processeurs
+section-load = Charge
+section-disks = Disques
+section-disk-usage = Utilisation du disque
+section-networks = Réseaux
+section-components = Composants
+section-container = Conteneur
+section-docker = Conteneurs Docker
+section-k8s-node = Nœud Kubernetes
+section-pod-processes = Processus des pods
+section-hosts = Hôtes
+section-sensors = Capteurs
+section-kernel-resources = Ressources du noyau
+section-file-holders = Ouvert par
+section-connections = Connexions
+section-port-owners = Sockets sur le port
+section-self-stats = Surcoût de l'outil
+section-profile-collection = Temps de collecte
+section-capabilities = Capacités
+section-processes = Processus
+section-bench = Bancs d'essai
+section-thermal = Thermique
+section-throttle-summary = Bilan du bridage
+section-watch-summary = Résumé de la surveillance
+section-kill = Signaux
+section-process = Processus
+section-environment = Environnement
+section-threads = Threads
+section-users = Utilisateurs
+section-sessions = Sessions
+section-history = Historique
+section-diff = Modifications
+section-disk-changes = Espace disque
+section-started = Processus démarrés
+section-exited = Processus terminés
+
+# Labels
+system-name = Nom du système :
+kernel-version = Version du noyau :
+os-version = Version de l'OS :
+host-name = Nom d'hôte :
+uptime = Temps de fonctionnement :
+boot-time = Heure de démarrage :
+taken-at = Prise le :
+diff-from = De :
+diff-to = À :
+load-average = Charge moyenne :
+load-per-cpu = Charge par processeur :
+load-threshold = Seuil :
+load-exceeded = DÉPASSÉ
+load-ok = OK
+total-cpus = Nombre de CPU :
+physical-cores = Cœurs physiques :
+sockets = Sockets :
+cpu-vendor = Fabricant :
+cpu-brand = Modèle :
+nb-cpus = Nombre de CPU :
+cpu-scale = CPU % :
+cpu-scale-per-core = d'un cœur (peut dépasser 100 %)
+cpu-scale-total = de tous les CPU
+global-usage = Utilisation globale :
+cpu-time-split = Répartition du temps :
+time-user = utilisateur
+time-system = système
+time-iowait = attente E/S
+time-irq = IRQ
+time-steal = volé
+core-voltage = Tension du cœur :
+total-cpu-usage = Utilisation CPU totale :
+total-memory = Mémoire totale :
+used-memory = Mémoire utilisée :
+available-memory = Mémoire disponible :
+free-memory = Mémoire libre :
+buffers = Tampons :
+cached = Cache :
+cgroup-memory = Mémoire du cgroup :
+cgroup-rss = RSS du cgroup :
+total-swap = Swap total :
+used-swap = Swap utilisé :
+scanned-path = Chemin :
+total-size = Taille totale :
+total-files = Fichiers :
+skipped-unreadable = Entrées illisibles :
+file-handles = Descripteurs de fichiers :
+epoll-instances = Instances epoll :
+inotify-instances = Instances inotify :
+inotify-max-instances = Instances inotify max :
+inotify-max-watches = Surveillances inotify max :
+epoll-max-watches = Surveillances epoll max :
+no-file-holders = Aucun processus ne l'a ouvert.
+no-connections = Aucun socket correspondant.
+no-port-owners = Rien n'utilise ce port.
+no-containers = Aucun conteneur en cours d’exécution.
+no-pod-processes = Aucun processus dans des pods Kubernetes.
+no-sensors = Aucun capteur matériel trouvé.
+no-sessions = Aucune session active.
+no-history = Aucun échantillon enregistré pour cette métrique sur cette période.
+no-changes = Aucune modification.
+diff-rebooted = Le système a redémarré entre les instantanés.
+deleted = supprimé
+iterations = Itérations :
+cpu-time = Temps CPU :
+peak-memory = Mémoire maximale :
+status-supported = pris en charge
+status-unsupported = non pris en charge
+status-needs-privilege = nécessite des privilèges
+outcome-matched = trouvé (non envoyé)
+outcome-sent = envoyé
+outcome-failed = échec
+outcome-unsupported = non pris en charge ici
+alert-ok = OK : tous les seuils respectés
+alert-critical = CRITICAL : déclenchés :
+alert-unknown = UNKNOWN : illisibles :
+alert-status-ok = ok
+alert-status-fired = DÉCLENCHÉ
+alert-status-unknown = inconnu
+scanning = Analyse…
+scanned-files = fichiers
+committed-memory = Mémoire validée :
+commit-limit = Limite de validation :
+container-runtime = Environnement :
+not-in-container = aucun (hors conteneur)
+container-id = ID du conteneur :
+cgroup = Cgroup :
+cpu-limit = Limite CPU :
+cpus-used = CPU utilisés :
+memory-limit = Limite mémoire :
+memory-used = Mémoire utilisée (cgroup) :
+unlimited = illimité
+not-available = N/D
+cpu-frequency = Fréquence CPU :
+peak = pic
+temperature = Température :
+critical = critique
+throttling = Bridage :
+throttle-under-voltage = sous-tension
+throttle-freq-capped = fréquence plafonnée
+throttle-throttled = bridé
+throttle-soft-temp-limit = limite de température douce
+since-boot = depuis le démarrage
+event-started = démarré
+event-exited = terminé
+event-parent = parent
+event-ran-for = a duré
+event-disk-attached = disque connecté
+event-disk-detached = disque déconnecté
+event-interface-added = interface ajoutée
+event-interface-removed = interface retirée
+event-power-changed = alimentation modifiée
+yes = oui
+no = non
+samples = Échantillons :
+elapsed = Durée :
+metric-cpu-usage = Utilisation CPU
+metric-memory-used = Mémoire utilisée
+network-received = Réseau reçu :
+network-transmitted = Réseau envoyé :
+fetch-os = OS
+fetch-kernel = Noyau
+fetch-uptime = Temps de fonctionnement
+fetch-cpu = CPU
+fetch-memory = Mémoire
+fetch-disk = Disque
+throttled-samples = Échantillons bridés :
+frequency-range = Plage de fréquence :
+process-pid = PID :
+process-name = Nom :
+process-cmd = Ligne de commande :
+process-exe = Exécutable :
+process-cwd = Répertoire de travail :
+process-parent = PID parent :
+process-status = État :
+process-user = Utilisateur :
+process-started = Démarré :
+process-run-time = Durée d'exécution :
+process-cpu = Utilisation CPU :
+process-memory = Mémoire :
+process-virtual-memory = Mémoire virtuelle :
+process-disk-read = Lu sur disque :
+process-disk-written = Écrit sur disque :
+process-threads = Threads :
+max-temperature = Température max. :
+
+# Table headers
+col-id = ID
+col-usage-pct = Utilisation %
+col-avg-pct = Moy. %
+col-peak-pct = Pic %
+col-user-pct = Utilisateur %
+col-system-pct = Système %
+col-iowait-pct = Attente E/S %
+col-irq-pct = IRQ %
+col-steal-pct = Volé %
+col-vendor = Fabricant
+col-brand = Modèle
+col-mhz = MHz
+col-min-mhz = MHz min.
+col-max-mhz = MHz max.
+col-socket = Socket
+col-core = Cœur
+col-name = Nom
+col-kind = Type
+col-fs = SF
+col-mount = Point de montage
+col-removable = Amovible
+col-read-only = Lecture seule
+col-available = Disponible
+col-total = Total
+col-used-pct = Utilisé %
+col-change-per-min = Variation/min
+col-full-in = Plein dans
+col-read = Lu
+col-written = Écrit
+col-read-per-sec = Lu/s
+col-written-per-sec = Écrit/s
+col-path = Chemin
+col-size = Taille
+col-share-pct = Part %
+col-files = Fichiers
+col-protocol = Protocole
+col-state = État
+col-local-address = Adresse locale
+col-remote-address = Adresse distante
+col-count = Nombre
+col-held-as = Accès
+col-section = Section
+col-samples = Mesures
+col-avg-ms = Moy. ms
+col-max-ms = Max ms
+col-stage = Étape
+col-items = Éléments
+col-first-ms = Première ms
+col-refreshes = Rafraîchissements
+col-metric = Métrique
+col-status = État
+col-image = Image
+col-net-io = E/S réseau
+col-block-io = E/S bloc
+col-pids = PID
+col-cpu-cores = CPU (cœurs)
+col-memory-bytes = Mémoire (octets)
+col-pod = Pod
+col-container = Conteneur
+col-qos = QoS
+col-detail = Détail
+col-queue = File d'attente
+col-namespace = Espace de noms
+col-interface = Interface
+col-mac = Adresse MAC
+col-mtu = MTU
+col-addresses = Adresses
+col-packets = Paquets (rx / tx)
+col-errors = Erreurs (rx / tx)
+col-drops = Rejets (rx / tx)
+col-received-delta = Δ Reçu
+col-transmitted-delta = Δ Émis
+col-packets-delta = Δ Paquets (rx / tx)
+col-errors-delta = Δ Erreurs (rx / tx)
+col-received = Reçu
+col-transmitted = Émis
+col-received-per-sec = Reçu/s
+col-transmitted-per-sec = Émis/s
+col-label = Libellé
+col-temp = Temp.
+col-max = Max
+col-min = Min
+col-avg = Moy.
+col-p95 = P95
+col-pct-of-max = % du max.
+col-critical = Critique
+col-pid = PID
+col-tid = TID
+col-cpu-pct = CPU %
+col-memory = Mémoire
+col-run-time = Durée
+col-handles = Handles
+col-benchmark = Test
+col-result = Résultat
+col-time = Durée
+col-signal = Signal
+col-outcome = Résultat
+col-uid = UID
+col-gid = GID
+col-groups = Groupes
+col-sessions = Sessions
+col-user = Utilisateur
+col-tty = TTY
+col-host = Hôte
+col-address = Adresse
+col-load = Charge
+col-disk-alerts = Disques pleins
+col-login = Connexion
+col-rule = Règle
+col-target = Cible
+col-value = Valeur
+col-before = Avant
+col-after = Après
+col-change = Variation
+col-mem-pct = Mém. %
+col-virtual = Virtuelle
+col-started = Démarré
+col-threads = Threads
+col-command = Commande
+
+# Benchmark names
+bench-cpu-single = Hachage CPU (1 thread)
+bench-cpu-multi = Hachage CPU (tous les threads)
+bench-memory-copy = Copie mémoire
+bench-disk-seq-write = Écriture séquentielle disque
+bench-disk-seq-read = Lecture séquentielle disque
+bench-disk-seq-read-cached = Lecture séquentielle disque (en cache)
+bench-disk-rand-read = Lecture aléatoire 4K disque
+bench-disk-rand-read-cached = Lecture aléatoire 4K disque (en cache)
+bench-disk-rand-write = Écriture aléatoire 4K disque
+
+# Vue interactive
+tui-cpu = CPU
+tui-memory = Mémoire
+tui-swap = Swap
+tui-filter = Filtre :
+tui-help = q quitter · ↑↓ PgPréc PgSuiv défiler · c/m/p/n trier · / filtrer · k terminer
+tui-confirm-kill = Terminer
+tui-signal-sent = Demande d'arrêt envoyée à
+tui-signal-failed = Impossible de terminer
@@ -0,0 +1,310 @@
+//! `agent` and `query`: snapshots served over TCP or a Unix socket, so one machine can
+//! poll many without logging into each.
+//!
+//! The protocol is one JSON object per line. The client sends a [`Request`], and the
+//! agent answers with the versioned [`Snapshot`], or `{"error": "..."}`. A connection
+//! may carry any number of requests. Over TCP the exchange can run inside TLS, and the
+//! agent can demand a shared token.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName, pem::PemObject};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sysinfo::System;
+use crate::args::{AgentAddr, Commands};
+use crate::collector::{init_system, refresh_system};
+use crate::fmt::versioned;
+use crate::models::Snapshot;
+use crate::redact::{redact, Redactor};
+use crate::snapshot;
+
+/// Longest request line the agent reads; requests are a few dozen bytes.
+const MAX_REQUEST: u64 = 64 * 1024;
+/// How long `query` waits for an answer.
+pub const TIMEOUT: Duration = Duration::from_secs(300);
+/// How long an agent keeps a connection that sends nothing. `query` and the `--host`
+/// dashboard connect for each request, so only stuck or idle clients hit it.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+/// Most connections an agent serves at once, each on its own thread; more are closed
+/// right away.
+pub const MAX_CONNECTIONS: usize = 64;
+
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+pub struct Request {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Only the `top` busiest processes, instead of all of them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top: Option<usize>,
+}
+
+/// Compares tokens in time that doesn't depend on where they differ.
+pub fn tokens_match(expected: &str, given: &str) -> bool {
+    expected.len() == given.len() && expected.bytes().zip(given.bytes()).fold(0, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+/// The answer to one request line: `snapshot` is called with the request's `top` once
+/// the token checks out.
+pub fn respond(line: &str, token: Option<&str>, snapshot: impl FnOnce(Option<usize>) -> io::Result<Value>) -> Value {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return json!({"error": format!("invalid request: {}", e)}),
+    };
+    if let Some(expected) = token
+        && !request.token.as_deref().is_some_and(|given| tokens_match(expected, given))
+    {
+        return json!({"error": "unauthorized"});
+    }
+    snapshot(request.top).unwrap_or_else(|e| json!({"error": e.to_string()}))
+}
+
+/// TLS settings for an agent from a PEM certificate chain and private key.
+pub fn server_tls(cert: &Path, key: &Path) -> io::Result<Arc<rustls::ServerConfig>> {
+    let pem_error = |path: &Path, e: rustls::pki_types::pem::Error| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e));
+    let certs = CertificateDer::pem_file_iter(cert)
+        .and_then(|certs| certs.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| pem_error(cert, e))?;
+    let key = PrivateKeyDer::from_pem_file(key).map_err(|e| pem_error(key, e))?;
+    let config = rustls::ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    Ok(Arc::new(config))
+}
+
+/// TLS settings for `query`, trusting the certificates in `ca`, or else the usual web
+/// roots.
+pub fn client_tls(ca: Option<&Path>) -> io::Result<Arc<rustls::ClientConfig>> {
+    let mut roots = rustls::RootCertStore::empty();
+    match ca {
+        Some(ca) => {
+            let invalid = |e: &dyn std::fmt::Display| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", ca.display(), e));
+            for cert in CertificateDer::pem_file_iter(ca).map_err(|e| invalid(&e))? {
+                roots.add(cert.map_err(|e| invalid(&e))?).map_err(|e| invalid(&e))?;
+            }
+        }
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+    let config = rustls::ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+        .with_safe_default_protocol_versions()
+        .map_err(io::Error::other)?
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    Ok(Arc::new(config))
+}
+
+trait Stream: Read + Write + Send {}
+impl<T: Read + Write + Send> Stream for T {}
+
+/// The machine's state, kept between requests so CPU usage covers the time since the last.
+struct State<'a> {
+    sys: System,
+    redactor: Option<&'a mut Redactor>,
+}
+
+impl State<'_> {
+    fn snapshot(&mut self, top: Option<usize>) -> io::Result<Value> {
+        refresh_system(&mut self.sys, &snapshot_command());
+        let mut info = snapshot::take(&self.sys);
+        if let Some(top) = top {
+            info.processes.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage));
+            info.processes.truncate(top);
+        }
+        redact(&mut info, self.redactor.as_deref_mut());
+        versioned(&info, None).map_err(io::Error::other)
+    }
+}
+
+fn snapshot_command() -> Option<Commands> {
+    Some(Commands::Snapshot { path: None })
+}
+
+/// One of the [`MAX_CONNECTIONS`] an agent serves at once, given back when dropped.
+pub struct Slot<'a>(&'a AtomicUsize);
+
+impl<'a> Slot<'a> {
+    /// A slot counted in `active`, unless all are taken.
+    pub fn take(active: &'a AtomicUsize) -> Option<Self> {
+        active.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| (n < MAX_CONNECTIONS).then_some(n + 1)).ok()?;
+        Some(Slot(active))
+    }
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Removes a socket left at `path` by an agent that died, so a new one can bind there.
+/// A live socket still answers and is kept, and anything other than a socket is an
+/// error rather than deleted.
+#[cfg(unix)]
+pub fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixStream;
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} exists and isn't a socket", path.display())));
+    }
+    if UnixStream::connect(path).is_err() {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Serves snapshots until the process is stopped, each connection on its own thread,
+/// up to [`MAX_CONNECTIONS`] at once.
+/// Without `token`, anyone who can reach the address can read them.
+pub fn run(listen: &AgentAddr, token: Option<&str>, tls: Option<Arc<rustls::ServerConfig>>, sample: Option<Duration>, redactor: Option<&mut Redactor>) -> io::Result<()> {
+    let state = Mutex::new(State { sys: init_system(&snapshot_command(), sample), redactor });
+    let active = AtomicUsize::new(0);
+    let slot = || {
+        let slot = Slot::take(&active);
+        if slot.is_none() {
+            eprintln!("Closing a connection: {} are already open", MAX_CONNECTIONS);
+        }
+        slot
+    };
+    let serve = &|stream: io::Result<Box<dyn Stream>>| {
+        if let Err(e) = stream.and_then(|stream| handle(stream, token, &state)) {
+            eprintln!("Error handling connection: {}", e);
+        }
+    };
+    std::thread::scope(|scope| match listen {
+        AgentAddr::Tcp { host, port } => {
+            let listener = TcpListener::bind((host.as_str(), *port))?;
+            eprintln!("Serving snapshots on {}{}", listener.local_addr()?, if tls.is_some() { " over TLS" } else { "" });
+            for stream in listener.incoming() {
+                let Some(slot) = slot() else { continue };
+                let tls = tls.clone();
+                scope.spawn(move || {
+                    let _slot = slot;
+                    serve(stream.and_then(|stream| {
+                        stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+                        Ok(match tls {
+                            Some(tls) => Box::new(rustls::StreamOwned::new(rustls::ServerConnection::new(tls).map_err(io::Error::other)?, stream)) as Box<dyn Stream>,
+                            None => Box::new(stream),
+                        })
+                    }))
+                });
+            }
+            Ok(())
+        }
+        AgentAddr::Unix(path) => {
+            if tls.is_some() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "TLS is only for TCP; a Unix socket is guarded by its file permissions"));
+            }
+            #[cfg(unix)]
+            {
+                remove_stale_socket(path)?;
+                let listener = std::os::unix::net::UnixListener::bind(path)?;
+                eprintln!("Serving snapshots on {}", path.display());
+                for stream in listener.incoming() {
+                    let Some(slot) = slot() else { continue };
+                    scope.spawn(move || {
+                        let _slot = slot;
+                        serve(stream.and_then(|stream| {
+                            stream.set_read_timeout(Some(IDLE_TIMEOUT))?;
+                            Ok(Box::new(stream) as Box<dyn Stream>)
+                        }))
+                    });
+                }
+                Ok(())
+            }
+            #[cfg(not(unix))]
+            {
+                let _ = path;
+                Err(io::Error::new(io::ErrorKind::Unsupported, "Unix sockets are only available on Unix"))
+            }
+        }
+    })
+}
+
+fn handle(stream: Box<dyn Stream>, token: Option<&str>, state: &Mutex<State>) -> io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    loop {
+        let mut line = String::new();
+        match reader.by_ref().take(MAX_REQUEST).read_line(&mut line) {
+            Ok(0) => return Ok(()),
+            Ok(_) => {}
+            // TLS clients often hang up without a close_notify.
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e),
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let answer = respond(&line, token, |top| state.lock().unwrap_or_else(|e| e.into_inner()).snapshot(top));
+        let stream = reader.get_mut();
+        writeln!(stream, "{}", answer)?;
+        stream.flush()?;
+    }
+}
+
+/// Connects to `addr`, inside TLS when `tls` is given, waiting up to `timeout` for the
+/// connection and then for each read.
+fn connect(addr: &AgentAddr, tls: Option<Arc<rustls::ClientConfig>>, timeout: Duration) -> io::Result<Box<dyn Stream>> {
+    match addr {
+        AgentAddr::Tcp { host, port } => {
+            let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("{} has no address", host));
+            let stream = (host.as_str(), *port).to_socket_addrs()?.find_map(|addr| {
+                TcpStream::connect_timeout(&addr, timeout).map_err(|e| last_error = e).ok()
+            }).ok_or(last_error)?;
+            stream.set_read_timeout(Some(timeout))?;
+            Ok(match tls {
+                Some(tls) => {
+                    let name = ServerName::try_from(host.clone()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                    Box::new(rustls::StreamOwned::new(rustls::ClientConnection::new(tls, name).map_err(io::Error::other)?, stream))
+                }
+                None => Box::new(stream),
+            })
+        }
+        AgentAddr::Unix(path) => {
+            if tls.is_some() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, "TLS is only for TCP agents"));
+            }
+            #[cfg(unix)]
+            {
+                let stream = std::os::unix::net::UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(timeout))?;
+                Ok(Box::new(stream))
+            }
+            #[cfg(not(unix))]
+            {
+                let _ = path;
+                Err(io::Error::new(io::ErrorKind::Unsupported, "Unix sockets are only available on Unix"))
+            }
+        }
+    }
+}
+
+/// Fetches a snapshot from the agent at `addr`.
+pub fn query(addr: &AgentAddr, token: Option<&str>, tls: Option<Arc<rustls::ClientConfig>>, top: Option<usize>, timeout: Duration) -> io::Result<Snapshot> {
+    let mut reader = BufReader::new(connect(addr, tls, timeout)?);
+    let request = Request { token: token.map(str::to_string), top };
+    let stream = reader.get_mut();
+    writeln!(stream, "{}", serde_json::to_string(&request)?)?;
+    stream.flush()?;
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "the agent closed the connection"));
+    }
+    let answer: Value = serde_json::from_str(&line)?;
+    if let Some(error) = answer.get("error").and_then(Value::as_str) {
+        let kind = if error == "unauthorized" { io::ErrorKind::PermissionDenied } else { io::ErrorKind::Other };
+        return Err(io::Error::new(kind, format!("the agent answered: {}", error)));
+    }
+    Ok(serde_json::from_value(answer)?)
+}
@@ -0,0 +1,211 @@
+//! Threshold checks for `alert`, e.g. `cpu > 90` or `disk_avail@/ < 10GiB`, with exit
+//! codes that monitoring plugins (Nagios, Icinga) understand.
+
+use std::fs;
+use std::path::Path;
+use sysinfo::{Disks, System};
+use crate::collector::{get_components_info, get_load_average};
+use crate::models::{AlertResult, AlertStatus};
+
+/// Metrics a rule can test, and whether their thresholds are byte sizes.
+pub const METRICS: &[(&str, bool)] = &[
+    ("cpu", false),
+    ("mem_used_pct", false),
+    ("swap_used_pct", false),
+    ("disk_used_pct", false),
+    ("disk_avail", true),
+    ("load1", false),
+    ("load5", false),
+    ("load15", false),
+    ("temp", false),
+];
+
+const OPERATORS: &[&str] = &[">=", "<=", "==", "!=", ">", "<"];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    /// The rule as written, used to report it.
+    pub text: String,
+    pub metric: &'static str,
+    /// Mount point for the disk metrics (`disk_avail@/home`); all disks when absent.
+    pub mount: Option<String>,
+    pub op: &'static str,
+    pub threshold: f64,
+}
+
+impl Rule {
+    fn fires(&self, value: f64) -> bool {
+        match self.op {
+            ">=" => value >= self.threshold,
+            "<=" => value <= self.threshold,
+            "==" => value == self.threshold,
+            "!=" => value != self.threshold,
+            ">" => value > self.threshold,
+            _ => value < self.threshold,
+        }
+    }
+}
+
+/// Parses `METRIC[@MOUNT] OP VALUE`, where VALUE may carry a `%` or a size unit
+/// (`10GiB`, `500MB`).
+pub fn parse_rule(text: &str) -> Result<Rule, String> {
+    let text = text.trim();
+    let (at, op) = OPERATORS.iter()
+        .filter_map(|op| text.find(op).map(|at| (at, *op)))
+        // The earliest match wins, and `>=` is listed before `>` so it wins a tie.
+        .min_by_key(|(at, _)| *at)
+        .ok_or_else(|| format!("`{}`: expected a comparison such as `cpu > 90`", text))?;
+    let (name, value) = (text[..at].trim(), text[at + op.len()..].trim());
+    let (metric, mount) = parse_metric(name).map_err(|e| format!("`{}`: {}", text, e))?;
+    let threshold = parse_value(value, is_bytes(metric)).ok_or_else(|| format!("`{}`: invalid value `{}`", text, value))?;
+    Ok(Rule { text: text.to_string(), metric, mount, op, threshold })
+}
+
+/// Parses `METRIC[@MOUNT]`, the left-hand side of a rule.
+pub fn parse_metric(name: &str) -> Result<(&'static str, Option<String>), String> {
+    let (name, mount) = match name.trim().split_once('@') {
+        Some((name, mount)) => (name, Some(mount.to_string())),
+        None => (name.trim(), None),
+    };
+    let &(metric, _) = METRICS.iter()
+        .find(|(m, _)| *m == name)
+        .ok_or_else(|| format!("unknown metric `{}`", name))?;
+    if mount.is_some() && !metric.starts_with("disk_") {
+        return Err("only disk metrics take a mount point".to_string());
+    }
+    Ok((metric, mount))
+}
+
+fn parse_value(value: &str, bytes: bool) -> Option<f64> {
+    let end = value.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-')).unwrap_or(value.len());
+    let number: f64 = value[..end].parse().ok()?;
+    let unit = value[end..].trim();
+    let factor = match unit.to_ascii_lowercase().as_str() {
+        "" => 1.0,
+        "%" if !bytes => 1.0,
+        "b" if bytes => 1.0,
+        "k" | "kib" if bytes => 1024.0,
+        "m" | "mib" if bytes => 1024.0_f64.powi(2),
+        "g" | "gib" if bytes => 1024.0_f64.powi(3),
+        "t" | "tib" if bytes => 1024.0_f64.powi(4),
+        "kb" if bytes => 1e3,
+        "mb" if bytes => 1e6,
+        "gb" if bytes => 1e9,
+        "tb" if bytes => 1e12,
+        _ => return None,
+    };
+    Some(number * factor)
+}
+
+/// Reads one rule per line; blank lines and lines starting with `#` are skipped.
+pub fn load_rules(path: &Path) -> Result<Vec<Rule>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_rule)
+        .collect()
+}
+
+/// Current values of every metric a rule can reference.
+#[derive(Debug, Default)]
+pub struct Readings {
+    pub cpu: f64,
+    pub mem_used_pct: Option<f64>,
+    pub swap_used_pct: Option<f64>,
+    /// Mount point, available bytes, and total bytes.
+    pub disks: Vec<(String, u64, u64)>,
+    pub load: Option<(f64, f64, f64)>,
+    /// Hottest sensor, in °C.
+    pub temp: Option<f64>,
+}
+
+impl Readings {
+    /// The current value of `metric`. A disk metric without a `mount` reads the
+    /// fullest disk: the most used, or the least available.
+    pub fn value(&self, metric: &str, mount: Option<&str>) -> Option<f64> {
+        if metric.starts_with("disk_") {
+            let values = self.disks.iter()
+                .filter(|(m, ..)| mount.is_none_or(|mount| mount == m))
+                .filter_map(|(_, available, total)| disk_value(metric, *available, *total));
+            return if metric == "disk_avail" { values.reduce(f64::min) } else { values.reduce(f64::max) };
+        }
+        match metric {
+            "cpu" => Some(self.cpu),
+            "mem_used_pct" => self.mem_used_pct,
+            "swap_used_pct" => self.swap_used_pct,
+            "load1" => self.load.map(|l| l.0),
+            "load5" => self.load.map(|l| l.1),
+            "load15" => self.load.map(|l| l.2),
+            _ => self.temp,
+        }
+    }
+}
+
+fn disk_value(metric: &str, available: u64, total: u64) -> Option<f64> {
+    match metric {
+        "disk_avail" => Some(available as f64),
+        _ => (total > 0).then(|| (total - available) as f64 / total as f64 * 100.0),
+    }
+}
+
+pub fn get_readings(sys: &System) -> Readings {
+    let percent = |used: u64, total: u64| (total > 0).then(|| used as f64 / total as f64 * 100.0);
+    let disks = Disks::new_with_refreshed_list();
+    Readings {
+        cpu: sys.global_cpu_usage() as f64,
+        mem_used_pct: percent(sys.used_memory(), sys.total_memory()),
+        swap_used_pct: percent(sys.used_swap(), sys.total_swap()),
+        disks: disks.iter()
+            .map(|d| (d.mount_point().to_string_lossy().into_owned(), d.available_space(), d.total_space()))
+            .collect(),
+        load: get_load_average().map(|l| (l.one, l.five, l.fifteen)),
+        temp: get_components_info().iter().filter_map(|c| c.temperature).map(f64::from).reduce(f64::max),
+    }
+}
+
+/// Checks every rule; disk rules without a mount point yield one result per disk.
+pub fn evaluate(rules: &[Rule], readings: &Readings) -> Vec<AlertResult> {
+    let mut results = Vec::new();
+    for rule in rules {
+        let mut check = |target: Option<String>, value: Option<f64>| {
+            let status = match value {
+                Some(value) if rule.fires(value) => AlertStatus::Fired,
+                Some(_) => AlertStatus::Ok,
+                None => AlertStatus::Unknown,
+            };
+            results.push(AlertResult { rule: rule.text.clone(), metric: rule.metric.to_string(), target, value, status });
+        };
+        if rule.metric.starts_with("disk_") {
+            let disks: Vec<_> = readings.disks.iter()
+                .filter(|(mount, ..)| rule.mount.as_ref().is_none_or(|m| m == mount))
+                .collect();
+            if disks.is_empty() {
+                check(rule.mount.clone(), None);
+            }
+            for (mount, available, total) in disks {
+                check(Some(mount.clone()), disk_value(rule.metric, *available, *total));
+            }
+            continue;
+        }
+        check(None, readings.value(rule.metric, None));
+    }
+    results
+}
+
+/// Plugin exit status: 0 when every check passed, 2 (critical) when any fired, and
+/// 3 (unknown) when a metric couldn't be read.
+pub fn exit_code(results: &[AlertResult]) -> i32 {
+    if results.iter().any(|r| r.status == AlertStatus::Fired) {
+        2
+    } else if results.iter().any(|r| r.status == AlertStatus::Unknown) {
+        3
+    } else {
+        0
+    }
+}
+
+/// Whether `metric`'s values are byte counts.
+pub fn is_bytes(metric: &str) -> bool {
+    METRICS.iter().any(|(m, bytes)| *m == metric && *bytes)
+}
@@ -0,0 +1,120 @@
+use std::fs::{self, Metadata};
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::fmt::format_bytes;
+use crate::i18n::{localize_number, tr};
+use crate::models::{DirUsage, DiskUsageReport};
+
+/// How often the progress line on stderr is refreshed.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Walks `root` without leaving its filesystem and reports the `top` largest
+/// directories up to `depth` levels below it. Progress goes to stderr when it is a terminal.
+pub fn analyze(root: &Path, depth: usize, top: usize) -> io::Result<DiskUsageReport> {
+    let meta = fs::symlink_metadata(root)?;
+    if !meta.is_dir() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("{} is not a directory", root.display())));
+    }
+    let mut scan = Scan {
+        device: device(&meta),
+        depth,
+        directories: Vec::new(),
+        files: 0,
+        bytes: 0,
+        skipped: 0,
+        progress: io::stderr().is_terminal().then(Instant::now),
+    };
+    let (total_size, total_files) = scan.dir(root, 0);
+    if scan.progress.is_some() {
+        eprint!("\r\x1B[2K");
+    }
+
+    let mut directories = scan.directories;
+    directories.sort_by_key(|d| std::cmp::Reverse(d.size));
+    directories.truncate(top);
+    Ok(DiskUsageReport {
+        root: root.display().to_string(),
+        total_size,
+        total_files,
+        skipped: scan.skipped,
+        directories,
+    })
+}
+
+struct Scan {
+    device: Option<u64>,
+    depth: usize,
+    directories: Vec<DirUsage>,
+    files: u64,
+    bytes: u64,
+    skipped: u64,
+    progress: Option<Instant>,
+}
+
+impl Scan {
+    /// Returns the size and file count below `dir`, recording it when within `depth`.
+    fn dir(&mut self, dir: &Path, level: usize) -> (u64, u64) {
+        let Ok(entries) = fs::read_dir(dir) else {
+            self.skipped += 1;
+            return (0, 0);
+        };
+        let (mut size, mut files) = (0, 0);
+        for entry in entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                self.skipped += 1;
+                continue;
+            };
+            if meta.is_dir() {
+                if self.device.is_some() && device(&meta) != self.device {
+                    continue;
+                }
+                let (s, f) = self.dir(&entry.path(), level + 1);
+                size += s;
+                files += f;
+            } else {
+                let s = allocated_size(&meta);
+                size += s;
+                files += 1;
+                self.files += 1;
+                self.bytes += s;
+                self.report_progress();
+            }
+        }
+        if level >= 1 && level <= self.depth {
+            self.directories.push(DirUsage { path: dir.display().to_string(), depth: level, size, files });
+        }
+        (size, files)
+    }
+
+    fn report_progress(&mut self) {
+        if let Some(last) = self.progress && last.elapsed() >= PROGRESS_INTERVAL {
+            eprint!("\r\x1B[2K{} {} {}, {}", tr("scanning"), localize_number(&self.files.to_string()), tr("scanned-files"), format_bytes(self.bytes));
+            let _ = io::stderr().flush();
+            self.progress = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(unix)]
+fn device(meta: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device(_: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Space the file occupies on disk, so sparse files don't inflate the totals.
+#[cfg(unix)]
+fn allocated_size(meta: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    meta.blocks() * 512
+}
+
+#[cfg(not(unix))]
+fn allocated_size(meta: &Metadata) -> u64 {
+    meta.len()
+}
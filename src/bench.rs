@@ -0,0 +1,153 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::hint::black_box;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::models::BenchResult;
+
+const MIB: u64 = 1024 * 1024;
+const BLOCK: usize = 4096;
+
+/// Runs every micro-benchmark. Timed loops run for `duration`; disk tests use a
+/// scratch file of `size_mib` MiB in `dir`, removed afterwards.
+pub fn run_benchmarks(duration: Duration, dir: &Path, size_mib: u64) -> io::Result<Vec<BenchResult>> {
+    let mut results = vec![
+        bench_cpu("cpu_single", duration, 1),
+        bench_cpu("cpu_multi", duration, thread::available_parallelism().map(|n| n.get()).unwrap_or(1)),
+        bench_memory(duration),
+    ];
+    let path = dir.join(format!(".sysinfo-cli-bench-{}", std::process::id()));
+    let disk = bench_disk(&path, duration, size_mib.max(1));
+    let _ = fs::remove_file(&path);
+    results.extend(disk?);
+    Ok(results)
+}
+
+fn result(benchmark: &str, value: f64, unit: &str, started: Instant) -> BenchResult {
+    BenchResult {
+        benchmark: benchmark.to_string(),
+        value,
+        unit: unit.to_string(),
+        elapsed_ms: started.elapsed().as_secs_f64() * 1000.0,
+    }
+}
+
+fn bench_cpu(name: &str, duration: Duration, threads: usize) -> BenchResult {
+    let started = Instant::now();
+    let hashes: u64 = thread::scope(|s| {
+        let workers: Vec<_> = (0..threads).map(|t| s.spawn(move || {
+            let mut count = 0u64;
+            let mut state = t as u64;
+            while started.elapsed() < duration {
+                for _ in 0..10_000 {
+                    let mut hasher = DefaultHasher::new();
+                    state.hash(&mut hasher);
+                    state = black_box(hasher.finish());
+                }
+                count += 10_000;
+            }
+            count
+        })).collect();
+        workers.into_iter().map(|w| w.join().unwrap_or(0)).sum()
+    });
+    result(name, hashes as f64 / started.elapsed().as_secs_f64() / 1e6, "Mhash/s", started)
+}
+
+fn bench_memory(duration: Duration) -> BenchResult {
+    let src = vec![1u8; 64 * MIB as usize];
+    let mut dst = vec![0u8; src.len()];
+    let started = Instant::now();
+    let mut copied = 0u64;
+    while started.elapsed() < duration {
+        dst.copy_from_slice(black_box(&src));
+        black_box(&mut dst);
+        copied += src.len() as u64;
+    }
+    result("memory_copy", copied as f64 / started.elapsed().as_secs_f64() / (1024.0 * MIB as f64), "GiB/s", started)
+}
+
+fn bench_disk(path: &Path, duration: Duration, size_mib: u64) -> io::Result<Vec<BenchResult>> {
+    let mut results = Vec::new();
+    let chunk = vec![0x5Au8; MIB as usize];
+    let size = size_mib * MIB;
+
+    // Write passes are timed until the data is on the disk, not just in the page cache.
+    let started = Instant::now();
+    let mut file = File::create(path)?;
+    for _ in 0..size_mib {
+        file.write_all(&chunk)?;
+    }
+    file.sync_all()?;
+    results.push(result("disk_seq_write", size_mib as f64 / started.elapsed().as_secs_f64(), "MiB/s", started));
+
+    // Reads served from the page cache measure memory, so they're labelled when it can't be skipped.
+    let (mut file, uncached) = open_uncached(path)?;
+    let read_name = |name: &str| if uncached { name.to_string() } else { format!("{}_cached", name) };
+
+    let started = Instant::now();
+    let mut storage = Vec::new();
+    let buf = aligned(&mut storage, MIB as usize);
+    while file.read(buf)? > 0 {}
+    results.push(result(&read_name("disk_seq_read"), size_mib as f64 / started.elapsed().as_secs_f64(), "MiB/s", started));
+
+    let blocks = size / BLOCK as u64;
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let block = aligned(&mut storage, BLOCK);
+
+    let started = Instant::now();
+    let mut ops = 0u64;
+    while started.elapsed() < duration {
+        file.seek(SeekFrom::Start(rng.next() % blocks * BLOCK as u64))?;
+        file.read_exact(block)?;
+        ops += 1;
+    }
+    results.push(result(&read_name("disk_rand_read"), ops as f64 / started.elapsed().as_secs_f64(), "IOPS", started));
+
+    let started = Instant::now();
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let mut ops = 0u64;
+    while started.elapsed() < duration {
+        file.seek(SeekFrom::Start(rng.next() % blocks * BLOCK as u64))?;
+        file.write_all(block)?;
+        ops += 1;
+    }
+    file.sync_all()?;
+    results.push(result("disk_rand_write", ops as f64 / started.elapsed().as_secs_f64(), "IOPS", started));
+
+    Ok(results)
+}
+
+/// Opens `path` for reads that skip the page cache (`O_DIRECT`, on Linux) and says
+/// whether they do; some file systems, such as tmpfs, refuse it.
+fn open_uncached(path: &Path) -> io::Result<(File, bool)> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        if let Ok(file) = OpenOptions::new().read(true).custom_flags(libc::O_DIRECT).open(path) {
+            return Ok((file, true));
+        }
+    }
+    Ok((File::open(path)?, false))
+}
+
+/// A zeroed `len`-byte buffer in `storage`, aligned to the block size as `O_DIRECT` requires.
+fn aligned(storage: &mut Vec<u8>, len: usize) -> &mut [u8] {
+    *storage = vec![0u8; len + BLOCK];
+    let offset = storage.as_ptr().align_offset(BLOCK);
+    &mut storage[offset..offset + len]
+}
+
+/// Tiny PRNG for picking random block offsets.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
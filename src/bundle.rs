@@ -0,0 +1,113 @@
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{self, Write};
+use std::time::{Instant, SystemTime};
+use sysinfo::System;
+use crate::args::SortBy;
+use crate::collector::*;
+use crate::fmt::*;
+use crate::history;
+use crate::models::{AllReport, BundleDiagnostics, SectionTiming};
+use crate::redact::{redact, Redactor};
+
+/// Collects every section, the `all` report, and diagnostics (plus the `record` history
+/// database, when there is one) and writes them to a `.tar.gz` or `.zip` archive, picked
+/// from the extension of `path`.
+pub fn write_bundle(path: &str, sys: &System, redactor: Option<&mut Redactor>) -> io::Result<()> {
+    let entries = collect_entries(sys, redactor)?;
+    let file = File::create(path)?;
+    if path.ends_with(".zip") {
+        write_zip(file, &entries)
+    } else {
+        write_tar_gz(file, &entries)
+    }
+}
+
+fn collect_entries(sys: &System, mut redactor: Option<&mut Redactor>) -> io::Result<Vec<(String, Vec<u8>)>> {
+    let mut entries = Vec::new();
+    let mut timings = Vec::new();
+    let mut report = String::new();
+
+    // The text report is meant for humans reading it in an editor, not a terminal.
+    let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+    colored::control::set_override(false);
+
+    macro_rules! section {
+        ($name:literal, $collect:expr, $format:expr) => {{
+            let started = Instant::now();
+            let mut info = $collect;
+            redact(&mut info, redactor.as_deref_mut());
+            timings.push(SectionTiming { section: $name.to_string(), ms: started.elapsed().as_secs_f64() * 1000.0 });
+            entries.push((format!("{}.json", $name), to_json(&info)?));
+            report.push_str(&$format(&info));
+            report.push('\n');
+            info
+        }};
+    }
+
+    let system = section!("system", get_system_info(), format_system_info);
+    let cpu = section!("cpu", get_cpu_info(sys), format_cpu_info);
+    let memory = section!("memory", get_memory_info(sys), format_memory_info);
+    let disks = section!("disks", get_disks_info(true), |i: &Vec<_>| format_disks_info(i));
+    let network = section!("network", get_network_info(), |i: &Vec<_>| format_network_info(i));
+    let components = section!("components", get_components_info(), |i: &Vec<_>| format_components_info(i));
+    let mut processes = section!("processes", get_processes_info(sys, None, None, &[SortBy::Cpu], None, false, ProcessExtras { io: false, ..ProcessExtras::ALL }), |i: &Vec<_>| format_processes_info(i));
+
+    // The same figures as `all`, already redacted; processes are sorted busiest first.
+    processes.truncate(10);
+    let all = AllReport { system, cpu, memory, disks, network, components, processes };
+    entries.push(("all.json".to_string(), to_json(&all)?));
+    entries.push(("all.txt".to_string(), format_all_report(&all).into_bytes()));
+
+    // Puts back what `--color` or the terminal decided; colored only exposes the effective setting.
+    colored::control::set_override(colorize);
+
+    // The database holds raw mount point and interface names, so it stays out of redacted bundles.
+    if redactor.is_none() && let Some(path) = history::default_db_path().filter(|path| path.is_file()) {
+        entries.push(("history.db".to_string(), std::fs::read(path)?));
+    }
+
+    let mut diagnostics = BundleDiagnostics {
+        tool_version: env!("CARGO_PKG_VERSION").to_string(),
+        generated_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        args: std::env::args().collect(),
+        cpu_arch: System::cpu_arch(),
+        long_os_version: System::long_os_version(),
+        supported_system: sysinfo::IS_SUPPORTED_SYSTEM,
+        collection_times: timings,
+    };
+    redact(&mut diagnostics, redactor);
+    entries.push(("report.txt".to_string(), report.into_bytes()));
+    entries.push(("diagnostics.json".to_string(), to_json(&diagnostics)?));
+    Ok(entries)
+}
+
+fn to_json<T: Serialize>(value: &T) -> io::Result<Vec<u8>> {
+    serde_json::to_vec_pretty(value).map_err(io::Error::other)
+}
+
+fn write_tar_gz(file: File, entries: &[(String, Vec<u8>)]) -> io::Result<()> {
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mtime = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    for (name, contents) in entries {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        archive.append_data(&mut header, name, contents.as_slice())?;
+    }
+    archive.into_inner()?.finish()?.flush()
+}
+
+fn write_zip(file: File, entries: &[(String, Vec<u8>)]) -> io::Result<()> {
+    let mut archive = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    for (name, contents) in entries {
+        archive.start_file(name.as_str(), options).map_err(io::Error::other)?;
+        archive.write_all(contents)?;
+    }
+    archive.finish().map_err(io::Error::other)?.flush()
+}
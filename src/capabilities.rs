@@ -0,0 +1,84 @@
+//! Feature detection: which metric groups this build can report on the current
+//! platform and with the current privileges.
+
+use std::path::Path;
+use sysinfo::{Components, CpuRefreshKind, RefreshKind, System};
+use crate::models::{Capability, CapabilityStatus};
+use crate::{docker, power, sbc, winperf};
+
+pub fn get_capabilities() -> Vec<Capability> {
+    use CapabilityStatus::*;
+    let linux = cfg!(target_os = "linux");
+    let sysinfo_status = if sysinfo::IS_SUPPORTED_SYSTEM { Supported } else { Unsupported };
+    let sys = System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing().with_frequency()));
+    let components = Components::new_with_refreshed_list();
+    let root = is_root();
+    let privileged = |detail: &'static str| if root { (Supported, None) } else { (NeedsPrivilege, Some(detail)) };
+    let linux_only = |status: (CapabilityStatus, Option<&'static str>)| if linux { status } else { (Unsupported, Some("Linux only")) };
+
+    let checks: Vec<(&str, (CapabilityStatus, Option<&'static str>))> = vec![
+        ("system", (sysinfo_status, None)),
+        ("cpu", (sysinfo_status, None)),
+        ("cpu_frequency", if sys.cpus().iter().any(|c| c.frequency() > 0) { (Supported, None) } else { (Unsupported, Some("no frequency reported")) }),
+        ("memory", (sysinfo_status, None)),
+        ("disks", (sysinfo_status, None)),
+        ("network", (sysinfo_status, None)),
+        ("components", if components.is_empty() { (Unsupported, Some("no sensors found")) } else { (Supported, None) }),
+        ("gpu", (Unsupported, Some("not collected by this tool"))),
+        ("sensors", linux_only(if Path::new("/sys/class/hwmon").is_dir() { (Supported, None) } else { (Unsupported, Some("no hwmon class")) })),
+        ("processes", (sysinfo_status, None)),
+        ("users", (sysinfo_status, None)),
+        ("sessions", linux_only((Supported, None))),
+        ("resources", linux_only(privileged("epoll/inotify counts only cover your own processes"))),
+        ("whoholds", linux_only(privileged("only your own processes are checked"))),
+        ("connections", if cfg!(windows) { (Supported, None) } else { linux_only(privileged("only your own processes' sockets are named")) }),
+        ("container", linux_only(if Path::new("/proc/self/cgroup").exists() { (Supported, None) } else { (Unsupported, Some("no cgroups")) })),
+        ("docker", docker_status()),
+        ("k8s_node", linux_only(if Path::new("/var/log/pods").is_dir() { (Supported, None) } else { (Unsupported, Some("not a Kubernetes node")) })),
+        ("network_namespaces", linux_only(privileged("namespaces without processes cannot be read"))),
+        ("power_supplies", linux_only(if Path::new("/sys/class/power_supply").is_dir() { (Supported, None) } else { (Unsupported, Some("no power supply class")) })),
+        ("battery", linux_only(if power::has_battery() { (Supported, None) } else { (Unsupported, Some("no battery found")) })),
+        ("sbc_sensors", if sbc::core_voltage().is_some() { (Supported, None) } else { (Unsupported, Some("vcgencmd not available")) }),
+        ("windows_perf_counters", if winperf::commit_charge().is_some() { (Supported, None) } else { (Unsupported, Some(if cfg!(windows) { "performance data unavailable" } else { "Windows only" })) }),
+        ("clipboard", clipboard_status()),
+    ];
+    checks.into_iter().map(|(metric, (status, detail))| Capability {
+        metric: metric.to_string(),
+        status,
+        detail: detail.map(str::to_string),
+    }).collect()
+}
+
+fn is_root() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        unsafe { libc::geteuid() == 0 }
+    }
+    #[cfg(not(target_os = "linux"))]
+    false
+}
+
+/// Whether this platform speaks to Docker (Unix), and whether the socket is there to
+/// speak to.
+fn docker_status() -> (CapabilityStatus, Option<&'static str>) {
+    if !cfg!(unix) {
+        return (CapabilityStatus::Unsupported, Some("Unix only"));
+    }
+    if Path::new(&docker::socket_path(None)).exists() {
+        (CapabilityStatus::Supported, None)
+    } else {
+        (CapabilityStatus::Unsupported, Some("no Docker socket"))
+    }
+}
+
+fn clipboard_status() -> (CapabilityStatus, Option<&'static str>) {
+    // Connecting to an absent X server takes a while to time out, so check for a display first.
+    if cfg!(target_os = "linux") && std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        return (CapabilityStatus::Unsupported, Some("no display"));
+    }
+    match arboard::Clipboard::new() {
+        Ok(_) => (CapabilityStatus::Supported, None),
+        Err(_) => (CapabilityStatus::Unsupported, Some("clipboard unavailable")),
+    }
+}
@@ -0,0 +1,67 @@
+//! SVG line charts of time series, such as recorded history: a graph a report can embed
+//! without exporting the data to Grafana first.
+
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+const WIDTH: f64 = 800.0;
+const HEIGHT: f64 = 400.0;
+/// Room for the title and legend above the plot, the value labels left of it, and the
+/// times below it.
+const TOP: f64 = 50.0;
+const LEFT: f64 = 90.0;
+const RIGHT: f64 = 20.0;
+const BOTTOM: f64 = 40.0;
+/// Line colors, in the order of the lines.
+const COLORS: &[&str] = &["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f"];
+
+/// One line of a chart: its name in the legend (empty for none) and its
+/// `(Unix seconds, value)` points, oldest first.
+pub type Line = (String, Vec<(u64, f64)>);
+
+/// A line chart titled `title`, over the value range (from zero, for non-negative series)
+/// and the time span of all the `lines`' points. `label` writes the values on the axis.
+pub fn svg(title: &str, lines: &[Line], label: impl Fn(f64) -> String) -> String {
+    let points = || lines.iter().flat_map(|(_, points)| points.iter());
+    let start = points().map(|p| p.0).min().unwrap_or(0);
+    let end = points().map(|p| p.0).max().unwrap_or(0).max(start + 1);
+    let low = points().map(|p| p.1).fold(0.0, f64::min);
+    let high = points().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max).max(low + 1.0);
+    let x = |time: u64| LEFT + (time - start) as f64 / (end - start) as f64 * (WIDTH - LEFT - RIGHT);
+    let y = |value: f64| TOP + (high - value) / (high - low) * (HEIGHT - TOP - BOTTOM);
+
+    let mut s = String::new();
+    let _ = writeln!(s, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="sans-serif" font-size="12">"#, w = WIDTH, h = HEIGHT);
+    let _ = writeln!(s, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(s, r#"<text x="{}" y="22" font-size="16">{}</text>"#, LEFT, escape(title));
+    for i in 0..=4 {
+        let value = low + (high - low) * i as f64 / 4.0;
+        let _ = writeln!(s, r##"<line x1="{:.1}" y1="{y:.1}" x2="{:.1}" y2="{y:.1}" stroke="#ddd"/>"##, LEFT, WIDTH - RIGHT, y = y(value));
+        let _ = writeln!(s, r#"<text x="{:.1}" y="{:.1}" text-anchor="end">{}</text>"#, LEFT - 6.0, y(value) + 4.0, escape(&label(value)));
+    }
+    let time = |secs: u64| humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(secs)).to_string();
+    let _ = writeln!(s, r#"<text x="{}" y="{}">{}</text>"#, LEFT, HEIGHT - 12.0, time(start));
+    let _ = writeln!(s, r#"<text x="{}" y="{}" text-anchor="end">{}</text>"#, WIDTH - RIGHT, HEIGHT - 12.0, time(end));
+
+    let mut legend_x = LEFT;
+    for (i, (name, points)) in lines.iter().enumerate() {
+        let color = COLORS[i % COLORS.len()];
+        if points.len() == 1 {
+            let _ = writeln!(s, r#"<circle cx="{:.1}" cy="{:.1}" r="2" fill="{}"/>"#, x(points[0].0), y(points[0].1), color);
+        } else {
+            let coords: Vec<String> = points.iter().map(|&(t, v)| format!("{:.1},{:.1}", x(t), y(v))).collect();
+            let _ = writeln!(s, r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#, coords.join(" "), color);
+        }
+        if !name.is_empty() {
+            let _ = writeln!(s, r#"<rect x="{:.1}" y="31" width="10" height="10" fill="{}"/>"#, legend_x, color);
+            let _ = writeln!(s, r#"<text x="{:.1}" y="40">{}</text>"#, legend_x + 14.0, escape(name));
+            legend_x += 30.0 + 7.0 * name.chars().count() as f64;
+        }
+    }
+    s.push_str("</svg>\n");
+    s
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -1,135 +1,795 @@
+//! Reads the system through sysinfo (plus platform extras such as `vcgencmd` and
+//! Windows performance counters) into the structs in [`crate::models`].
+
+use regex::{Regex, RegexBuilder};
 use sysinfo::{
-    Components, CpuRefreshKind, Disks, MemoryRefreshKind, Networks, 
-    ProcessRefreshKind, RefreshKind, System, ProcessesToUpdate
+    Components, CpuRefreshKind, Disks, IpNetwork, MemoryRefreshKind, Networks, Pid, Process,
+    ProcessRefreshKind, ProcessStatus, RefreshKind, System, ProcessesToUpdate, UpdateKind, Users
 };
+use std::cmp::Ordering;
+use std::ffi::OsString;
+use std::panic;
+use std::path::{Path, PathBuf};
 use std::thread;
-use crate::args::{Commands, SortBy};
+use std::time::Duration;
+use crate::args::{Commands, OutputFormat, ProcessColumn, ProcessState, SortBy};
 use crate::models::*;
+use crate::fmt::format_bytes;
+use crate::watch::{HardwareSnapshot, SeenProcess};
+use crate::{container, cpufreq, netns, power, sbc, winperf};
 
-pub fn init_system(command: &Option<Commands>) -> System {
-    let mut sys = match command {
-        Some(Commands::System) => System::new_with_specifics(RefreshKind::nothing()),
-        Some(Commands::Cpu) => {
-            let mut s = System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing().with_cpu_usage()));
-            thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-            s.refresh_cpu_usage();
-            s
-        }
-        Some(Commands::Memory) => System::new_with_specifics(RefreshKind::nothing().with_memory(MemoryRefreshKind::nothing().with_ram().with_swap())),
-        Some(Commands::Processes { .. }) => {
-            let mut s = System::new_with_specifics(
-                RefreshKind::nothing()
-                    .with_processes(ProcessRefreshKind::nothing().with_cpu().with_memory())
-                    .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
-            );
-            thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-            s.refresh_processes_specifics(
-                ProcessesToUpdate::All,
-                true,
-                ProcessRefreshKind::nothing().with_cpu().with_memory()
-            );
-            s
+/// What `command` reads from `System`.
+pub fn refresh_kind(command: &Option<Commands>) -> RefreshKind {
+    let cpu_usage = CpuRefreshKind::nothing().with_cpu_usage();
+    let memory = MemoryRefreshKind::nothing().with_ram().with_swap();
+    let processes = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    match command {
+        None | Some(Commands::Alert { .. }) | Some(Commands::Statusbar { .. }) | Some(Commands::Push { .. }) => RefreshKind::nothing().with_memory(memory).with_cpu(cpu_usage),
+        Some(Commands::Cpu { .. }) => RefreshKind::nothing().with_cpu(cpu_usage.with_frequency()),
+        Some(Commands::Load { .. }) => RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing()),
+        Some(Commands::Thermal { .. }) => RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing().with_frequency()),
+        Some(Commands::Memory) => RefreshKind::nothing().with_memory(memory),
+        Some(Commands::Fetch { .. }) => RefreshKind::nothing().with_memory(memory).with_cpu(CpuRefreshKind::nothing()),
+        // CPU usage too, so the first refresh waits out the container's CPU sample.
+        Some(Commands::Container) => RefreshKind::nothing().with_memory(memory).with_cpu(cpu_usage),
+        Some(Commands::Processes { io, sort, .. }) => {
+            let mut processes = processes.with_cmd(UpdateKind::OnlyIfNotSet).with_user(UpdateKind::OnlyIfNotSet);
+            if *io || sort.iter().any(|s| matches!(s, SortBy::DiskRead | SortBy::DiskWrite)) {
+                processes = processes.with_disk_usage();
+            }
+            RefreshKind::nothing()
+                .with_processes(processes)
+                .with_cpu(cpu_usage)
+                .with_memory(MemoryRefreshKind::nothing().with_ram())
         }
-        _ => System::new_with_specifics(
-                RefreshKind::nothing()
-                    .with_memory(MemoryRefreshKind::nothing().with_ram())
-                    .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
-            ),
-    };
+        Some(Commands::Process { .. }) => RefreshKind::nothing()
+            .with_processes(ProcessRefreshKind::everything().without_tasks())
+            .with_cpu(cpu_usage),
+        Some(Commands::Docker { .. }) => RefreshKind::nothing().with_processes(processes).with_cpu(cpu_usage),
+        Some(Commands::K8sNode) => RefreshKind::nothing().with_processes(processes).with_memory(memory).with_cpu(cpu_usage),
+        Some(Commands::Port { .. }) => RefreshKind::nothing()
+            .with_processes(ProcessRefreshKind::nothing().with_cmd(UpdateKind::OnlyIfNotSet).with_user(UpdateKind::OnlyIfNotSet)),
+        Some(Commands::Whoholds { .. }) | Some(Commands::Connections { .. }) | Some(Commands::Kill { .. }) => RefreshKind::nothing().with_processes(ProcessRefreshKind::nothing()),
+        Some(Commands::Bundle { .. }) | Some(Commands::Snapshot { .. }) | Some(Commands::All { .. }) => RefreshKind::nothing().with_processes(processes).with_cpu(cpu_usage).with_memory(memory),
+        // Everything else collects through its own sysinfo types or /proc.
+        _ => RefreshKind::nothing(),
+    }
+}
 
-    if command.is_none() {
-        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
-        sys.refresh_cpu_usage();
+/// Creates a `System` refreshed for `command`. Commands that report CPU usage wait
+/// `sample` between the two CPU refreshes, never less than sysinfo's minimum interval.
+pub fn init_system(command: &Option<Commands>, sample: Option<Duration>) -> System {
+    let mut sys = System::new();
+    refresh_system(&mut sys, command);
+    if samples_cpu_usage(command) {
+        thread::sleep(sample.unwrap_or(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).max(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL));
+        refresh_system(&mut sys, command);
     }
-    
     sys
 }
 
+/// [`init_system`], with `collect` running on another thread meanwhile, so reads that
+/// don't need `System` overlap the CPU sample instead of following it.
+pub fn init_system_with<T: Send>(command: &Option<Commands>, sample: Option<Duration>, collect: impl FnOnce() -> T + Send) -> (System, T) {
+    thread::scope(|scope| {
+        let collecting = scope.spawn(collect);
+        let sys = init_system(command, sample);
+        (sys, collecting.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+    })
+}
+
+/// What a report reads without `System`, collected by [`prefetch`].
+pub enum Prefetched {
+    Summary(SystemInfo),
+    All(AllParts),
+}
+
+/// The part of `command`'s first report that [`init_system_with`] can collect while
+/// it samples CPU usage: the system details for the summary, and everything but CPU,
+/// memory, and processes for `all`.
+pub fn prefetch(command: &Option<Commands>, hide_idle: bool) -> Option<Prefetched> {
+    match command {
+        None => Some(Prefetched::Summary(get_system_info())),
+        Some(Commands::All { .. }) => Some(Prefetched::All(get_all_parts(hide_idle))),
+        _ => None,
+    }
+}
+
+/// Whether `command` shows CPU usage, which is only meaningful over at least
+/// [`sysinfo::MINIMUM_CPU_UPDATE_INTERVAL`].
+pub fn samples_cpu_usage(command: &Option<Commands>) -> bool {
+    refresh_kind(command).cpu().is_some_and(|cpu| cpu.cpu_usage())
+}
+
+/// Refreshes `sys` for `command` in place. In watch mode the previous refresh is the
+/// first CPU sample, so usage covers the whole interval without another wait.
+pub fn refresh_system(sys: &mut System, command: &Option<Commands>) {
+    let kind = refresh_kind(command);
+    if let Some(memory) = kind.memory() {
+        sys.refresh_memory_specifics(memory);
+    }
+    if let Some(cpu) = kind.cpu() {
+        sys.refresh_cpu_specifics(cpu);
+    }
+    if let Some(processes) = kind.processes() {
+        let pids = match command {
+            Some(Commands::Process { pid, .. }) => vec![Pid::from_u32(*pid)],
+            _ => Vec::new(),
+        };
+        let targets = if pids.is_empty() { ProcessesToUpdate::All } else { ProcessesToUpdate::Some(&pids) };
+        sys.refresh_processes_specifics(targets, true, processes);
+    }
+}
+
+pub fn get_uptime_info() -> UptimeInfo {
+    UptimeInfo { uptime: System::uptime(), boot_time: System::boot_time() }
+}
+
+/// The 1/5/15-minute load averages; `None` on Windows, which has no such counter.
+pub fn get_load_average() -> Option<LoadAverage> {
+    if cfg!(windows) {
+        return None;
+    }
+    let load = System::load_average();
+    Some(LoadAverage { one: load.one, five: load.five, fifteen: load.fifteen })
+}
+
+/// Current load against an optional limit for the one-minute average.
+pub fn get_load_report(sys: &System, threshold: Option<f64>) -> Option<LoadReport> {
+    let load_average = get_load_average()?;
+    Some(LoadReport {
+        load_average,
+        nb_cpus: sys.cpus().len(),
+        threshold,
+        exceeded: threshold.map(|limit| load_average.one > limit),
+    })
+}
+
+/// The default report: system details, memory, and overall CPU usage.
+pub fn get_summary<'a>(sys: &System, system: &'a SystemInfo, mem: &'a MemoryInfo) -> Summary<'a> {
+    Summary {
+        system,
+        memory: mem,
+        cpu_total_usage: sys.global_cpu_usage(),
+        nb_cpus: sys.cpus().len(),
+    }
+}
+
+/// The categories of `all` that are read without `System`.
+pub struct AllParts {
+    pub system: SystemInfo,
+    pub disks: Vec<DiskInfo>,
+    pub network: Vec<NetworkInfo>,
+    pub components: Vec<ComponentInfo>,
+}
+
+/// Reads disks, networks, and sensors at the same time, each on its own thread, since
+/// any of them can stall on a slow mount or driver.
+pub fn get_all_parts(hide_idle: bool) -> AllParts {
+    thread::scope(|scope| {
+        let disks = scope.spawn(|| get_disks_info(false));
+        let network = scope.spawn(|| {
+            let mut network = get_network_info();
+            retain_active(&mut network, hide_idle);
+            network
+        });
+        let components = scope.spawn(|| {
+            let mut components = get_components_info();
+            retain_active(&mut components, hide_idle);
+            components
+        });
+        let system = get_system_info();
+        AllParts {
+            system,
+            disks: disks.join().unwrap_or_else(|e| panic::resume_unwind(e)),
+            network: network.join().unwrap_or_else(|e| panic::resume_unwind(e)),
+            components: components.join().unwrap_or_else(|e| panic::resume_unwind(e)),
+        }
+    })
+}
+
+/// Every category, with the `top` busiest processes.
+pub fn get_all_report(sys: &System, parts: AllParts, top: usize, hide_idle: bool) -> AllReport {
+    AllReport {
+        system: parts.system,
+        cpu: get_cpu_info(sys),
+        memory: get_memory_info(sys),
+        disks: parts.disks,
+        network: parts.network,
+        components: parts.components,
+        processes: get_processes_info(sys, None, Some(top), &[SortBy::Cpu], None, hide_idle, ProcessExtras { io: false, ..ProcessExtras::ALL }),
+    }
+}
+
 pub fn get_system_info() -> SystemInfo {
     SystemInfo {
         name: System::name(),
         kernel_version: System::kernel_version(),
         os_version: System::os_version(),
         host_name: System::host_name(),
+        uptime: System::uptime(),
+        boot_time: System::boot_time(),
+        load_average: get_load_average(),
     }
 }
 
 pub fn get_cpu_info(sys: &System) -> CpuInfo {
     CpuInfo {
         nb_cpus: sys.cpus().len(),
-        cpus: sys.cpus().iter().enumerate().map(|(i, cpu)| SingleCpuInfo {
-            id: i,
-            usage: cpu.cpu_usage(),
-            vendor: cpu.vendor_id().to_string(),
-            brand: cpu.brand().to_string(),
+        physical_cores: System::physical_core_count(),
+        cpu_limit: container::cpu_limit(),
+        topology: None,
+        cpus: sys.cpus().iter().enumerate().map(|(i, cpu)| {
+            let (min_frequency, max_frequency) = cpufreq::frequency_limits(i);
+            SingleCpuInfo {
+                id: i,
+                usage: cpu.cpu_usage(),
+                vendor: cpu.vendor_id().to_string(),
+                brand: cpu.brand().to_string(),
+                frequency: cpu.frequency(),
+                min_frequency,
+                max_frequency,
+                ..Default::default()
+            }
         }).collect(),
         total_usage: sys.global_cpu_usage(),
+        breakdown: None,
+        load_average: get_load_average(),
+        core_voltage: sbc::core_voltage(),
+        throttling: sbc::throttling(),
     }
 }
 
+/// Fills in `cpu --topology`: each CPU's socket and core, and how many of each there
+/// are. Without per-CPU ids, only the physical core count is known.
+pub fn add_cpu_topology(info: &mut CpuInfo) {
+    for cpu in &mut info.cpus {
+        if let Some((socket, core)) = cpufreq::topology_ids(cpu.id) {
+            cpu.socket = Some(socket);
+            cpu.core = Some(core);
+        }
+    }
+    let ids: Option<Vec<(u64, u64)>> = info.cpus.iter().map(|cpu| cpu.socket.zip(cpu.core)).collect();
+    let (sockets, cores) = match ids {
+        Some(ids) if !ids.is_empty() => {
+            let (sockets, cores) = cpufreq::count_topology(&ids);
+            (Some(sockets), Some(cores))
+        }
+        _ => (None, info.physical_cores),
+    };
+    info.topology = Some(CpuTopology { sockets, cores, threads: info.nb_cpus });
+}
+
 pub fn get_memory_info(sys: &System) -> MemoryInfo {
+    let commit = winperf::commit_charge();
+    let (buffers, cached) = std::fs::read_to_string("/proc/meminfo").map(|text| parse_meminfo_caches(&text)).unwrap_or_default();
+    // sysinfo insists on a memory refresh before it reads the cgroup.
+    let cgroup = if sys.total_memory() > 0 { sys.cgroup_limits() } else { None };
     MemoryInfo {
         total_memory: sys.total_memory(),
         used_memory: sys.used_memory(),
+        available_memory: sys.available_memory(),
+        free_memory: sys.free_memory(),
+        buffers,
+        cached,
         total_swap: sys.total_swap(),
         used_swap: sys.used_swap(),
+        committed_memory: commit.map(|(committed, _)| committed),
+        commit_limit: commit.map(|(_, limit)| limit),
+        cgroup: cgroup.filter(|c| c.total_memory < sys.total_memory()).map(|c| CgroupMemory {
+            limit: c.total_memory,
+            used: c.total_memory - c.free_memory,
+            rss: c.rss,
+        }),
     }
 }
 
-pub fn get_disks_info() -> Vec<DiskInfo> {
-    let disks = Disks::new_with_refreshed_list();
-    disks.iter().map(|disk| DiskInfo {
+/// Buffers and cache in bytes from `/proc/meminfo`, the way `free` counts them:
+/// cache includes reclaimable slab memory.
+pub fn parse_meminfo_caches(text: &str) -> (Option<u64>, Option<u64>) {
+    let field = |name: &str| text.lines().find_map(|line| {
+        let kib: u64 = line.strip_prefix(name)?.strip_prefix(':')?.trim().strip_suffix("kB")?.trim().parse().ok()?;
+        Some(kib * 1024)
+    });
+    let cached = field("Cached").map(|cached| cached + field("SReclaimable").unwrap_or(0));
+    (field("Buffers"), cached)
+}
+
+/// Mounted disks; with `io`, also the bytes each has read and written.
+pub fn get_disks_info(io: bool) -> Vec<DiskInfo> {
+    disk_info_from(&Disks::new_with_refreshed_list(), io)
+}
+
+pub fn disk_info_from(disks: &Disks, io: bool) -> Vec<DiskInfo> {
+    let mut info: Vec<DiskInfo> = disks.iter().map(|disk| DiskInfo {
         name: disk.name().to_string_lossy().into_owned(),
+        mount_point: disk.mount_point().to_string_lossy().into_owned(),
         kind: disk.kind().to_string(),
         file_system: disk.file_system().to_string_lossy().into_owned(),
+        is_removable: disk.is_removable(),
+        is_read_only: disk.is_read_only(),
         available_space: disk.available_space(),
         total_space: disk.total_space(),
-    }).collect()
+        used_percent: used_percent(disk.available_space(), disk.total_space()),
+        usage_level: None,
+        queue_length: winperf::disk_queue_length(disk.mount_point()),
+        available_rate: None,
+        time_to_full: None,
+        read_bytes: io.then(|| disk.usage().total_read_bytes),
+        written_bytes: io.then(|| disk.usage().total_written_bytes),
+        read_rate: None,
+        write_rate: None,
+    }).collect();
+    info.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+    info
+}
+
+/// The `disks` filters: `--mount` paths and `--fs` types to keep (all when empty), and
+/// `--exclude` types to drop. Types compare case-insensitively, so `ntfs` finds `NTFS`.
+#[derive(Default)]
+pub struct DiskFilter {
+    pub mounts: Vec<PathBuf>,
+    pub file_systems: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl DiskFilter {
+    pub fn matches(&self, disk: &DiskInfo) -> bool {
+        let is_type = |types: &[String]| types.iter().any(|t| t.eq_ignore_ascii_case(&disk.file_system));
+        // Paths compare by component, so `/data/` finds `/data`.
+        (self.mounts.is_empty() || self.mounts.iter().any(|m| m == Path::new(&disk.mount_point)))
+            && (self.file_systems.is_empty() || is_type(&self.file_systems))
+            && !is_type(&self.exclude)
+    }
+}
+
+fn used_percent(available: u64, total: u64) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+    (total.saturating_sub(available) as f64 / total as f64 * 100.0) as f32
+}
+
+/// Where `value` stands against the `warn` and `crit` limits; reaching a limit counts.
+fn threshold_level(value: f32, warn: Option<f32>, crit: Option<f32>) -> UsageLevel {
+    let over = |limit: Option<f32>| limit.is_some_and(|limit| value >= limit);
+    if over(crit) {
+        UsageLevel::Critical
+    } else if over(warn) {
+        UsageLevel::Warning
+    } else {
+        UsageLevel::Ok
+    }
+}
+
+/// Sets each disk's `usage_level` against the `--warn-pct` and `--crit-pct` limits;
+/// a disk is over a limit once its used share reaches it. Returns the highest level.
+pub fn check_disk_usage(disks: &mut [DiskInfo], warn: Option<f32>, crit: Option<f32>) -> Option<UsageLevel> {
+    if warn.is_none() && crit.is_none() {
+        return None;
+    }
+    for disk in disks.iter_mut() {
+        disk.usage_level = Some(threshold_level(disk.used_percent, warn, crit));
+    }
+    Some(disks.iter().filter_map(|disk| disk.usage_level).max().unwrap_or(UsageLevel::Ok))
+}
+
+/// Sets each sensor's `temperature_level` against the `--warn` and `--crit` limits in
+/// °C. Sensors without a reading are left unset. Returns the highest level.
+pub fn check_temperatures(components: &mut [ComponentInfo], warn: Option<f32>, crit: Option<f32>) -> Option<UsageLevel> {
+    if warn.is_none() && crit.is_none() {
+        return None;
+    }
+    for component in components.iter_mut() {
+        component.temperature_level = component.temperature.map(|t| threshold_level(t, warn, crit));
+    }
+    Some(components.iter().filter_map(|c| c.temperature_level).max().unwrap_or(UsageLevel::Ok))
 }
 
 pub fn get_network_info() -> Vec<NetworkInfo> {
-    let networks = Networks::new_with_refreshed_list();
-    networks.iter().map(|(name, data)| NetworkInfo {
-        interface: name.clone(),
-        received: data.total_received(),
-        transmitted: data.total_transmitted(),
-    }).collect()
+    network_info_from(&Networks::new_with_refreshed_list(), None, false)
+}
+
+/// Interfaces in `networks`. Given `elapsed` since its previous refresh, also the receive
+/// and transmit rates over that span, and with `delta` the counters it added.
+pub fn network_info_from(networks: &Networks, elapsed: Option<Duration>, delta: bool) -> Vec<NetworkInfo> {
+    let secs = elapsed.map(|e| e.as_secs_f64()).filter(|&secs| secs > 0.0);
+    let drops = netns::interface_drops();
+    let mut info: Vec<NetworkInfo> = networks.iter().map(|(name, data)| {
+        let (drops_received, drops_transmitted) = drops.get(name).copied().unwrap_or_default();
+        NetworkInfo {
+            interface: name.clone(),
+            mac_address: (!data.mac_address().is_unspecified()).then(|| data.mac_address().to_string()),
+            mtu: Some(data.mtu()).filter(|&mtu| mtu > 0),
+            ip_addresses: ip_addresses(data.ip_networks()),
+            received: data.total_received(),
+            transmitted: data.total_transmitted(),
+            packets_received: data.total_packets_received(),
+            packets_transmitted: data.total_packets_transmitted(),
+            errors_received: data.total_errors_on_received(),
+            errors_transmitted: data.total_errors_on_transmitted(),
+            drops_received,
+            drops_transmitted,
+            rx_rate: secs.map(|secs| data.received() as f64 / secs),
+            tx_rate: secs.map(|secs| data.transmitted() as f64 / secs),
+            delta: (delta && elapsed.is_some()).then(|| NetworkDelta {
+                received: data.received(),
+                transmitted: data.transmitted(),
+                packets_received: data.packets_received(),
+                packets_transmitted: data.packets_transmitted(),
+                errors_received: data.errors_on_received(),
+                errors_transmitted: data.errors_on_transmitted(),
+            }),
+            namespace: None,
+        }
+    }).collect();
+    info.sort_by(|a, b| natural_cmp(&a.interface, &b.interface));
+    info
+}
+
+/// IPv4 before IPv6, each in address order; sysinfo keeps them in no particular order.
+fn ip_addresses(networks: &[IpNetwork]) -> Vec<String> {
+    let mut networks = networks.to_vec();
+    networks.sort_by_key(|net| (net.addr, net.prefix));
+    networks.iter().map(|net| format!("{}/{}", net.addr, net.prefix)).collect()
 }
 
 pub fn get_components_info() -> Vec<ComponentInfo> {
     let components = Components::new_with_refreshed_list();
-    components.iter().map(|c| ComponentInfo {
+    let mut info: Vec<ComponentInfo> = components.iter().map(|c| ComponentInfo {
         label: c.label().to_string(),
         temperature: c.temperature(),
         max: c.max(),
-    }).collect()
+        critical: c.critical(),
+        percent_of_max: c.temperature().zip(c.max()).filter(|&(_, max)| max > 0.0).map(|(t, max)| t / max * 100.0),
+        temperature_level: None,
+    }).collect();
+    // Single-board computers often expose the SoC sensor only through vcgencmd or a
+    // thermal zone without a hwmon entry.
+    if let Some(temperature) = sbc::soc_temperature() {
+        info.push(ComponentInfo { label: "SoC".to_string(), temperature: Some(temperature), ..Default::default() });
+    } else {
+        for (kind, temperature) in sbc::thermal_zones() {
+            if !info.iter().any(|c| c.label.starts_with(&kind)) {
+                info.push(ComponentInfo { label: kind, temperature: Some(temperature), ..Default::default() });
+            }
+        }
+    }
+    info.sort_by(|a, b| natural_cmp(&a.label, &b.label));
+    info
 }
 
-pub fn get_processes_info(sys: &System, filter: &Option<String>, limit: Option<usize>, sort: SortBy) -> Vec<ProcessInfo> {
-    let mut processes: Vec<ProcessInfo> = sys.processes().values().filter(|p| {
-        if let Some(f) = filter {
-            p.name().to_string_lossy().contains(f)
-        } else {
-            true
+/// Critical temperature assumed when no sensor reports one.
+pub const DEFAULT_CRITICAL_TEMP: f32 = 100.0;
+
+/// Samples the average CPU frequency together with the hottest temperature sensor.
+pub fn get_thermal_sample(sys: &System, critical: Option<f32>) -> ThermalSample {
+    let cpus = sys.cpus();
+    let frequency_mhz = if cpus.is_empty() { 0 } else { cpus.iter().map(|c| c.frequency()).sum::<u64>() / cpus.len() as u64 };
+    let components = Components::new_with_refreshed_list();
+    let hottest = components.iter()
+        .filter_map(|c| c.temperature().map(|t| (t, c.critical())))
+        .max_by(|a, b| a.0.total_cmp(&b.0));
+    ThermalSample {
+        frequency_mhz,
+        peak_frequency_mhz: None,
+        temperature: hottest.map(|(t, _)| t),
+        critical: critical.or(hottest.and_then(|(_, c)| c)).unwrap_or(DEFAULT_CRITICAL_TEMP),
+        throttling: false,
+    }
+}
+
+/// The `processes` filters. The `--filter` pattern is a substring, or a regular expression
+/// with `--regex`, matched against the process name and, with `--cmdline`, the full
+/// command line.
+#[derive(Default)]
+pub struct ProcessFilter {
+    pattern: Option<Regex>,
+    cmdline: bool,
+    /// Any of these states; all states when empty.
+    pub states: Vec<ProcessState>,
+    pub min_cpu: Option<f32>,
+    pub min_memory: Option<u64>,
+}
+
+impl ProcessFilter {
+    pub fn new(pattern: &str, regex: bool, ignore_case: bool, cmdline: bool) -> Result<Self, regex::Error> {
+        let source = if regex { pattern.to_string() } else { regex::escape(pattern) };
+        let pattern = RegexBuilder::new(&source).case_insensitive(ignore_case).build()?;
+        Ok(ProcessFilter { pattern: Some(pattern), cmdline, ..Default::default() })
+    }
+
+    /// A plain, case-sensitive substring match on the name.
+    pub fn substring(pattern: &str) -> Self {
+        ProcessFilter::new(pattern, false, false, false).expect("escaped literal is a valid regex")
+    }
+
+    /// Whether the pattern, if any, matches `name` or (with `--cmdline`) `cmd`.
+    pub fn matches(&self, name: &str, cmd: &[OsString]) -> bool {
+        let Some(pattern) = &self.pattern else { return true };
+        if pattern.is_match(name) {
+            return true;
         }
+        // Arguments are joined with spaces so a pattern can span them, e.g. "python3 manage.py".
+        self.cmdline && !cmd.is_empty()
+            && pattern.is_match(&cmd.iter().map(|a| a.to_string_lossy()).collect::<Vec<_>>().join(" "))
+    }
+
+    fn matches_process(&self, p: &Process) -> bool {
+        (self.states.is_empty() || process_state(p.status()).is_some_and(|s| self.states.contains(&s)))
+            && self.min_cpu.is_none_or(|min| p.cpu_usage() >= min)
+            && self.min_memory.is_none_or(|min| p.memory() >= min)
+            && self.matches(&p.name().to_string_lossy(), p.cmd())
+    }
+}
+
+/// Groups sysinfo's per-platform states the way `ps` users think of them: idle kernel
+/// threads and uninterruptible (disk) waits count as sleeping, traced as stopped, and dead
+/// as zombie. The remaining transient states match no `--state`.
+pub fn process_state(status: ProcessStatus) -> Option<ProcessState> {
+    match status {
+        ProcessStatus::Run => Some(ProcessState::Running),
+        ProcessStatus::Sleep | ProcessStatus::Idle | ProcessStatus::UninterruptibleDiskSleep | ProcessStatus::Parked => Some(ProcessState::Sleeping),
+        ProcessStatus::Stop | ProcessStatus::Tracing => Some(ProcessState::Stopped),
+        ProcessStatus::Zombie | ProcessStatus::Dead => Some(ProcessState::Zombie),
+        _ => None,
+    }
+}
+
+/// The per-process figures beyond what sysinfo keeps, filled in only when asked for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessExtras {
+    /// Disk I/O totals, which need `sys` to have refreshed disk usage.
+    pub io: bool,
+    /// Thread counts, read from `/proc` for each process (Linux).
+    pub threads: bool,
+    /// Open handle counts, one query per process (Windows).
+    pub handles: bool,
+}
+
+impl ProcessExtras {
+    pub const ALL: ProcessExtras = ProcessExtras { io: true, threads: true, handles: true };
+
+    /// What a process table with `columns` (the default ones when empty) shows. JSON,
+    /// YAML, and CSV carry every field, so they get threads and handles regardless.
+    pub fn for_table(format: OutputFormat, columns: &[ProcessColumn], io: bool) -> Self {
+        let structured = !matches!(format, OutputFormat::Text | OutputFormat::Markdown);
+        ProcessExtras {
+            io,
+            threads: structured || columns.contains(&ProcessColumn::Threads),
+            handles: structured || columns.is_empty() || columns.contains(&ProcessColumn::Handles),
+        }
+    }
+}
+
+/// Processes matching `filter`, without idle ones when `hide_idle` is set, sorted by the
+/// `sort` keys and limited. `descending` overrides each key's own direction.
+/// The user name and command line are only known when `sys` refreshed them; `extras`
+/// picks the costlier figures, read only for the processes that are kept.
+pub fn get_processes_info(sys: &System, filter: Option<&ProcessFilter>, limit: Option<usize>, sort: &[SortBy], descending: Option<bool>, hide_idle: bool, extras: ProcessExtras) -> Vec<ProcessInfo> {
+    let mut processes = process_info_from(sys, &Users::new_with_refreshed_list(), filter, extras.io);
+    retain_active(&mut processes, hide_idle);
+
+    sort_processes(&mut processes, sort, descending);
+
+    if let Some(l) = limit {
+        processes.truncate(l);
+    }
+    for process in &mut processes {
+        if extras.threads {
+            process.threads = thread_count(process.pid);
+        }
+        if extras.handles {
+            process.handles = winperf::process_handle_count(process.pid);
+        }
+    }
+
+    processes
+}
+
+/// The processes in `sys` matching `filter`, unsorted, with user names from `users`;
+/// with `io`, also the bytes each has read and written.
+pub fn process_info_from(sys: &System, users: &Users, filter: Option<&ProcessFilter>, io: bool) -> Vec<ProcessInfo> {
+    let total_memory = sys.total_memory();
+    sys.processes().values().filter(|p| {
+        filter.is_none_or(|f| f.matches_process(p))
     }).map(|p| ProcessInfo {
-        pid: p.pid().to_string(),
+        pid: p.pid().as_u32(),
         name: p.name().to_string_lossy().into_owned(),
         cpu_usage: p.cpu_usage(),
         memory: p.memory(),
-    }).collect();
+        run_time: p.run_time(),
+        handles: None,
+        user: p.user_id().and_then(|uid| users.get_user_by_id(uid)).map(|user| user.name().to_string()),
+        memory_usage: if total_memory > 0 { (p.memory() as f64 / total_memory as f64 * 100.0) as f32 } else { 0.0 },
+        virtual_memory: p.virtual_memory(),
+        start_time: p.start_time(),
+        status: p.status().to_string(),
+        threads: None,
+        cmd: p.cmd().iter().map(|arg| arg.to_string_lossy().into_owned()).collect(),
+        read_bytes: io.then(|| p.disk_usage().total_read_bytes),
+        written_bytes: io.then(|| p.disk_usage().total_written_bytes),
+        read_rate: None,
+        write_rate: None,
+    }).collect()
+}
 
-    match sort {
-        SortBy::Cpu => processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap()),
-        SortBy::Memory => processes.sort_by(|a, b| b.memory.cmp(&a.memory)),
-        SortBy::Pid => processes.sort_by(|a, b| a.pid.cmp(&b.pid)),
-        SortBy::Name => processes.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+/// Rescales per-core CPU usage so 100% is all `nb_cpus` CPUs, for `--cpu-mode total`.
+pub fn normalize_cpu_usage(processes: &mut [ProcessInfo], nb_cpus: usize) {
+    for process in processes {
+        process.cpu_usage /= nb_cpus.max(1) as f32;
     }
+}
 
-    if let Some(l) = limit {
-        processes.truncate(l);
+/// The thread count from `/proc/<pid>/status`; `None` on other platforms.
+fn thread_count(pid: u32) -> Option<u32> {
+    if !cfg!(target_os = "linux") {
+        return None;
     }
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    status.lines().find_map(|l| l.strip_prefix("Threads:"))?.trim().parse().ok()
+}
 
-    processes
+/// Sorts by each key in turn, each in its default direction unless `descending` is given.
+pub fn sort_processes(processes: &mut [ProcessInfo], keys: &[SortBy], descending: Option<bool>) {
+    processes.sort_by(|a, b| {
+        keys.iter().map(|key| {
+            let order = match key {
+                SortBy::Cpu => a.cpu_usage.total_cmp(&b.cpu_usage),
+                SortBy::Memory => a.memory.cmp(&b.memory),
+                SortBy::Pid => a.pid.cmp(&b.pid),
+                SortBy::Name => natural_cmp(&a.name, &b.name),
+                SortBy::DiskRead => a.read_bytes.cmp(&b.read_bytes),
+                SortBy::DiskWrite => a.written_bytes.cmp(&b.written_bytes),
+            };
+            if descending.unwrap_or(key.descending_by_default()) { order.reverse() } else { order }
+        }).find(|order| order.is_ne()).unwrap_or(Ordering::Equal)
+    });
+}
+
+/// Everything known about one process, or `None` if it doesn't exist.
+pub fn get_process_detail(sys: &System, pid: u32) -> Option<ProcessDetail> {
+    let p = sys.process(Pid::from_u32(pid))?;
+    let users = Users::new_with_refreshed_list();
+    let lossy = |values: &[std::ffi::OsString]| values.iter().map(|v| v.to_string_lossy().into_owned()).collect();
+    let disk = p.disk_usage();
+    Some(ProcessDetail {
+        pid,
+        name: p.name().to_string_lossy().into_owned(),
+        cmd: lossy(p.cmd()),
+        exe: p.exe().map(|path| path.display().to_string()),
+        cwd: p.cwd().map(|path| path.display().to_string()),
+        parent: p.parent().map(|parent| parent.as_u32()),
+        status: p.status().to_string(),
+        user: p.user_id().and_then(|uid| users.get_user_by_id(uid)).map(|user| user.name().to_string()),
+        start_time: p.start_time(),
+        run_time: p.run_time(),
+        cpu_usage: p.cpu_usage(),
+        memory: p.memory(),
+        virtual_memory: p.virtual_memory(),
+        disk_read: disk.total_read_bytes,
+        disk_written: disk.total_written_bytes,
+        threads: thread_count(pid),
+        thread_list: None,
+        environ: lossy(p.environ()),
+    })
+}
+
+/// CPU time in milliseconds and peak resident memory of this process.
+pub fn get_own_usage() -> (Option<u64>, Option<u64>) {
+    let pid = sysinfo::get_current_pid().ok();
+    let mut sys = System::new();
+    if let Some(pid) = pid {
+        sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, ProcessRefreshKind::nothing().with_cpu().with_memory());
+    }
+    let process = pid.and_then(|pid| sys.process(pid));
+    let peak_rss = winperf::peak_working_set()
+        .or_else(|| {
+            // VmHWM is the resident set's high-water mark, in KiB.
+            let status = std::fs::read_to_string("/proc/self/status").ok()?;
+            let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+            line.split_whitespace().nth(1)?.parse::<u64>().ok().map(|kib| kib * 1024)
+        })
+        .or_else(|| process.map(|p| p.memory()));
+    (process.map(|p| p.accumulated_cpu_time()), peak_rss)
+}
+
+/// Lists attached disks, network interfaces, and power supply states for `HardwareWatcher`.
+pub fn get_hardware_snapshot() -> HardwareSnapshot {
+    let disks = Disks::new_with_refreshed_list();
+    let networks = Networks::new_with_refreshed_list();
+    HardwareSnapshot {
+        disks: disks.iter().map(|d| (
+            d.name().to_string_lossy().into_owned(),
+            format!("{}, {}", d.mount_point().display(), format_bytes(d.total_space())),
+        )).collect(),
+        interfaces: networks.keys().cloned().collect(),
+        power: power::power_supplies().into_iter().collect(),
+    }
 }
 
+/// Lists the processes matching `filter` for `ProcessFollower`.
+pub fn get_seen_processes(sys: &System, filter: Option<&ProcessFilter>) -> Vec<SeenProcess> {
+    sys.processes().values()
+        // Only the pattern applies: usage and state change over a process's life, and
+        // crossing a threshold would read as the process starting or exiting.
+        .filter(|p| filter.is_none_or(|f| f.matches(&p.name().to_string_lossy(), p.cmd())))
+        .map(|p| SeenProcess {
+            pid: p.pid().as_u32(),
+            start_time: p.start_time(),
+            name: p.name().to_string_lossy().into_owned(),
+            parent: p.parent().map(|pid| pid.as_u32()),
+            run_time: p.run_time(),
+        })
+        .collect()
+}
+
+/// Processes below this much memory and 0.0% CPU count as idle.
+pub const IDLE_PROCESS_MEMORY: u64 = 1024 * 1024;
+
+/// Rows that `--hide-idle` leaves out.
+pub trait Idle {
+    fn is_idle(&self) -> bool;
+}
+
+impl Idle for NetworkInfo {
+    fn is_idle(&self) -> bool {
+        self.received == 0 && self.transmitted == 0
+    }
+}
+
+impl Idle for ProcessInfo {
+    fn is_idle(&self) -> bool {
+        // Anything that would display as 0.0%.
+        self.cpu_usage < 0.05 && self.memory < IDLE_PROCESS_MEMORY
+    }
+}
+
+impl Idle for ComponentInfo {
+    fn is_idle(&self) -> bool {
+        self.temperature.is_none()
+    }
+}
+
+/// Drops idle rows when `hide` is set.
+pub fn retain_active<T: Idle>(rows: &mut Vec<T>, hide: bool) {
+    if hide {
+        rows.retain(|row| !row.is_idle());
+    }
+}
+
+/// Compares strings so embedded numbers sort by value ("sda2" < "sda10"),
+/// ignoring case except as a final tie-breaker.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut x, mut y) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (x.peek().copied(), y.peek().copied()) {
+            (None, None) => return a.cmp(b),
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(c), Some(d)) if c.is_ascii_digit() && d.is_ascii_digit() => {
+                let take_number = |it: &mut std::iter::Peekable<std::str::Chars>| {
+                    let mut digits = String::new();
+                    while let Some(c) = it.next_if(|c| c.is_ascii_digit()) {
+                        digits.push(c);
+                    }
+                    digits
+                };
+                let (m, n) = (take_number(&mut x), take_number(&mut y));
+                let (m, n) = (m.trim_start_matches('0'), n.trim_start_matches('0'));
+                let ord = m.len().cmp(&n.len()).then_with(|| m.cmp(n));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            (Some(c), Some(d)) => {
+                let ord = c.to_lowercase().cmp(d.to_lowercase());
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+                x.next();
+                y.next();
+            }
+        }
+    }
+}
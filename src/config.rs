@@ -0,0 +1,131 @@
+//! User defaults from `~/.config/sysinfo-cli/config.toml` (or `--config`), merged
+//! under the command line: an option given as a flag always wins.
+
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use serde::{Deserialize, Deserializer};
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::args::{parse_interval, Cli, ColorMode, OutputFormat, Units};
+use crate::layout::Layout;
+
+/// Defaults loaded from a TOML file such as:
+///
+/// ```toml
+/// format = "json"
+/// watch = 5
+/// color = "never"
+/// units = "mib"
+/// si = true
+///
+/// [layout.columns]
+/// processes = ["pid", "name", "cpu", "memory"]
+///
+/// [alert]
+/// rules = ["cpu > 90", "disk_avail@/ < 10GiB"]
+///
+/// [daemon]
+/// every = ["cpu=5s", "disks=1m"]
+/// to = ["file:///var/log/sysinfo.jsonl", "mqtt://broker"]
+/// ```
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub format: Option<OutputFormat>,
+    /// Interval for a bare `--watch`: seconds (`5`, `0.5`) or a duration (`"250ms"`).
+    #[serde(default, deserialize_with = "interval")]
+    pub watch: Option<Duration>,
+    pub color: Option<ColorMode>,
+    pub units: Option<Units>,
+    /// Decimal units, as with `--si`.
+    pub si: Option<bool>,
+    /// Table layout, as in a `--layout` file; ignored when `--layout` is given.
+    pub layout: Option<Layout>,
+    #[serde(default)]
+    pub alert: AlertConfig,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct AlertConfig {
+    /// Checked by `alert` when no rules are given on the command line.
+    #[serde(default)]
+    pub rules: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct DaemonConfig {
+    /// Schedules such as `cpu=5s`, used by `daemon` when none are given with `--every`.
+    #[serde(default)]
+    pub every: Vec<String>,
+    /// Sink URLs, used by `daemon` when none are given with `--to`.
+    #[serde(default)]
+    pub to: Vec<String>,
+}
+
+/// `$XDG_CONFIG_HOME/sysinfo-cli/config.toml`, falling back to `~/.config`; on
+/// Windows, under `%APPDATA%`.
+pub fn default_path() -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    dir.map(|dir| dir.join("sysinfo-cli").join("config.toml"))
+}
+
+/// Reads `path`, or the default location if none is given. Only an explicitly
+/// given file has to exist.
+pub fn load_config(path: Option<&Path>) -> Result<Config, String> {
+    let path = match path {
+        Some(path) => path.to_path_buf(),
+        None => match default_path() {
+            Some(path) if path.exists() => path,
+            _ => return Ok(Config::default()),
+        },
+    };
+    let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn interval<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Interval {
+        Seconds(f64),
+        Text(String),
+    }
+    let value = match Interval::deserialize(deserializer)? {
+        Interval::Seconds(secs) => secs.to_string(),
+        Interval::Text(text) => text,
+    };
+    parse_interval(&value).map(Some).map_err(serde::de::Error::custom)
+}
+
+/// Fills every option that wasn't given on the command line from `config`.
+pub fn apply(config: &Config, cli: &mut Cli, matches: &ArgMatches) {
+    let from_cli = |id: &str| matches.value_source(id) == Some(ValueSource::CommandLine);
+    if let Some(format) = config.format && !from_cli("format") && !cli.json {
+        cli.format = format;
+    }
+    if let Some(interval @ None) = cli.watch.as_mut() {
+        *interval = config.watch;
+    }
+    if let Some(color) = config.color && !from_cli("color") {
+        cli.color = color;
+    }
+    if let Some(units) = config.units && !from_cli("units") {
+        cli.units = units;
+    }
+    if let Some(si) = config.si && !from_cli("si") {
+        cli.si = si;
+    }
+}
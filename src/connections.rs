@@ -0,0 +1,287 @@
+//! `connections`: open TCP and UDP sockets and the processes that own them, read from
+//! `/proc/net` and `/proc/<pid>/fd` (Linux) or the IP Helper tables (Windows).
+
+use std::io;
+use std::net::IpAddr;
+use sysinfo::{Pid, System, Users};
+use crate::models::{Connection, PortOwner, SocketProtocol, SocketState};
+
+/// The `connections` filters. Without `--tcp` or `--udp`, both are listed.
+#[derive(Default)]
+pub struct ConnectionFilter {
+    pub tcp: bool,
+    pub udp: bool,
+    pub listening: bool,
+    /// Any of these local or remote ports; all ports when empty.
+    pub ports: Vec<u16>,
+}
+
+impl ConnectionFilter {
+    pub fn matches(&self, c: &Connection) -> bool {
+        let protocol = match c.protocol {
+            SocketProtocol::Tcp => self.tcp || !self.udp,
+            SocketProtocol::Udp => self.udp || !self.tcp,
+        };
+        protocol
+            && (!self.listening || is_listening(c))
+            && (self.ports.is_empty() || self.ports.contains(&c.local_port) || self.ports.contains(&c.remote_port))
+    }
+}
+
+/// Whether `c` waits for peers: a listening TCP socket or an unconnected UDP one, as `ss -l` counts them.
+pub fn is_listening(c: &Connection) -> bool {
+    matches!(c.state, SocketState::Listen | SocketState::Unconnected)
+}
+
+/// Open sockets matching `filter`, by protocol and local port.
+pub fn get_connections(sys: &System, filter: &ConnectionFilter) -> io::Result<Vec<Connection>> {
+    #[cfg(target_os = "linux")]
+    let mut connections = linux::get_connections(sys)?;
+    #[cfg(windows)]
+    let mut connections = windows::get_connections(sys)?;
+    #[cfg(not(any(target_os = "linux", windows)))]
+    let mut connections: Vec<Connection> = {
+        let _ = sys;
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "connections are only available on Linux and Windows"));
+    };
+    connections.retain(|c| filter.matches(c));
+    connections.sort_by(|a, b| {
+        (a.protocol, a.local_port, &a.local_address, &a.remote_address, a.remote_port)
+            .cmp(&(b.protocol, b.local_port, &b.local_address, &b.remote_address, b.remote_port))
+    });
+    Ok(connections)
+}
+
+/// Sockets bound to local `port`, listening or connected, with the user and command
+/// line of the process owning each.
+pub fn get_port_owners(sys: &System, port: u16) -> io::Result<Vec<PortOwner>> {
+    let filter = ConnectionFilter { ports: vec![port], ..Default::default() };
+    let users = Users::new_with_refreshed_list();
+    Ok(get_connections(sys, &filter)?.into_iter().filter(|c| c.local_port == port).map(|connection| {
+        let process = connection.pid.and_then(|pid| sys.process(Pid::from_u32(pid)));
+        PortOwner {
+            user: process.and_then(|p| p.user_id()).and_then(|uid| users.get_user_by_id(uid)).map(|user| user.name().to_string()),
+            command: process.map(|p| p.cmd().iter().map(|arg| arg.to_string_lossy()).collect::<Vec<_>>().join(" ")).filter(|cmd| !cmd.is_empty()),
+            connection,
+        }
+    }).collect())
+}
+
+/// Parses `/proc/net/{tcp,tcp6,udp,udp6}` into connections and the inodes of their sockets.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn parse_proc_net(text: &str, protocol: SocketProtocol) -> Vec<(Connection, u64)> {
+    text.lines().skip(1).filter_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (local_address, local_port) = parse_socket_address(fields.get(1)?)?;
+        let (remote_address, remote_port) = parse_socket_address(fields.get(2)?)?;
+        let state = u8::from_str_radix(fields.get(3)?, 16).ok()?;
+        let inode = fields.get(9)?.parse().ok()?;
+        let connection = Connection {
+            protocol,
+            local_address: local_address.to_string(),
+            local_port,
+            remote_address: remote_address.to_string(),
+            remote_port,
+            state: proc_state(protocol, state),
+            pid: None,
+            process: None,
+        };
+        Some((connection, inode))
+    }).collect()
+}
+
+/// `0100007F:0035` is 127.0.0.1 port 53. Addresses are the kernel's 32-bit words printed
+/// in hex, so their bytes are in this machine's order; ports are plain numbers.
+fn parse_socket_address(field: &str) -> Option<(IpAddr, u16)> {
+    let (address, port) = field.split_once(':')?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for start in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(start..start + 8)?, 16).ok()?;
+        bytes.extend(word.to_ne_bytes());
+    }
+    let address = match bytes.len() {
+        4 => IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?),
+        16 => IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?),
+        _ => return None,
+    };
+    Some((address, port))
+}
+
+/// The kernel's `TCP_*` state numbers. UDP sockets only use `ESTABLISHED` (connected)
+/// and `CLOSE` (unconnected).
+fn proc_state(protocol: SocketProtocol, state: u8) -> SocketState {
+    use SocketState::*;
+    match (protocol, state) {
+        (SocketProtocol::Udp, 7) => Unconnected,
+        (_, 1) => Established,
+        (_, 2) => SynSent,
+        (_, 3) => SynReceived,
+        (_, 4) => FinWait1,
+        (_, 5) => FinWait2,
+        (_, 6) => TimeWait,
+        (_, 7) => Closed,
+        (_, 8) => CloseWait,
+        (_, 9) => LastAck,
+        (_, 10) => Listen,
+        (_, 11) => Closing,
+        _ => Unknown,
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::HashMap;
+    use std::fs;
+    use std::io;
+    use sysinfo::{Pid, System};
+    use crate::models::{Connection, SocketProtocol};
+    use super::parse_proc_net;
+
+    pub fn get_connections(sys: &System) -> io::Result<Vec<Connection>> {
+        let mut sockets = Vec::new();
+        for (file, protocol) in [("tcp", SocketProtocol::Tcp), ("tcp6", SocketProtocol::Tcp), ("udp", SocketProtocol::Udp), ("udp6", SocketProtocol::Udp)] {
+            match fs::read_to_string(format!("/proc/net/{}", file)) {
+                Ok(text) => sockets.extend(parse_proc_net(&text, protocol)),
+                // Kernels built without IPv6 have no tcp6 and udp6.
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
+        }
+        let owners = socket_owners(sys);
+        Ok(sockets.into_iter().map(|(mut connection, inode)| {
+            if let Some((pid, name)) = owners.get(&inode) {
+                connection.pid = Some(*pid);
+                connection.process = Some(name.clone());
+            }
+            connection
+        }).collect())
+    }
+
+    /// The process holding each socket inode open, from the `socket:[N]` links in
+    /// `/proc/<pid>/fd`. Other users' processes are skipped unless running as root.
+    fn socket_owners(sys: &System) -> HashMap<u64, (u32, String)> {
+        let mut owners = HashMap::new();
+        let Ok(entries) = fs::read_dir("/proc") else { return owners };
+        for entry in entries.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else { continue };
+            let Ok(fds) = fs::read_dir(entry.path().join("fd")) else { continue };
+            let mut name = None;
+            for fd in fds.flatten() {
+                let Ok(link) = fs::read_link(fd.path()) else { continue };
+                let Some(inode) = link.to_str()
+                    .and_then(|l| l.strip_prefix("socket:["))
+                    .and_then(|l| l.strip_suffix(']'))
+                    .and_then(|i| i.parse::<u64>().ok()) else { continue };
+                let name = name.get_or_insert_with(|| sys.process(Pid::from_u32(pid))
+                    .map(|p| p.name().to_string_lossy().into_owned())
+                    .or_else(|| fs::read_to_string(entry.path().join("comm")).ok().map(|c| c.trim().to_string()))
+                    .unwrap_or_default());
+                owners.entry(inode).or_insert_with(|| (pid, name.clone()));
+            }
+        }
+        owners
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::io;
+    use std::net::{Ipv4Addr, Ipv6Addr};
+    use sysinfo::{Pid, System};
+    use windows_sys::Win32::Foundation::{ERROR_INSUFFICIENT_BUFFER, NO_ERROR};
+    use windows_sys::Win32::NetworkManagement::IpHelper::*;
+    use windows_sys::Win32::Networking::WinSock::{AF_INET, AF_INET6};
+    use crate::models::{Connection, SocketProtocol, SocketState};
+
+    pub fn get_connections(sys: &System) -> io::Result<Vec<Connection>> {
+        let name = |pid: u32| sys.process(Pid::from_u32(pid)).map(|p| p.name().to_string_lossy().into_owned());
+        let connection = |protocol, local: (String, u32), remote: (String, u32), state, pid: u32| Connection {
+            protocol,
+            local_address: local.0,
+            // Ports are in network byte order in the low 16 bits.
+            local_port: u16::from_be(local.1 as u16),
+            remote_address: remote.0,
+            remote_port: u16::from_be(remote.1 as u16),
+            state,
+            pid: Some(pid),
+            process: name(pid),
+        };
+        let unspecified_v4 = || (Ipv4Addr::UNSPECIFIED.to_string(), 0);
+        let unspecified_v6 = || (Ipv6Addr::UNSPECIFIED.to_string(), 0);
+
+        let mut connections = Vec::new();
+        // SAFETY: each table is filled by the matching call with its class and family.
+        unsafe {
+            let tcp = table(|buffer, size| GetExtendedTcpTable(buffer, size, 0, AF_INET as u32, TCP_TABLE_OWNER_PID_ALL, 0))?;
+            for row in rows::<MIB_TCPROW_OWNER_PID>(&tcp) {
+                let local = (Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes()).to_string(), row.dwLocalPort);
+                let remote = (Ipv4Addr::from(row.dwRemoteAddr.to_ne_bytes()).to_string(), row.dwRemotePort);
+                connections.push(connection(SocketProtocol::Tcp, local, remote, tcp_state(row.dwState), row.dwOwningPid));
+            }
+            let tcp6 = table(|buffer, size| GetExtendedTcpTable(buffer, size, 0, AF_INET6 as u32, TCP_TABLE_OWNER_PID_ALL, 0))?;
+            for row in rows::<MIB_TCP6ROW_OWNER_PID>(&tcp6) {
+                let local = (Ipv6Addr::from(row.ucLocalAddr).to_string(), row.dwLocalPort);
+                let remote = (Ipv6Addr::from(row.ucRemoteAddr).to_string(), row.dwRemotePort);
+                connections.push(connection(SocketProtocol::Tcp, local, remote, tcp_state(row.dwState), row.dwOwningPid));
+            }
+            // The UDP tables don't say whether a socket is connected, or to what.
+            let udp = table(|buffer, size| GetExtendedUdpTable(buffer, size, 0, AF_INET as u32, UDP_TABLE_OWNER_PID, 0))?;
+            for row in rows::<MIB_UDPROW_OWNER_PID>(&udp) {
+                let local = (Ipv4Addr::from(row.dwLocalAddr.to_ne_bytes()).to_string(), row.dwLocalPort);
+                connections.push(connection(SocketProtocol::Udp, local, unspecified_v4(), SocketState::Unconnected, row.dwOwningPid));
+            }
+            let udp6 = table(|buffer, size| GetExtendedUdpTable(buffer, size, 0, AF_INET6 as u32, UDP_TABLE_OWNER_PID, 0))?;
+            for row in rows::<MIB_UDP6ROW_OWNER_PID>(&udp6) {
+                let local = (Ipv6Addr::from(row.ucLocalAddr).to_string(), row.dwLocalPort);
+                connections.push(connection(SocketProtocol::Udp, local, unspecified_v6(), SocketState::Unconnected, row.dwOwningPid));
+            }
+        }
+        Ok(connections)
+    }
+
+    /// Calls `get` with a growing buffer until the table fits. The buffer is made of
+    /// `u32`s so the rows, all 32-bit fields, are aligned.
+    fn table(get: impl Fn(*mut c_void, *mut u32) -> u32) -> io::Result<Vec<u32>> {
+        let mut buffer: Vec<u32> = Vec::new();
+        let mut size = 0u32;
+        loop {
+            let pointer = if buffer.is_empty() { std::ptr::null_mut() } else { buffer.as_mut_ptr().cast() };
+            match get(pointer, &mut size) {
+                NO_ERROR => return Ok(buffer),
+                // Sockets opened since the last call can make it grow again.
+                ERROR_INSUFFICIENT_BUFFER => buffer.resize((size as usize).div_ceil(4), 0),
+                code => return Err(io::Error::from_raw_os_error(code as i32)),
+            }
+        }
+    }
+
+    /// The rows of a `MIB_*TABLE_OWNER_PID`: an entry count followed by the rows.
+    ///
+    /// # Safety
+    /// `buffer` must hold a table whose rows are `R`.
+    unsafe fn rows<R: Copy>(buffer: &[u32]) -> Vec<R> {
+        let Some(&count) = buffer.first() else { return Vec::new() };
+        // SAFETY: the caller guarantees `count` rows of `R` follow the count.
+        unsafe { std::slice::from_raw_parts(buffer.as_ptr().add(1).cast::<R>(), count as usize).to_vec() }
+    }
+
+    fn tcp_state(state: u32) -> SocketState {
+        use SocketState::*;
+        match state as i32 {
+            MIB_TCP_STATE_CLOSED | MIB_TCP_STATE_DELETE_TCB => Closed,
+            MIB_TCP_STATE_LISTEN => Listen,
+            MIB_TCP_STATE_SYN_SENT => SynSent,
+            MIB_TCP_STATE_SYN_RCVD => SynReceived,
+            MIB_TCP_STATE_ESTAB => Established,
+            MIB_TCP_STATE_FIN_WAIT1 => FinWait1,
+            MIB_TCP_STATE_FIN_WAIT2 => FinWait2,
+            MIB_TCP_STATE_CLOSE_WAIT => CloseWait,
+            MIB_TCP_STATE_CLOSING => Closing,
+            MIB_TCP_STATE_LAST_ACK => LastAck,
+            MIB_TCP_STATE_TIME_WAIT => TimeWait,
+            _ => Unknown,
+        }
+    }
+}
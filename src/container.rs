@@ -0,0 +1,172 @@
+//! `container`: whether we run inside a container, and the cgroup's CPU quota and
+//! memory limit with usage measured against them rather than the host (Linux).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use crate::models::ContainerInfo;
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// The cgroup version and our cgroup's path from `/proc/self/cgroup`: the memory
+/// controller's line on v1, else the `0::` line on v2. Hybrid setups list both, but
+/// the controllers are on v1 there.
+pub fn parse_cgroup_path(text: &str) -> Option<(u8, String)> {
+    let v1 = text.lines().find_map(|line| {
+        let mut fields = line.splitn(3, ':');
+        let controllers = fields.nth(1)?;
+        controllers.split(',').any(|c| c == "memory").then(|| fields.next()).flatten().map(|path| (1, path.to_string()))
+    });
+    v1.or_else(|| text.lines().find_map(|line| line.strip_prefix("0::")).map(|path| (2, path.to_string())))
+}
+
+/// The runtime a cgroup path belongs to, e.g. `/kubepods/burstable/pod…/…` or
+/// `/system.slice/docker-….scope`.
+pub fn runtime_from_cgroup(path: &str) -> Option<&'static str> {
+    if path.contains("kubepods") {
+        Some("kubernetes")
+    } else if path.contains("libpod") {
+        Some("podman")
+    } else if path.contains("docker") {
+        Some("docker")
+    } else if path.contains("containerd") || path.contains("cri-") {
+        Some("containerd")
+    } else if path.contains("/lxc") {
+        Some("lxc")
+    } else {
+        None
+    }
+}
+
+/// The first 64-character hex id in `text`, the way runtimes name containers.
+pub fn container_id(text: &str) -> Option<String> {
+    text.split(|c: char| !c.is_ascii_hexdigit())
+        .find(|word| word.len() == 64)
+        .map(str::to_string)
+}
+
+/// CPUs allowed by a v2 `cpu.max` such as `150000 100000`; `None` for `max`.
+pub fn parse_cpu_max(text: &str) -> Option<f64> {
+    let mut fields = text.split_whitespace();
+    let quota: f64 = fields.next()?.parse().ok()?;
+    let period: f64 = fields.next()?.parse().ok()?;
+    (period > 0.0).then_some(quota / period)
+}
+
+/// A memory limit in bytes; `None` for v2's `max` and v1's "no limit", which is the
+/// largest page-aligned value.
+pub fn parse_memory_limit(text: &str) -> Option<u64> {
+    text.trim().parse().ok().filter(|&limit| limit < 1 << 62)
+}
+
+/// Our cgroup's directory for `controller` (only used on v1). With a cgroup
+/// namespace, as in most containers, our cgroup is mounted at the root instead.
+fn cgroup_dir(version: u8, path: &str, controller: &str) -> Option<PathBuf> {
+    let root = match version {
+        2 => PathBuf::from(CGROUP_ROOT),
+        _ => Path::new(CGROUP_ROOT).join(controller),
+    };
+    [root.join(path.trim_start_matches('/')), root].into_iter().find(|dir| dir.is_dir())
+}
+
+fn read(dir: &Option<PathBuf>, file: &str) -> Option<String> {
+    fs::read_to_string(dir.as_ref()?.join(file)).ok()
+}
+
+fn own_cgroup() -> Option<(u8, String)> {
+    fs::read_to_string("/proc/self/cgroup").ok().and_then(|text| parse_cgroup_path(&text))
+}
+
+/// CPUs our cgroup's quota allows, or `None` without a quota.
+pub fn cpu_limit() -> Option<f64> {
+    let (version, path) = own_cgroup()?;
+    let dir = cgroup_dir(version, &path, "cpu");
+    match version {
+        2 => parse_cpu_max(&read(&dir, "cpu.max")?),
+        _ => {
+            let quota: f64 = read(&dir, "cpu.cfs_quota_us")?.trim().parse().ok()?;
+            let period: f64 = read(&dir, "cpu.cfs_period_us")?.trim().parse().ok()?;
+            (quota > 0.0 && period > 0.0).then_some(quota / period)
+        }
+    }
+}
+
+/// CPU time our cgroup has used, in microseconds.
+fn cpu_usage_usec() -> Option<u64> {
+    let (version, path) = own_cgroup()?;
+    match version {
+        2 => {
+            let stat = read(&cgroup_dir(2, &path, ""), "cpu.stat")?;
+            stat.lines().find_map(|line| line.strip_prefix("usage_usec ")?.trim().parse().ok())
+        }
+        _ => {
+            let nanos: u64 = read(&cgroup_dir(1, &path, "cpuacct"), "cpuacct.usage")?.trim().parse().ok()?;
+            Some(nanos / 1000)
+        }
+    }
+}
+
+/// The previous reading of the cgroup's CPU time, so usage covers the time since.
+#[derive(Default)]
+pub struct CpuSampler {
+    previous: Option<(Instant, u64)>,
+}
+
+impl CpuSampler {
+    /// CPUs in use on average since the previous call; `None` on the first.
+    pub fn sample(&mut self) -> Option<f64> {
+        let now = (Instant::now(), cpu_usage_usec()?);
+        let previous = self.previous.replace(now)?;
+        let elapsed = now.0.duration_since(previous.0).as_micros() as f64;
+        (elapsed > 0.0).then(|| now.1.saturating_sub(previous.1) as f64 / elapsed)
+    }
+}
+
+/// What runtime, if any, we're in, and our cgroup's limits and usage. `cpus_used`
+/// comes from a [`CpuSampler`]; the host figures put the limits in proportion.
+pub fn get_container_info(cpus_used: Option<f64>, host_cpus: usize, host_memory: u64) -> ContainerInfo {
+    let cgroup = own_cgroup();
+    let path = cgroup.as_ref().map(|(_, path)| path.as_str()).unwrap_or_default();
+    let mountinfo = fs::read_to_string("/proc/self/mountinfo").unwrap_or_default();
+    // With a cgroup namespace the path is just `/`, so look for the runtime's own marks too.
+    let runtime = if std::env::var_os("KUBERNETES_SERVICE_HOST").is_some() {
+        Some("kubernetes")
+    } else if Path::new("/run/.containerenv").exists() {
+        Some("podman")
+    } else if Path::new("/.dockerenv").exists() {
+        Some("docker")
+    } else {
+        runtime_from_cgroup(path)
+    };
+    let id = runtime.and_then(|_| container_id(path).or_else(|| {
+        mountinfo.lines().filter(|line| line.contains("/containers/")).find_map(container_id)
+    }));
+    let (memory_limit, memory_used) = match &cgroup {
+        Some((2, path)) => {
+            let dir = cgroup_dir(2, path, "");
+            (read(&dir, "memory.max").and_then(|t| parse_memory_limit(&t)), read(&dir, "memory.current").and_then(|t| t.trim().parse().ok()))
+        }
+        Some((_, path)) => {
+            let dir = cgroup_dir(1, path, "memory");
+            (read(&dir, "memory.limit_in_bytes").and_then(|t| parse_memory_limit(&t)), read(&dir, "memory.usage_in_bytes").and_then(|t| t.trim().parse().ok()))
+        }
+        None => (None, None),
+    };
+    let cpu_limit = cpu_limit();
+    let percent = |used: f64, of: f64| (of > 0.0).then(|| (used / of * 100.0) as f32);
+    ContainerInfo {
+        in_container: runtime.is_some(),
+        runtime: runtime.map(str::to_string),
+        id,
+        cgroup_version: cgroup.as_ref().map(|(version, _)| *version),
+        cgroup_path: cgroup.map(|(_, path)| path),
+        cpu_limit,
+        cpus_used,
+        cpu_limit_percent: cpus_used.zip(cpu_limit).and_then(|(used, limit)| percent(used, limit)),
+        memory_limit,
+        memory_used,
+        memory_limit_percent: memory_used.zip(memory_limit).and_then(|(used, limit)| percent(used as f64, limit as f64)),
+        host_cpus,
+        host_memory,
+    }
+}
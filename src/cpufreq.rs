@@ -0,0 +1,28 @@
+//! CPU frequency limits and topology from `/sys/devices/system/cpu` (Linux). Other
+//! platforms have no such files, so every lookup comes back empty there.
+
+use std::collections::BTreeSet;
+use std::fs;
+
+fn read_u64(cpu: usize, file: &str) -> Option<u64> {
+    fs::read_to_string(format!("/sys/devices/system/cpu/cpu{}/{}", cpu, file)).ok()?.trim().parse().ok()
+}
+
+/// The lowest and highest frequency `cpu` can run at, in MHz.
+pub fn frequency_limits(cpu: usize) -> (Option<u64>, Option<u64>) {
+    let mhz = |file: &str| read_u64(cpu, file).map(|khz| khz / 1000);
+    (mhz("cpufreq/cpuinfo_min_freq"), mhz("cpufreq/cpuinfo_max_freq"))
+}
+
+/// The socket (physical package) and core `cpu` belongs to.
+pub fn topology_ids(cpu: usize) -> Option<(u64, u64)> {
+    Some((read_u64(cpu, "topology/physical_package_id")?, read_u64(cpu, "topology/core_id")?))
+}
+
+/// How many sockets and physical cores the `(socket, core)` pairs of the logical CPUs
+/// span; core ids repeat across sockets, so cores are counted per socket.
+pub fn count_topology(ids: &[(u64, u64)]) -> (usize, usize) {
+    let sockets: BTreeSet<u64> = ids.iter().map(|&(socket, _)| socket).collect();
+    let cores: BTreeSet<(u64, u64)> = ids.iter().copied().collect();
+    (sockets.len(), cores.len())
+}
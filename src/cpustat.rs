@@ -0,0 +1,70 @@
+//! Per-CPU time counters from `/proc/stat` (Linux), for `cpu --detailed`: where time
+//! went between two reads, which sysinfo only reports as a single usage figure.
+
+use std::fs;
+use crate::models::CpuBreakdown;
+
+/// Cumulative time in clock ticks, in `/proc/stat` column order.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+/// Every `cpu` line of `/proc/stat`: `None` for the aggregate line, else the CPU number.
+/// Guest time is left out because the kernel already counts it as user time.
+pub fn parse_proc_stat(text: &str) -> Vec<(Option<usize>, CpuTimes)> {
+    text.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let id = match fields.next()?.strip_prefix("cpu")? {
+            "" => None,
+            n => Some(n.parse().ok()?),
+        };
+        let mut next = || fields.next().and_then(|f| f.parse().ok()).unwrap_or(0);
+        let times = CpuTimes {
+            user: next(),
+            nice: next(),
+            system: next(),
+            idle: next(),
+            iowait: next(),
+            irq: next(),
+            softirq: next(),
+            steal: next(),
+        };
+        Some((id, times))
+    }).collect()
+}
+
+/// The current counters, or none where there's no `/proc/stat`.
+pub fn read_proc_stat() -> Vec<(Option<usize>, CpuTimes)> {
+    fs::read_to_string("/proc/stat").map(|text| parse_proc_stat(&text)).unwrap_or_default()
+}
+
+/// Each counter's share of the time between `before` and `after`, in percent. `None`
+/// when no time passed, or the counters went back, as when a CPU came back online.
+pub fn breakdown(before: &CpuTimes, after: &CpuTimes) -> Option<CpuBreakdown> {
+    let fields = |t: &CpuTimes| [t.user, t.nice, t.system, t.idle, t.iowait, t.irq, t.softirq, t.steal];
+    let deltas: Option<Vec<u64>> = fields(after).iter().zip(fields(before)).map(|(a, b)| a.checked_sub(b)).collect();
+    let deltas = deltas?;
+    let total: u64 = deltas.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let pct = |i: usize| deltas[i] as f32 * 100.0 / total as f32;
+    Some(CpuBreakdown {
+        user: pct(0),
+        nice: pct(1),
+        system: pct(2),
+        idle: pct(3),
+        iowait: pct(4),
+        irq: pct(5),
+        softirq: pct(6),
+        steal: pct(7),
+    })
+}
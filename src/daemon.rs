@@ -0,0 +1,143 @@
+//! `daemon`: collects metric groups on independent schedules and sends every sample
+//! to the configured sinks until stopped.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+use clap::ValueEnum;
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+use crate::args::MetricGroup;
+use crate::collector::{get_components_info, get_cpu_info, get_load_average, get_memory_info};
+use crate::prometheus::{Exposition, ToMetrics};
+use crate::sink::{record, Sink};
+use crate::watch::{sleep_unless_stopped, DiskRates, NetworkRates};
+
+/// When each group is due next.
+pub struct Schedule {
+    jobs: Vec<(MetricGroup, Duration, Instant)>,
+}
+
+impl Schedule {
+    /// Every group is first due at `start`. A group listed twice keeps its last interval.
+    pub fn new(every: &[(MetricGroup, Duration)], start: Instant) -> Self {
+        let mut jobs: Vec<(MetricGroup, Duration, Instant)> = Vec::new();
+        for (group, interval) in every {
+            jobs.retain(|(g, _, _)| g != group);
+            jobs.push((*group, *interval, start));
+        }
+        Schedule { jobs }
+    }
+
+    /// The groups due at `now`, each moved on by its interval. Ticks missed while
+    /// collecting took longer are skipped rather than run back to back.
+    pub fn due(&mut self, now: Instant) -> Vec<MetricGroup> {
+        let mut due = Vec::new();
+        for (group, interval, next) in &mut self.jobs {
+            if *next <= now {
+                due.push(*group);
+                while *next <= now {
+                    *next += *interval;
+                }
+            }
+        }
+        due
+    }
+
+    /// When the next group is due.
+    pub fn next(&self) -> Option<Instant> {
+        self.jobs.iter().map(|(_, _, next)| *next).min()
+    }
+}
+
+/// State kept between samples so CPU usage and rates cover the time since the last one.
+struct Collector {
+    sys: System,
+    disk_rates: DiskRates,
+    network_rates: NetworkRates,
+}
+
+impl Collector {
+    /// `None` when the group isn't available here, such as the load average on Windows.
+    fn collect(&mut self, group: MetricGroup) -> serde_json::Result<Option<(serde_json::Value, Exposition)>> {
+        let mut metrics = Exposition::default();
+        let report = match group {
+            MetricGroup::Cpu => {
+                self.sys.refresh_cpu_usage();
+                let info = get_cpu_info(&self.sys);
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+            MetricGroup::Memory => {
+                self.sys.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram().with_swap());
+                let info = get_memory_info(&self.sys);
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+            MetricGroup::Load => {
+                let Some(info) = get_load_average() else { return Ok(None) };
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+            MetricGroup::Disks => {
+                let info = self.disk_rates.sample(true);
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+            MetricGroup::Network => {
+                let info = self.network_rates.sample(false);
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+            MetricGroup::Temp => {
+                let info = get_components_info();
+                info.write_metrics(&mut metrics);
+                serde_json::to_value(info)
+            }
+        };
+        Ok(Some((report?, metrics)))
+    }
+}
+
+pub fn group_name(group: MetricGroup) -> String {
+    group.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default()
+}
+
+/// Collects each group when it's due and sends it to every sink, or prints it as a
+/// JSON line without sinks, until `stop` is set. Returns how many samples were taken.
+pub fn run(every: &[(MetricGroup, Duration)], sinks: &mut [Sink], stop: &AtomicBool) -> usize {
+    let mut collector = Collector {
+        sys: System::new_with_specifics(RefreshKind::nothing().with_cpu(CpuRefreshKind::nothing().with_cpu_usage())),
+        disk_rates: DiskRates::default(),
+        network_rates: NetworkRates::default(),
+    };
+    // The first CPU usage needs a baseline.
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    let mut schedule = Schedule::new(every, Instant::now());
+    let mut count = 0;
+    while !stop.load(Ordering::SeqCst) {
+        for group in schedule.due(Instant::now()) {
+            let name = group_name(group);
+            let (report, metrics) = match collector.collect(group) {
+                Ok(Some(sample)) => sample,
+                Ok(None) => continue,
+                Err(e) => {
+                    eprintln!("Error serializing {}: {}", name, e);
+                    continue;
+                }
+            };
+            if sinks.is_empty() && writeln!(io::stdout(), "{}", record(&name, &report, SystemTime::now())).is_err() {
+                // Nothing reads the samples any more, e.g. after `| head`.
+                return count;
+            }
+            for sink in sinks.iter_mut() {
+                if let Err(e) = sink.publish(&name, Some(&report), &metrics) {
+                    eprintln!("Error sending {} to a sink: {}", name, e);
+                }
+            }
+            count += 1;
+        }
+        let Some(next) = schedule.next() else { break };
+        sleep_unless_stopped(next.saturating_duration_since(Instant::now()), stop);
+    }
+    count
+}
@@ -0,0 +1,155 @@
+//! `docker`: running containers with their resource usage, from the Docker Engine API
+//! over its Unix socket, and the host processes that belong to each. Other platforms
+//! report the command unavailable.
+
+use std::io;
+use serde_json::Value;
+use sysinfo::System;
+use crate::collector::natural_cmp;
+use crate::models::{DockerContainer, DockerProcess};
+
+/// Where the Docker socket is: `--socket`, else a `unix://` `DOCKER_HOST`, else the
+/// default path.
+pub fn socket_path(socket: Option<&str>) -> String {
+    if let Some(socket) = socket {
+        return socket.to_string();
+    }
+    std::env::var("DOCKER_HOST").ok()
+        .and_then(|host| host.strip_prefix("unix://").map(str::to_string))
+        .unwrap_or_else(|| "/var/run/docker.sock".to_string())
+}
+
+/// Running containers from `GET /containers/json`, without usage figures yet, in
+/// natural order of their names (`worker-2` before `worker-10`).
+pub fn parse_containers(list: &Value) -> Vec<DockerContainer> {
+    let Some(list) = list.as_array() else { return Vec::new() };
+    let text = |c: &Value, key: &str| c[key].as_str().unwrap_or_default().to_string();
+    let mut containers: Vec<DockerContainer> = list.iter().map(|c| DockerContainer {
+        id: text(c, "Id").chars().take(12).collect(),
+        // Names keep the leading slash of the legacy link syntax.
+        name: c["Names"][0].as_str().unwrap_or_default().trim_start_matches('/').to_string(),
+        image: text(c, "Image"),
+        status: text(c, "Status"),
+        ..Default::default()
+    }).collect();
+    containers.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+    containers
+}
+
+/// Fills in usage from a `GET /containers/{id}/stats?stream=false` body, computed the
+/// way `docker stats` does: CPU over the sample the daemon takes, where 100% is one
+/// CPU, and memory without the inactive page cache.
+pub fn apply_stats(container: &mut DockerContainer, stats: &Value) {
+    let cpu = &stats["cpu_stats"];
+    let precpu = &stats["precpu_stats"];
+    let cpu_delta = cpu["cpu_usage"]["total_usage"].as_u64().zip(precpu["cpu_usage"]["total_usage"].as_u64()).and_then(|(a, b)| a.checked_sub(b));
+    let system_delta = cpu["system_cpu_usage"].as_u64().zip(precpu["system_cpu_usage"].as_u64()).and_then(|(a, b)| a.checked_sub(b));
+    let online = cpu["online_cpus"].as_u64()
+        .or_else(|| cpu["cpu_usage"]["percpu_usage"].as_array().map(|cpus| cpus.len() as u64))
+        .unwrap_or(1);
+    container.cpu_usage = match (cpu_delta, system_delta) {
+        (Some(cpu), Some(system)) if system > 0 => Some((cpu as f64 / system as f64 * online as f64 * 100.0) as f32),
+        _ => None,
+    };
+
+    let memory = &stats["memory_stats"];
+    // cgroup v2 reports inactive_file, v1 total_inactive_file.
+    let inactive = ["inactive_file", "total_inactive_file"].iter().find_map(|key| memory["stats"][key].as_u64()).unwrap_or(0);
+    container.memory_usage = memory["usage"].as_u64().map(|usage| usage.saturating_sub(inactive));
+    container.memory_limit = memory["limit"].as_u64();
+
+    if let Some(networks) = stats["networks"].as_object() {
+        let sum = |key: &str| networks.values().filter_map(|n| n[key].as_u64()).sum();
+        container.network_received = Some(sum("rx_bytes"));
+        container.network_transmitted = Some(sum("tx_bytes"));
+    }
+    if let Some(entries) = stats["blkio_stats"]["io_service_bytes_recursive"].as_array() {
+        let sum = |op: &str| entries.iter()
+            .filter(|e| e["op"].as_str().is_some_and(|o| o.eq_ignore_ascii_case(op)))
+            .filter_map(|e| e["value"].as_u64())
+            .sum();
+        container.block_read = Some(sum("read"));
+        container.block_written = Some(sum("write"));
+    }
+}
+
+/// Host PIDs from a `GET /containers/{id}/top` body, whose `PID` column holds them.
+pub fn parse_top_pids(top: &Value) -> Vec<u32> {
+    let Some(column) = top["Titles"].as_array().and_then(|titles| titles.iter().position(|t| t == "PID")) else {
+        return Vec::new();
+    };
+    top["Processes"].as_array().map(|rows| {
+        rows.iter().filter_map(|row| row[column].as_str()?.parse().ok()).collect()
+    }).unwrap_or_default()
+}
+
+/// The processes with `pids`, as the host sees them.
+pub fn host_processes(sys: &System, pids: &[u32]) -> Vec<DockerProcess> {
+    pids.iter().filter_map(|&pid| {
+        let process = sys.process(sysinfo::Pid::from_u32(pid))?;
+        Some(DockerProcess {
+            pid,
+            name: process.name().to_string_lossy().into_owned(),
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+        })
+    }).collect()
+}
+
+/// The body of an HTTP/1.0 response, or an error for any status but 200.
+pub fn response_body(response: &[u8]) -> io::Result<&[u8]> {
+    let split = response.windows(4).position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "incomplete HTTP response"))?;
+    let status_line = String::from_utf8_lossy(&response[..split]).lines().next().unwrap_or_default().to_string();
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        return Err(io::Error::other(format!("Docker answered {}", status_line)));
+    }
+    Ok(&response[split + 4..])
+}
+
+/// Running containers, each with its usage and host processes. Every container's
+/// stats take the daemon about a second, so they're fetched side by side.
+pub fn get_containers(sys: &System, socket: &str) -> io::Result<Vec<DockerContainer>> {
+    #[cfg(unix)]
+    {
+        let mut containers = parse_containers(&client::get(socket, "/containers/json")?);
+        std::thread::scope(|scope| {
+            for container in containers.iter_mut() {
+                scope.spawn(move || {
+                    if let Ok(stats) = client::get(socket, &format!("/containers/{}/stats?stream=false", container.id)) {
+                        apply_stats(container, &stats);
+                    }
+                    if let Ok(top) = client::get(socket, &format!("/containers/{}/top", container.id)) {
+                        container.processes = host_processes(sys, &parse_top_pids(&top));
+                    }
+                });
+            }
+        });
+        Ok(containers)
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = (sys, socket);
+        Err(io::Error::new(io::ErrorKind::Unsupported, "docker is only available on Unix"))
+    }
+}
+
+#[cfg(unix)]
+mod client {
+    use std::io::{self, Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::time::Duration;
+    use serde_json::Value;
+    use super::response_body;
+
+    /// `GET path` as JSON. HTTP/1.0 keeps the body unchunked and ends it by closing.
+    pub fn get(socket: &str, path: &str) -> io::Result<Value> {
+        let mut stream = UnixStream::connect(socket)
+            .map_err(|e| io::Error::new(e.kind(), format!("connecting to {}: {}", socket, e)))?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        write!(stream, "GET {} HTTP/1.0\r\nHost: docker\r\n\r\n", path)?;
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        Ok(serde_json::from_slice(response_body(&response)?)?)
+    }
+}
@@ -0,0 +1,68 @@
+//! Errors that end a run, each mapped to one of the [`status`] exit codes and printed
+//! once by `main`, as JSON on stderr with `--json`.
+
+use std::io;
+use serde_json::json;
+use crate::status;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The command line or its input was invalid.
+    #[error("{0}")]
+    Usage(String),
+    /// The requested data isn't available on this system.
+    #[error("{0}")]
+    Unavailable(String),
+    /// Something failed at run time, e.g. no process matched.
+    #[error("{0}")]
+    Failed(String),
+    /// A file, socket, or other I/O operation failed; `context` says what was being done.
+    #[error("{context}: {source}")]
+    Io { context: String, source: io::Error },
+    #[error("serializing to JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("serializing to YAML: {0}")]
+    Yaml(#[from] serde_yaml_ng::Error),
+    /// Ends the run with this status without a message, e.g. after printing alerts
+    /// that fired; whatever explains it has already been printed.
+    #[error("exit status {0}")]
+    Exit(i32),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl Error {
+    pub fn io(context: impl Into<String>, source: io::Error) -> Self {
+        Error::Io { context: context.into(), source }
+    }
+
+    /// The exit status for this error. I/O errors for features this platform lacks
+    /// count as unavailable data rather than failures.
+    pub fn status(&self) -> i32 {
+        match self {
+            Error::Usage(_) => status::USAGE,
+            Error::Unavailable(_) => status::UNAVAILABLE,
+            Error::Io { source, .. } if source.kind() == io::ErrorKind::Unsupported => status::UNAVAILABLE,
+            Error::Failed(_) | Error::Io { .. } | Error::Json(_) | Error::Yaml(_) => status::ERROR,
+            Error::Exit(code) => *code,
+        }
+    }
+
+    /// Whether stdout was closed early, e.g. by `| head`; not worth a message.
+    pub fn is_broken_pipe(&self) -> bool {
+        matches!(self, Error::Io { source, .. } if source.kind() == io::ErrorKind::BrokenPipe)
+    }
+
+    /// What `main` prints: `Error: <message>`, or `{"error": ..., "status": ...}` for `--json`.
+    /// `None` for errors that are reported by their exit status alone.
+    pub fn report(&self, json: bool) -> Option<String> {
+        if matches!(self, Error::Exit(_)) || self.is_broken_pipe() {
+            return None;
+        }
+        Some(if json {
+            json!({ "error": self.to_string(), "status": self.status() }).to_string()
+        } else {
+            format!("Error: {}", self)
+        })
+    }
+}
@@ -0,0 +1,93 @@
+//! `examples`: runnable invocations by area, kept in one table so the `examples`
+//! subcommand and each subcommand's `--help` show the same ones. The tests parse every
+//! entry, so an example that stops matching the flags fails the build.
+
+use clap::Command;
+use crate::models::ExampleInfo;
+
+pub struct Example {
+    /// One of [`AREAS`].
+    pub area: &'static str,
+    /// The subcommand whose `--help` lists it.
+    pub command: &'static str,
+    /// The arguments after `sysinfo-cli`.
+    pub args: &'static [&'static str],
+    pub description: &'static str,
+}
+
+/// Area ids and their titles, in the order they're printed.
+pub const AREAS: &[(&str, &str)] = &[
+    ("overview", "Overviews"),
+    ("processes", "Filtering processes"),
+    ("disks", "Watching disks"),
+    ("prometheus", "Exporting metrics"),
+    ("scripting", "Scripting with JSON and CSV"),
+    ("alerting", "Alerts and status bars"),
+];
+
+pub const EXAMPLES: &[Example] = &[
+    Example { area: "overview", command: "fetch", args: &["fetch"], description: "A neofetch-style overview of this machine" },
+    Example { area: "overview", command: "all", args: &["all", "--top", "5"], description: "Every category at once, with the five busiest processes" },
+    Example { area: "overview", command: "top", args: &["top", "--sort", "memory"], description: "An interactive, top-like view sorted by memory" },
+    Example { area: "processes", command: "processes", args: &["processes", "--filter", "nginx", "--sort", "memory", "--limit", "5"], description: "The five nginx processes using the most memory" },
+    Example { area: "processes", command: "processes", args: &["processes", "--filter", "python", "--cmdline", "--columns", "pid,user,cpu,rss,cmd"], description: "Python processes, matched on the whole command line, with chosen columns" },
+    Example { area: "processes", command: "processes", args: &["processes", "--state", "zombie"], description: "Zombie processes" },
+    Example { area: "processes", command: "processes", args: &["--watch", "2", "processes", "--io", "--sort", "disk-write", "--limit", "10"], description: "The ten heaviest disk writers, refreshed every 2 seconds" },
+    Example { area: "processes", command: "processes", args: &["processes", "--follow"], description: "A line for every process that starts or exits" },
+    Example { area: "processes", command: "process", args: &["process", "1"], description: "Everything known about PID 1" },
+    Example { area: "disks", command: "disks", args: &["disks", "--exclude", "tmpfs,overlay"], description: "Disks, without tmpfs and overlay mounts" },
+    Example { area: "disks", command: "disks", args: &["--watch", "5", "disks", "--io"], description: "Disk throughput, refreshed every 5 seconds" },
+    Example { area: "disks", command: "disks", args: &["disks", "--warn-pct", "80", "--crit-pct", "95"], description: "Flag nearly full disks; exits with status 2 when one is" },
+    Example { area: "disks", command: "disks", args: &["disks", "analyze", "/home", "--top", "10"], description: "The ten largest directories under /home" },
+    Example { area: "prometheus", command: "serve", args: &["serve", "--bind", "0.0.0.0"], description: "Serve /metrics for Prometheus to scrape from other hosts" },
+    Example { area: "prometheus", command: "all", args: &["--watch", "15", "--format", "prometheus", "--output", "/var/lib/node_exporter/sysinfo.prom", "all"], description: "Keep a node_exporter textfile collector file up to date" },
+    Example { area: "prometheus", command: "memory", args: &["--watch", "10", "--sink", "statsd://localhost:8125", "memory"], description: "Send memory metrics to StatsD every 10 seconds" },
+    Example { area: "prometheus", command: "push", args: &["--watch", "10", "push", "http://localhost:8428/write"], description: "Push the summary metrics to VictoriaMetrics every 10 seconds" },
+    Example { area: "scripting", command: "memory", args: &["memory", "--json", "--fields", "used_memory,total_memory"], description: "Only the memory figures a script needs, as JSON" },
+    Example { area: "scripting", command: "disks", args: &["disks", "--query", ".[].available_space"], description: "Each disk's free bytes, one per line, without jq" },
+    Example { area: "scripting", command: "load", args: &["--watch", "60", "--format", "csv", "--timestamp", "--output", "load.csv", "--append", "load"], description: "Log the load averages to a CSV file every minute" },
+    Example { area: "scripting", command: "snapshot", args: &["snapshot", "before.json"], description: "Save the machine's state, to compare later with `diff`" },
+    Example { area: "alerting", command: "alert", args: &["alert", "cpu > 90", "disk_used_pct > 95"], description: "A Nagios/Icinga-style check that exits with status 2 when a rule fires" },
+    Example { area: "alerting", command: "statusbar", args: &["--watch", "2", "statusbar"], description: "A line of Waybar module JSON every 2 seconds" },
+    Example { area: "alerting", command: "load", args: &["load", "--threshold", "4"], description: "Flag a one-minute load above 4" },
+];
+
+/// `args` as typed in a shell: arguments with spaces or shell syntax in single quotes.
+pub fn command_line(args: &[&str]) -> String {
+    let quote = |arg: &&str| {
+        if arg.chars().any(|c| c.is_whitespace() || "<>|&;$*?[]{}()'\"#~!`\\".contains(c)) {
+            format!("'{}'", arg.replace('\'', r"'\''"))
+        } else {
+            arg.to_string()
+        }
+    };
+    std::iter::once("sysinfo-cli".to_string()).chain(args.iter().map(quote)).collect::<Vec<_>>().join(" ")
+}
+
+/// The examples for `filter`, an area or a subcommand, or all of them, in area order.
+/// `None` when `filter` matches neither.
+pub fn get_examples(filter: Option<&str>) -> Option<Vec<ExampleInfo>> {
+    let matches = |example: &Example| filter.is_none_or(|f| example.area == f || example.command == f);
+    let examples: Vec<ExampleInfo> = AREAS.iter()
+        .flat_map(|(area, _)| EXAMPLES.iter().filter(move |e| e.area == *area))
+        .filter(|e| matches(e))
+        .map(|e| ExampleInfo { area: e.area.to_string(), command: command_line(e.args), description: e.description.to_string() })
+        .collect();
+    (!examples.is_empty()).then_some(examples)
+}
+
+/// `cmd` with each subcommand's examples at the end of its `--help`, and a pointer to
+/// `examples` at the end of the top-level one.
+pub fn add_to_help(mut cmd: Command) -> Command {
+    let mut commands: Vec<&str> = EXAMPLES.iter().map(|e| e.command).collect();
+    commands.sort_unstable();
+    commands.dedup();
+    for command in commands {
+        let text: String = EXAMPLES.iter()
+            .filter(|e| e.command == command)
+            .map(|e| format!("  # {}\n  {}\n", e.description, command_line(e.args)))
+            .collect();
+        cmd = cmd.mut_subcommand(command, |sub| sub.after_help(format!("Examples:\n{}", text.trim_end())));
+    }
+    cmd.after_help("Run `sysinfo-cli examples` for example invocations by area.")
+}
@@ -0,0 +1,78 @@
+//! `fetch`: a short overview of the machine next to a logo of its OS, in the style of
+//! neofetch, for a glance rather than a report.
+
+use sysinfo::{Disks, System};
+use crate::models::{FetchDisk, FetchInfo};
+
+const TUX: &[&str] = &[
+    r"    .--.    ",
+    r"   |o_o |   ",
+    r"   |:_/ |   ",
+    r"  //   \ \  ",
+    r" (|     | ) ",
+    r"/'\_   _/`\ ",
+    r"\___)=(___/ ",
+];
+
+const APPLE: &[&str] = &[
+    r"        .:'    ",
+    r"    __ :'__    ",
+    r" .'`__`-'__``. ",
+    r":__________.-' ",
+    r":_________:    ",
+    r" :_________`-; ",
+    r"  `.__.-.__.'  ",
+];
+
+const WINDOWS: &[&str] = &[
+    r" ______  ______ ",
+    r"|      ||      |",
+    r"|______||______|",
+    r" ______  ______ ",
+    r"|      ||      |",
+    r"|______||______|",
+];
+
+const GENERIC: &[&str] = &[
+    r".-----------.",
+    r"| >_        |",
+    r"|           |",
+    r"'-----------'",
+    r"   _|___|_   ",
+];
+
+/// The logo drawn for `os`, as in [`std::env::consts::OS`]; lines are the same width.
+pub fn logo(os: &str) -> &'static [&'static str] {
+    match os {
+        "linux" | "android" => TUX,
+        "macos" | "ios" => APPLE,
+        "windows" => WINDOWS,
+        _ => GENERIC,
+    }
+}
+
+/// The distribution or OS with its version and the CPU architecture, e.g. `Ubuntu 24.04 x86_64`.
+fn os_description() -> Option<String> {
+    let parts: Vec<String> = [System::name(), System::os_version()].into_iter().flatten().collect();
+    (!parts.is_empty()).then(|| format!("{} {}", parts.join(" "), System::cpu_arch()))
+}
+
+pub fn get_fetch_info(sys: &System) -> FetchInfo {
+    let disks = Disks::new_with_refreshed_list();
+    FetchInfo {
+        user: std::env::var("USER").or_else(|_| std::env::var("USERNAME")).ok().filter(|u| !u.is_empty()),
+        host_name: System::host_name(),
+        os: os_description(),
+        kernel_version: System::kernel_version(),
+        uptime: System::uptime(),
+        cpu: sys.cpus().first().map(|cpu| cpu.brand().trim().to_string()).filter(|brand| !brand.is_empty()),
+        nb_cpus: sys.cpus().len(),
+        used_memory: sys.used_memory(),
+        total_memory: sys.total_memory(),
+        disks: disks.iter().filter(|disk| disk.total_space() > 0).map(|disk| FetchDisk {
+            mount_point: disk.mount_point().to_string_lossy().into_owned(),
+            used_space: disk.total_space() - disk.available_space(),
+            total_space: disk.total_space(),
+        }).collect(),
+    }
+}
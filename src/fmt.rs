@@ -1,57 +1,323 @@
+//! Renders reports as text tables and in every `--format`, with the `--units`,
+//! `--timestamp`, and layout settings applied.
+
 use colored::*;
-use comfy_table::Table;
+use crate::args::{CpuMode, OutputFormat, ProcessColumn, Units};
+use crate::i18n::{localize_number, tr};
+use crate::jq::{self, format_results};
+use crate::layout::{new_table, set_markdown};
+use crate::error::{Error, Result};
 use crate::models::*;
+use crate::prometheus::{format_prometheus, ToMetrics};
+use crate::watch::ProcessChanges;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, UNIX_EPOCH};
 
 pub fn format_system_info(info: &SystemInfo) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{:<25} {:?}\n", "System name:".yellow(), info.name.as_deref().unwrap_or_default()));
-    s.push_str(&format!("{:<25} {:?}\n", "Kernel version:".yellow(), info.kernel_version.as_deref().unwrap_or_default()));
-    s.push_str(&format!("{:<25} {:?}\n", "OS version:".yellow(), info.os_version.as_deref().unwrap_or_default()));
-    s.push_str(&format!("{:<25} {:?}\n", "Host name:".yellow(), info.host_name.as_deref().unwrap_or_default()));
+    s.push_str(&format!("{:<25} {:?}\n", tr("system-name").yellow(), info.name.as_deref().unwrap_or_default()));
+    s.push_str(&format!("{:<25} {:?}\n", tr("kernel-version").yellow(), info.kernel_version.as_deref().unwrap_or_default()));
+    s.push_str(&format!("{:<25} {:?}\n", tr("os-version").yellow(), info.os_version.as_deref().unwrap_or_default()));
+    s.push_str(&format!("{:<25} {:?}\n", tr("host-name").yellow(), info.host_name.as_deref().unwrap_or_default()));
+    s.push_str(&format!("{:<25} {}\n", tr("uptime").yellow(), format_duration(info.uptime)));
+    s.push_str(&format!("{:<25} {}\n", tr("boot-time").yellow(), format_unix_time(info.boot_time)));
+    if let Some(load) = &info.load_average {
+        s.push_str(&format!("{:<25} {}\n", tr("load-average").yellow(), format_load_average(load)));
+    }
+    s
+}
+
+pub fn format_uptime_info(info: &UptimeInfo) -> String {
+    let mut s = String::new();
+    s.push_str(&format!("{:<25} {}\n", tr("uptime").yellow(), format_duration(info.uptime)));
+    s.push_str(&format!("{:<25} {}\n", tr("boot-time").yellow(), format_unix_time(info.boot_time)));
+    s
+}
+
+/// RFC 3339 in UTC, e.g. "2026-10-16T08:12:00Z".
+pub fn format_unix_time(secs: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(secs)).to_string()
+}
+
+/// "0.52, 0.48, 0.40" for the 1, 5, and 15-minute averages.
+pub fn format_load_average(load: &LoadAverage) -> String {
+    [load.one, load.five, load.fifteen].map(|value| format_float(value as f32, 2)).join(", ")
+}
+
+pub fn format_load_report(info: &LoadReport) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-load"));
+    let load = &info.load_average;
+    s.push_str(&format!("{:<25} {}\n", tr("load-average").yellow(), format_load_average(load)));
+    s.push_str(&format!("{:<25} {}\n", tr("load-per-cpu").yellow(), format_float((load.one / info.nb_cpus.max(1) as f64) as f32, 2)));
+    if let (Some(limit), Some(exceeded)) = (info.threshold, info.exceeded) {
+        let status = if exceeded {
+            format!("{} ({} > {})", tr("load-exceeded"), format_float(load.one as f32, 2), format_float(limit as f32, 2)).red().bold().to_string()
+        } else {
+            tr("load-ok").green().to_string()
+        };
+        s.push_str(&format!("{:<25} {}\n", tr("load-threshold").yellow(), status));
+    }
     s
 }
 
+/// Renders `now`, in red if it rose since `before` and in green if it fell. Values that
+/// format the same count as unchanged, so rounding noise isn't highlighted.
+pub fn highlight_change<T: PartialOrd + Copy>(now: T, before: Option<T>, format: impl Fn(T) -> String) -> String {
+    let text = format(now);
+    match before {
+        Some(before) if format(before) != text && now > before => text.red().to_string(),
+        Some(before) if format(before) != text && now < before => text.green().to_string(),
+        _ => text,
+    }
+}
+
 pub fn format_cpu_info(info: &CpuInfo) -> String {
+    format_cpu_info_since(info, None)
+}
+
+/// The CPU report, highlighting usage that changed since `previous` (`--highlight`).
+pub fn format_cpu_info_since(info: &CpuInfo, previous: Option<&CpuInfo>) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{}\n", "=> CPUs:".bright_green().bold()));
-    s.push_str(&format!("{:<25} {}\n", "Total CPUs:".yellow(), info.nb_cpus));
-    s.push_str(&format!("{:<25} {:.1}%\n", "Global usage:".yellow(), info.total_usage));
+    s.push_str(&section_title("section-cpus"));
+    s.push_str(&format!("{:<25} {}\n", tr("total-cpus").yellow(), localize_number(&info.nb_cpus.to_string())));
+    if let Some(limit) = info.cpu_limit {
+        s.push_str(&format!("{:<25} {}\n", tr("cpu-limit").yellow(), format_float(limit as f32, 2)));
+    }
+    let topology = info.topology.as_ref();
+    if let Some(cores) = topology.and_then(|t| t.cores).or(info.physical_cores) {
+        s.push_str(&format!("{:<25} {}\n", tr("physical-cores").yellow(), localize_number(&cores.to_string())));
+    }
+    if let Some(sockets) = topology.and_then(|t| t.sockets) {
+        s.push_str(&format!("{:<25} {}\n", tr("sockets").yellow(), localize_number(&sockets.to_string())));
+    }
+    // Vendor and brand go in the table only when they differ between CPUs.
+    let uniform = info.cpus.windows(2).all(|w| w[0].vendor == w[1].vendor && w[0].brand == w[1].brand);
+    if let (true, Some(cpu)) = (uniform, info.cpus.first()) {
+        s.push_str(&format!("{:<25} {}\n", tr("cpu-vendor").yellow(), cpu.vendor));
+        s.push_str(&format!("{:<25} {}\n", tr("cpu-brand").yellow(), cpu.brand));
+    }
+    let usage = highlight_change(info.total_usage, previous.map(|p| p.total_usage), |u| format!("{}%", format_float(u, 1)));
+    s.push_str(&format!("{:<25} {}\n", tr("global-usage").yellow(), usage));
+    if let Some(b) = &info.breakdown {
+        let parts = [
+            ("time-user", b.user + b.nice),
+            ("time-system", b.system),
+            ("time-iowait", b.iowait),
+            ("time-irq", b.irq + b.softirq),
+            ("time-steal", b.steal),
+        ];
+        let parts: Vec<String> = parts.iter().map(|(key, pct)| format!("{} {}%", tr(key), format_float(*pct, 1))).collect();
+        s.push_str(&format!("{:<25} {}\n", tr("cpu-time-split").yellow(), parts.join(", ")));
+    }
+    if let Some(load) = &info.load_average {
+        s.push_str(&format!("{:<25} {}\n", tr("load-average").yellow(), format_load_average(load)));
+    }
+    if let Some(volts) = info.core_voltage {
+        s.push_str(&format!("{:<25} {} V\n", tr("core-voltage").yellow(), format_float(volts, 2)));
+    }
+    if let Some(flags) = &info.throttling {
+        let text = if flags.is_empty() {
+            tr("no").green().to_string()
+        } else {
+            flags.iter().map(|flag| match flag.strip_suffix("_since_boot") {
+                Some(name) => format!("{} ({})", tr(&format!("throttle-{}", name.replace('_', "-"))), tr("since-boot")),
+                None => tr(&format!("throttle-{}", flag.replace('_', "-"))),
+            }).collect::<Vec<_>>().join(", ").red().to_string()
+        };
+        s.push_str(&format!("{:<25} {}\n", tr("throttling").yellow(), text));
+    }
     
-    let mut table = Table::new();
-    table.set_header(vec!["ID", "Usage %", "Vendor", "Brand"]);
-    for cpu in &info.cpus {
-        table.add_row(vec![
-            cpu.id.to_string(),
-            format!("{:.1}", cpu.usage),
-            cpu.vendor.clone(),
-            cpu.brand.clone(),
+    let with_history = info.cpus.iter().any(|cpu| cpu.avg_usage.is_some());
+    let with_frequency = info.cpus.iter().any(|cpu| cpu.frequency > 0);
+    let with_limits = info.cpus.iter().any(|cpu| cpu.min_frequency.is_some() || cpu.max_frequency.is_some());
+    let with_topology = info.cpus.iter().any(|cpu| cpu.socket.is_some());
+    let mut columns = vec![("id", tr("col-id")), ("usage", tr("col-usage-pct"))];
+    if with_history {
+        columns.extend([("avg", tr("col-avg-pct")), ("peak", tr("col-peak-pct"))]);
+    }
+    if with_frequency {
+        columns.push(("mhz", tr("col-mhz")));
+    }
+    if with_limits {
+        columns.extend([("min_mhz", tr("col-min-mhz")), ("max_mhz", tr("col-max-mhz"))]);
+    }
+    if with_topology {
+        columns.extend([("socket", tr("col-socket")), ("core", tr("col-core"))]);
+    }
+    // User includes nice time and IRQ soft interrupts, as in top's summary.
+    let with_breakdown = info.cpus.iter().any(|cpu| cpu.breakdown.is_some());
+    if with_breakdown {
+        columns.extend([
+            ("user", tr("col-user-pct")),
+            ("system", tr("col-system-pct")),
+            ("iowait", tr("col-iowait-pct")),
+            ("irq", tr("col-irq-pct")),
+            ("steal", tr("col-steal-pct")),
         ]);
     }
+    if !uniform {
+        columns.extend([("vendor", tr("col-vendor")), ("brand", tr("col-brand"))]);
+    }
+    let mut table = new_table("cpu", columns);
+    let number = |n: Option<u64>| n.map(|n| localize_number(&n.to_string())).unwrap_or_default();
+    for cpu in &info.cpus {
+        let before = previous.and_then(|p| p.cpus.iter().find(|c| c.id == cpu.id)).map(|c| c.usage);
+        let mut row = vec![cpu.id.to_string(), highlight_change(cpu.usage, before, |u| format_float(u, 1))];
+        if with_history {
+            row.push(cpu.avg_usage.map(|u| format_float(u, 1)).unwrap_or_default());
+            row.push(cpu.peak_usage.map(|u| format_float(u, 1)).unwrap_or_default());
+        }
+        if with_frequency {
+            row.push(number(Some(cpu.frequency)));
+        }
+        if with_limits {
+            row.extend([number(cpu.min_frequency), number(cpu.max_frequency)]);
+        }
+        if with_topology {
+            row.extend([number(cpu.socket), number(cpu.core)]);
+        }
+        if with_breakdown {
+            match &cpu.breakdown {
+                Some(b) => row.extend([b.user + b.nice, b.system, b.iowait, b.irq + b.softirq, b.steal].map(|pct| format_float(pct, 1))),
+                None => row.extend(std::iter::repeat_n(String::new(), 5)),
+            }
+        }
+        if !uniform {
+            row.extend([cpu.vendor.clone(), cpu.brand.clone()]);
+        }
+        table.add_row(row);
+    }
     s.push_str(&format!("{}\n", table));
     s
 }
 
 pub fn format_memory_info(info: &MemoryInfo) -> String {
+    format_memory_info_since(info, None)
+}
+
+/// The memory report, highlighting usage that changed since `previous` (`--highlight`).
+pub fn format_memory_info_since(info: &MemoryInfo, previous: Option<&MemoryInfo>) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{:<25} {}\n", "Total memory:".yellow(), format_bytes(info.total_memory)));
-    s.push_str(&format!("{:<25} {}\n", "Used memory:".yellow(), format_bytes(info.used_memory)));
-    s.push_str(&format!("{:<25} {}\n", "Total swap:".yellow(), format_bytes(info.total_swap)));
-    s.push_str(&format!("{:<25} {}\n", "Used swap:".yellow(), format_bytes(info.used_swap)));
+    s.push_str(&format!("{:<25} {}\n", tr("total-memory").yellow(), format_bytes(info.total_memory)));
+    s.push_str(&format!("{:<25} {}\n", tr("used-memory").yellow(), highlight_change(info.used_memory, previous.map(|p| p.used_memory), format_bytes)));
+    s.push_str(&format!("{:<25} {}\n", tr("available-memory").yellow(), format_bytes(info.available_memory)));
+    s.push_str(&format!("{:<25} {}\n", tr("free-memory").yellow(), format_bytes(info.free_memory)));
+    if let Some(buffers) = info.buffers {
+        s.push_str(&format!("{:<25} {}\n", tr("buffers").yellow(), format_bytes(buffers)));
+    }
+    if let Some(cached) = info.cached {
+        s.push_str(&format!("{:<25} {}\n", tr("cached").yellow(), format_bytes(cached)));
+    }
+    s.push_str(&format!("{:<25} {}\n", tr("total-swap").yellow(), format_bytes(info.total_swap)));
+    s.push_str(&format!("{:<25} {}\n", tr("used-swap").yellow(), highlight_change(info.used_swap, previous.map(|p| p.used_swap), format_bytes)));
+    if let Some(committed) = info.committed_memory {
+        s.push_str(&format!("{:<25} {}\n", tr("committed-memory").yellow(), format_bytes(committed)));
+    }
+    if let Some(limit) = info.commit_limit {
+        s.push_str(&format!("{:<25} {}\n", tr("commit-limit").yellow(), format_bytes(limit)));
+    }
+    if let Some(cgroup) = &info.cgroup {
+        let used = format!("{} / {} ({}%)", format_bytes(cgroup.used), format_bytes(cgroup.limit), format_float(cgroup.used as f32 / cgroup.limit.max(1) as f32 * 100.0, 1));
+        s.push_str(&format!("{:<25} {}\n", tr("cgroup-memory").yellow(), used));
+        s.push_str(&format!("{:<25} {}\n", tr("cgroup-rss").yellow(), format_bytes(cgroup.rss)));
+    }
     s
 }
 
 pub fn format_disks_info(info: &[DiskInfo]) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{}\n", "=> Disks:".bright_green().bold()));
-    let mut table = Table::new();
-    table.set_header(vec!["Name", "Kind", "FS", "Available", "Total"]);
+    s.push_str(&section_title("section-disks"));
+    let with_queue = info.iter().any(|disk| disk.queue_length.is_some());
+    let with_rates = info.iter().any(|disk| disk.available_rate.is_some());
+    let with_io = info.iter().any(|disk| disk.read_bytes.is_some());
+    let with_io_rates = info.iter().any(|disk| disk.read_rate.is_some());
+    let mut columns = vec![
+        ("name", tr("col-name")),
+        ("mount", tr("col-mount")),
+        ("kind", tr("col-kind")),
+        ("fs", tr("col-fs")),
+        ("removable", tr("col-removable")),
+        ("read_only", tr("col-read-only")),
+        ("available", tr("col-available")),
+        ("total", tr("col-total")),
+        ("used_pct", tr("col-used-pct")),
+    ];
+    if with_queue {
+        columns.push(("queue", tr("col-queue")));
+    }
+    if with_rates {
+        columns.extend([("change", tr("col-change-per-min")), ("full_in", tr("col-full-in"))]);
+    }
+    if with_io {
+        columns.extend([("read", tr("col-read")), ("written", tr("col-written"))]);
+    }
+    if with_io_rates {
+        columns.extend([("read_rate", tr("col-read-per-sec")), ("write_rate", tr("col-written-per-sec"))]);
+    }
+    let mut table = new_table("disks", columns);
     for disk in info {
-        table.add_row(vec![
-            disk.name.cyan().to_string(),
+        let used = format!("{}%", format_float(disk.used_percent, 1));
+        let (name, used) = match disk.usage_level {
+            Some(UsageLevel::Critical) => (disk.name.red().bold(), used.red().bold()),
+            Some(UsageLevel::Warning) => (disk.name.yellow().bold(), used.yellow().bold()),
+            _ => (disk.name.cyan(), used.normal()),
+        };
+        let mut row = vec![
+            name.to_string(),
+            disk.mount_point.clone(),
             disk.kind.blue().to_string(),
             disk.file_system.yellow().to_string(),
+            tr(if disk.is_removable { "yes" } else { "no" }),
+            if disk.is_read_only { tr("yes").yellow().to_string() } else { tr("no") },
             format_bytes(disk.available_space),
             format_bytes(disk.total_space),
+            used.to_string(),
+        ];
+        if with_queue {
+            row.push(disk.queue_length.map(|q| localize_number(&format!("{:.2}", q))).unwrap_or_default());
+        }
+        if with_rates {
+            row.push(disk.available_rate.map(format_rate_per_min).unwrap_or_default());
+            row.push(disk.time_to_full.map(format_duration).unwrap_or_else(|| "-".to_string()));
+        }
+        if with_io {
+            row.push(disk.read_bytes.map(format_bytes).unwrap_or_default());
+            row.push(disk.written_bytes.map(format_bytes).unwrap_or_default());
+        }
+        if with_io_rates {
+            row.push(disk.read_rate.map(format_rate_per_sec).unwrap_or_default());
+            row.push(disk.write_rate.map(format_rate_per_sec).unwrap_or_default());
+        }
+        table.add_row(row);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_disk_usage(info: &DiskUsageReport) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-disk-usage"));
+    s.push_str(&format!("{:<25} {}\n", tr("scanned-path").yellow(), info.root));
+    s.push_str(&format!("{:<25} {}\n", tr("total-size").yellow(), format_bytes(info.total_size)));
+    s.push_str(&format!("{:<25} {}\n", tr("total-files").yellow(), localize_number(&info.total_files.to_string())));
+    if info.skipped > 0 {
+        s.push_str(&format!("{:<25} {}\n", tr("skipped-unreadable").yellow(), localize_number(&info.skipped.to_string()).red()));
+    }
+    let mut table = new_table("disk_usage", vec![
+        ("path", tr("col-path")),
+        ("size", tr("col-size")),
+        ("share", tr("col-share-pct")),
+        ("files", tr("col-files")),
+    ]);
+    for dir in &info.directories {
+        let share = if info.total_size == 0 { 0.0 } else { dir.size as f32 / info.total_size as f32 * 100.0 };
+        table.add_row(vec![
+            dir.path.cyan().to_string(),
+            format_bytes(dir.size),
+            format_float(share, 1),
+            localize_number(&dir.files.to_string()),
         ]);
     }
     s.push_str(&format!("{}\n", table));
@@ -60,15 +326,77 @@ pub fn format_disks_info(info: &[DiskInfo]) -> String {
 
 pub fn format_network_info(info: &[NetworkInfo]) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{}\n", "=> Networks:".bright_green().bold()));
-    let mut table = Table::new();
-    table.set_header(vec!["Interface", "Received", "Transmitted"]);
+    s.push_str(&section_title("section-networks"));
+    let with_namespaces = info.iter().any(|net| net.namespace.is_some());
+    let with_rates = info.iter().any(|net| net.rx_rate.is_some());
+    let with_drops = info.iter().any(|net| net.drops_received.is_some());
+    let mut columns = Vec::new();
+    if with_namespaces {
+        columns.push(("namespace", tr("col-namespace")));
+    }
+    columns.extend([
+        ("interface", tr("col-interface")),
+        ("mac", tr("col-mac")),
+        ("mtu", tr("col-mtu")),
+        ("addresses", tr("col-addresses")),
+        ("received", tr("col-received")),
+        ("transmitted", tr("col-transmitted")),
+        ("packets", tr("col-packets")),
+        ("errors", tr("col-errors")),
+    ]);
+    if with_drops {
+        columns.push(("drops", tr("col-drops")));
+    }
+    let with_delta = info.iter().any(|net| net.delta.is_some());
+    if with_delta {
+        columns.extend([
+            ("received_delta", tr("col-received-delta")),
+            ("transmitted_delta", tr("col-transmitted-delta")),
+            ("packets_delta", tr("col-packets-delta")),
+            ("errors_delta", tr("col-errors-delta")),
+        ]);
+    }
+    if with_rates {
+        columns.extend([("rx_rate", tr("col-received-per-sec")), ("tx_rate", tr("col-transmitted-per-sec"))]);
+    }
+    let mut table = new_table("network", columns);
     for net in info {
-        table.add_row(vec![
+        let mut row = Vec::new();
+        if with_namespaces {
+            row.push(net.namespace.clone().unwrap_or_default().blue().to_string());
+        }
+        // Received / transmitted, highlighted when anything went wrong.
+        let pair = |rx: u64, tx: u64| format!("{} / {}", localize_number(&rx.to_string()), localize_number(&tx.to_string()));
+        let errors = pair(net.errors_received, net.errors_transmitted);
+        row.extend([
             net.interface.cyan().to_string(),
+            net.mac_address.clone().unwrap_or_else(|| "-".to_string()),
+            net.mtu.map(|mtu| localize_number(&mtu.to_string())).unwrap_or_default(),
+            net.ip_addresses.join(", "),
             format_bytes(net.received).yellow().to_string(),
             format_bytes(net.transmitted).yellow().to_string(),
+            pair(net.packets_received, net.packets_transmitted),
+            if net.errors_received + net.errors_transmitted > 0 { errors.red().to_string() } else { errors },
         ]);
+        if with_drops {
+            let drops = pair(net.drops_received.unwrap_or(0), net.drops_transmitted.unwrap_or(0));
+            row.push(if net.drops_received.unwrap_or(0) + net.drops_transmitted.unwrap_or(0) > 0 { drops.yellow().to_string() } else { drops });
+        }
+        if with_delta {
+            let delta = net.delta.clone().unwrap_or_default();
+            let errors = pair(delta.errors_received, delta.errors_transmitted);
+            row.extend([
+                format_bytes(delta.received),
+                format_bytes(delta.transmitted),
+                pair(delta.packets_received, delta.packets_transmitted),
+                if delta.errors_received + delta.errors_transmitted > 0 { errors.red().bold().to_string() } else { errors },
+            ]);
+        }
+        if with_rates {
+            row.push(net.rx_rate.map(format_rate_per_sec).unwrap_or_default());
+            row.push(net.tx_rate.map(format_rate_per_sec).unwrap_or_default());
+        }
+        table.add_row(row);
     }
     s.push_str(&format!("{}\n", table));
     s
@@ -76,31 +404,583 @@ pub fn format_network_info(info: &[NetworkInfo]) -> String {
 
 pub fn format_components_info(info: &[ComponentInfo]) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{}\n", "=> Components:".bright_green().bold()));
-    let mut table = Table::new();
-    table.set_header(vec!["Label", "Temp", "Max"]);
+    s.push_str(&section_title("section-components"));
+    let with_critical = info.iter().any(|c| c.critical.is_some());
+    let mut columns = vec![
+        ("label", tr("col-label")),
+        ("temp", tr("col-temp")),
+        ("max", tr("col-max")),
+        ("pct_of_max", tr("col-pct-of-max")),
+    ];
+    if with_critical {
+        columns.push(("critical", tr("col-critical")));
+    }
+    let mut table = new_table("components", columns);
+    let celsius = |t: Option<f32>| format!("{}°C", t.map(|t| format_float(t, 1)).unwrap_or_else(|| tr("not-available")));
     for c in info {
+        // Past --crit, or the sensor's own critical temperature, is red; past --warn, yellow.
+        let at_critical = c.temperature.zip(c.critical).is_some_and(|(t, critical)| t >= critical);
+        let (label, temperature) = match c.temperature_level {
+            Some(UsageLevel::Critical) => (c.label.red().bold(), celsius(c.temperature).red().bold()),
+            _ if at_critical => (c.label.red().bold(), celsius(c.temperature).red().bold()),
+            Some(UsageLevel::Warning) => (c.label.yellow().bold(), celsius(c.temperature).yellow().bold()),
+            _ => (c.label.cyan(), celsius(c.temperature).normal()),
+        };
+        let mut row = vec![
+            label.to_string(),
+            temperature.to_string(),
+            celsius(c.max),
+            c.percent_of_max.map(|pct| format!("{}%", format_float(pct, 1))).unwrap_or_default(),
+        ];
+        if with_critical {
+            row.push(c.critical.map(|t| celsius(Some(t))).unwrap_or_default());
+        }
+        table.add_row(row);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_container_info(info: &ContainerInfo) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-container"));
+    let runtime = match &info.runtime {
+        Some(runtime) => runtime.cyan().bold().to_string(),
+        None => tr("not-in-container"),
+    };
+    s.push_str(&format!("{:<25} {}\n", tr("container-runtime").yellow(), runtime));
+    if let Some(id) = &info.id {
+        s.push_str(&format!("{:<25} {}\n", tr("container-id").yellow(), id));
+    }
+    if let (Some(version), Some(path)) = (info.cgroup_version, &info.cgroup_path) {
+        s.push_str(&format!("{:<25} v{} {}\n", tr("cgroup").yellow(), version, path));
+    }
+    // Usage against the limit where there is one, else against the host.
+    let share = |pct: Option<f32>| pct.map(|pct| format!(" ({}%)", format_float(pct, 1))).unwrap_or_default();
+    let cpu_limit = match info.cpu_limit {
+        Some(limit) => format!("{} / {}", format_float(limit as f32, 2), localize_number(&info.host_cpus.to_string())),
+        None => format!("{} ({})", tr("unlimited"), localize_number(&info.host_cpus.to_string())),
+    };
+    s.push_str(&format!("{:<25} {}\n", tr("cpu-limit").yellow(), cpu_limit));
+    if let Some(used) = info.cpus_used {
+        let of_host = (info.host_cpus > 0).then(|| (used / info.host_cpus as f64 * 100.0) as f32);
+        s.push_str(&format!("{:<25} {}{}\n", tr("cpus-used").yellow(), format_float(used as f32, 2), share(info.cpu_limit_percent.or(of_host))));
+    }
+    let memory_limit = match info.memory_limit {
+        Some(limit) => format!("{} / {}", format_bytes(limit), format_bytes(info.host_memory)),
+        None => format!("{} ({})", tr("unlimited"), format_bytes(info.host_memory)),
+    };
+    s.push_str(&format!("{:<25} {}\n", tr("memory-limit").yellow(), memory_limit));
+    if let Some(used) = info.memory_used {
+        let of_host = (info.host_memory > 0).then(|| used as f32 / info.host_memory as f32 * 100.0);
+        s.push_str(&format!("{:<25} {}{}\n", tr("memory-used").yellow(), format_bytes(used), share(info.memory_limit_percent.or(of_host))));
+    }
+    s
+}
+
+/// One table per chip, headed by its name and device.
+pub fn format_sensor_readings(info: &[SensorReading]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-sensors"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-sensors")));
+        return s;
+    }
+    for chip in info.chunk_by(|a, b| a.chip == b.chip && a.device == b.device) {
+        let heading = match &chip[0].device {
+            Some(device) => format!("{} ({})", chip[0].chip, device),
+            None => chip[0].chip.clone(),
+        };
+        s.push_str(&format!("{}\n", heading.cyan().bold()));
+        let mut table = new_table("sensors", vec![
+            ("label", tr("col-label")),
+            ("value", tr("col-value")),
+        ]);
+        for reading in chip {
+            table.add_row(vec![reading.label.clone(), format_sensor_value(reading.kind, reading.value)]);
+        }
+        s.push_str(&format!("{}\n", table));
+    }
+    s
+}
+
+/// `value` with the unit of `kind`, e.g. "1250 RPM" or "1.05 V".
+pub fn format_sensor_value(kind: SensorKind, value: f64) -> String {
+    match kind {
+        SensorKind::Temperature => format!("{}°C", format_float(value as f32, 1)),
+        SensorKind::Fan => format!("{} RPM", format_float(value as f32, 0)),
+        SensorKind::Voltage => format!("{} V", format_float(value as f32, 2)),
+        SensorKind::Current => format!("{} A", format_float(value as f32, 2)),
+        SensorKind::Power => format!("{} W", format_float(value as f32, 1)),
+    }
+}
+
+pub fn format_kernel_resources(info: &KernelResources) -> String {
+    let used_of = |used: Option<u64>, max: Option<u64>| match (used, max) {
+        (Some(used), Some(max)) if max > 0 => format!(
+            "{} / {} ({}%)",
+            localize_number(&used.to_string()),
+            localize_number(&max.to_string()),
+            format_float(used as f32 / max as f32 * 100.0, 1),
+        ),
+        (Some(used), _) => localize_number(&used.to_string()),
+        (None, _) => tr("not-available"),
+    };
+    let mut s = String::new();
+    s.push_str(&section_title("section-kernel-resources"));
+    s.push_str(&format!("{:<25} {}\n", tr("file-handles").yellow(), used_of(info.file_handles_allocated, info.file_handles_max)));
+    s.push_str(&format!("{:<25} {}\n", tr("epoll-instances").yellow(), used_of(info.epoll_instances, None)));
+    s.push_str(&format!("{:<25} {}\n", tr("inotify-instances").yellow(), used_of(info.inotify_instances, None)));
+    if let Some(max) = info.inotify_max_user_instances {
+        s.push_str(&format!("{:<25} {}\n", tr("inotify-max-instances").yellow(), localize_number(&max.to_string())));
+    }
+    if let Some(max) = info.inotify_max_user_watches {
+        s.push_str(&format!("{:<25} {}\n", tr("inotify-max-watches").yellow(), localize_number(&max.to_string())));
+    }
+    if let Some(max) = info.epoll_max_user_watches {
+        s.push_str(&format!("{:<25} {}\n", tr("epoll-max-watches").yellow(), localize_number(&max.to_string())));
+    }
+    let mut table = new_table("sockets", vec![
+        ("protocol", tr("col-protocol")),
+        ("state", tr("col-state")),
+        ("count", tr("col-count")),
+    ]);
+    for socket in &info.sockets {
         table.add_row(vec![
-            c.label.cyan().to_string(),
-            format!("{}°C", c.temperature.map(|t| format!("{:.1}", t)).unwrap_or_else(|| "N/A".to_string())),
-            format!("{}°C", c.max.map(|t| format!("{:.1}", t)).unwrap_or_else(|| "N/A".to_string())),
+            socket.protocol.cyan().to_string(),
+            socket.state.clone(),
+            localize_number(&socket.count.to_string()),
         ]);
     }
     s.push_str(&format!("{}\n", table));
     s
 }
 
+/// Cuts `text` to at most `max` characters, ending in "..." when shortened.
+fn ellipsize(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
 pub fn format_processes_info(info: &[ProcessInfo]) -> String {
+    format_process_table(info, &[], None, None)
+}
+
+/// The process table with `columns`, in that order; with none, PID, name, CPU, memory,
+/// and run time, plus handles on Windows and disk I/O when collected. With `changes`
+/// (`--highlight`), new processes are bold, CPU and memory show which way they moved,
+/// and processes that exited are listed below the table. With `cpu_scale`, the table is
+/// headed by what 100% CPU means and how many CPUs there are.
+pub fn format_process_table(info: &[ProcessInfo], columns: &[ProcessColumn], changes: Option<&ProcessChanges>, cpu_scale: Option<(CpuMode, usize)>) -> String {
     let mut s = String::new();
-    s.push_str(&format!("{}\n", "=> Processes:".bright_green().bold()));
-    let mut table = Table::new();
-    table.set_header(vec!["PID", "Name", "CPU %", "Memory"]);
+    s.push_str(&section_title("section-processes"));
+    if let Some((mode, nb_cpus)) = cpu_scale {
+        let scale = match mode {
+            CpuMode::PerCore => tr("cpu-scale-per-core"),
+            CpuMode::Total => tr("cpu-scale-total"),
+        };
+        s.push_str(&format!("{:<25} {}\n", tr("cpu-scale").yellow(), scale));
+        s.push_str(&format!("{:<25} {}\n", tr("nb-cpus").yellow(), localize_number(&nb_cpus.to_string())));
+    }
+    let mut columns = columns.to_vec();
+    if columns.is_empty() {
+        columns = vec![ProcessColumn::Pid, ProcessColumn::Name, ProcessColumn::Cpu, ProcessColumn::Rss, ProcessColumn::RunTime];
+        if info.iter().any(|p| p.handles.is_some()) {
+            columns.push(ProcessColumn::Handles);
+        }
+        if info.iter().any(|p| p.read_bytes.is_some()) {
+            columns.extend([ProcessColumn::Read, ProcessColumn::Written]);
+        }
+        if info.iter().any(|p| p.read_rate.is_some()) {
+            columns.extend([ProcessColumn::ReadRate, ProcessColumn::WriteRate]);
+        }
+    }
+    let headers = columns.iter().map(|c| (c.id(), tr(match c {
+        ProcessColumn::Pid => "col-pid",
+        ProcessColumn::Name => "col-name",
+        ProcessColumn::User => "col-user",
+        ProcessColumn::Cpu => "col-cpu-pct",
+        ProcessColumn::Mem => "col-mem-pct",
+        ProcessColumn::Vsz => "col-virtual",
+        ProcessColumn::Rss => "col-memory",
+        ProcessColumn::Start => "col-started",
+        ProcessColumn::RunTime => "col-run-time",
+        ProcessColumn::Status => "col-status",
+        ProcessColumn::Threads => "col-threads",
+        ProcessColumn::Cmd => "col-command",
+        ProcessColumn::Handles => "col-handles",
+        ProcessColumn::Read => "col-read",
+        ProcessColumn::Written => "col-written",
+        ProcessColumn::ReadRate => "col-read-per-sec",
+        ProcessColumn::WriteRate => "col-written-per-sec",
+    }))).collect();
+    let mut table = new_table("processes", headers);
     for p in info {
-        let name = if p.name.len() > 30 { format!("{}...", &p.name[..27]) } else { p.name.clone() };
+        let started = changes.is_some_and(|c| c.started.contains(&p.pid));
+        let before = changes.and_then(|c| c.previous.get(&p.pid));
+        table.add_row(columns.iter().map(|c| match c {
+            ProcessColumn::Pid if started => localize_number(&p.pid.to_string()).cyan().bold().to_string(),
+            ProcessColumn::Pid => localize_number(&p.pid.to_string()).cyan().to_string(),
+            ProcessColumn::Name if started => ellipsize(&p.name, 30).bold().to_string(),
+            ProcessColumn::Name => ellipsize(&p.name, 30),
+            ProcessColumn::User => p.user.clone().unwrap_or_default(),
+            ProcessColumn::Cpu => highlight_change(p.cpu_usage, before.map(|b| b.0), |u| format!("{:>5}", format_float(u, 1))),
+            ProcessColumn::Mem => format!("{:>5}", format_float(p.memory_usage, 1)),
+            ProcessColumn::Vsz => format_bytes(p.virtual_memory),
+            ProcessColumn::Rss => highlight_change(p.memory, before.map(|b| b.1), format_bytes),
+            ProcessColumn::Start => format_unix_time(p.start_time),
+            ProcessColumn::RunTime => format_duration(p.run_time),
+            ProcessColumn::Status => p.status.clone(),
+            ProcessColumn::Threads => p.threads.map(|t| localize_number(&t.to_string())).unwrap_or_default(),
+            // Full command lines are in the JSON output.
+            ProcessColumn::Cmd => ellipsize(&p.cmd.join(" "), 60),
+            ProcessColumn::Handles => p.handles.map(|h| localize_number(&h.to_string())).unwrap_or_default(),
+            ProcessColumn::Read => p.read_bytes.map(format_bytes).unwrap_or_default(),
+            ProcessColumn::Written => p.written_bytes.map(format_bytes).unwrap_or_default(),
+            ProcessColumn::ReadRate => p.read_rate.map(format_rate_per_sec).unwrap_or_default(),
+            ProcessColumn::WriteRate => p.write_rate.map(format_rate_per_sec).unwrap_or_default(),
+        }).collect::<Vec<_>>());
+    }
+    s.push_str(&format!("{}\n", table));
+    if let Some(changes) = changes && !changes.exited.is_empty() {
+        s.push_str(&section_title("section-exited"));
+        for event in &changes.exited {
+            s.push_str(&format!("{}\n", format_process_event(event)));
+        }
+    }
+    s
+}
+
+/// Examples under their area's title, each described in a comment above it.
+pub fn format_examples(info: &[ExampleInfo]) -> String {
+    let mut s = String::new();
+    for (i, example) in info.iter().enumerate() {
+        if i == 0 || info[i - 1].area != example.area {
+            if i > 0 {
+                s.push('\n');
+            }
+            let title = crate::examples::AREAS.iter().find(|(id, _)| *id == example.area).map_or(example.area.as_str(), |(_, title)| title);
+            s.push_str(&format!("{}\n", format!("=> {}:", title).bright_green().bold()));
+        }
+        s.push_str(&format!("  {}\n  {}\n", format!("# {}", example.description).dimmed(), example.command));
+    }
+    s
+}
+
+/// The overview lines beside the OS's logo, or alone without `logo`.
+pub fn format_fetch_info(info: &FetchInfo, logo: bool) -> String {
+    let percent = |used: u64, total: u64| if total > 0 { used as f64 / total as f64 * 100.0 } else { 0.0 };
+    let usage = |used: u64, total: u64| format!("{} / {} ({:.0}%)", format_bytes(used), format_bytes(total), percent(used, total));
+    let title = match (&info.user, &info.host_name) {
+        (Some(user), Some(host)) => format!("{}@{}", user, host),
+        (user, host) => user.clone().or_else(|| host.clone()).unwrap_or_default(),
+    };
+    let mut lines = vec![title.bright_cyan().bold().to_string(), "-".repeat(title.chars().count())];
+    let mut add = |label: String, value: String| lines.push(format!("{}: {}", label.yellow().bold(), value));
+    if let Some(os) = &info.os {
+        add(tr("fetch-os"), os.clone());
+    }
+    if let Some(kernel) = &info.kernel_version {
+        add(tr("fetch-kernel"), kernel.clone());
+    }
+    add(tr("fetch-uptime"), format_duration(info.uptime));
+    match &info.cpu {
+        Some(cpu) => add(tr("fetch-cpu"), format!("{} ({})", cpu, info.nb_cpus)),
+        None => add(tr("fetch-cpu"), info.nb_cpus.to_string()),
+    }
+    add(tr("fetch-memory"), usage(info.used_memory, info.total_memory));
+    for disk in &info.disks {
+        add(format!("{} ({})", tr("fetch-disk"), disk.mount_point), usage(disk.used_space, disk.total_space));
+    }
+
+    let logo = if logo { crate::fetch::logo(std::env::consts::OS) } else { &[] };
+    let width = logo.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+    let mut s = String::new();
+    for i in 0..logo.len().max(lines.len()) {
+        if !logo.is_empty() {
+            let art = logo.get(i).copied().unwrap_or("");
+            s.push_str(&format!("{}   ", format!("{:<width$}", art, width = width).bright_cyan().bold()));
+        }
+        s.push_str(lines.get(i).map_or("", String::as_str));
+        s.push('\n');
+    }
+    s
+}
+
+pub fn format_capabilities(info: &[Capability]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-capabilities"));
+    let mut table = new_table("capabilities", vec![
+        ("metric", tr("col-metric")),
+        ("status", tr("col-status")),
+        ("detail", tr("col-detail")),
+    ]);
+    for capability in info {
+        let status = match capability.status {
+            CapabilityStatus::Supported => tr("status-supported").green(),
+            CapabilityStatus::Unsupported => tr("status-unsupported").red(),
+            CapabilityStatus::NeedsPrivilege => tr("status-needs-privilege").yellow(),
+        };
+        table.add_row(vec![
+            capability.metric.cyan().to_string(),
+            status.to_string(),
+            capability.detail.clone().unwrap_or_default(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_process_detail(info: &ProcessDetail) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-process"));
+    let not_available = || tr("not-available");
+    let rows = [
+        ("process-pid", localize_number(&info.pid.to_string())),
+        ("process-name", info.name.clone()),
+        ("process-cmd", info.cmd.join(" ")),
+        ("process-exe", info.exe.clone().unwrap_or_else(not_available)),
+        ("process-cwd", info.cwd.clone().unwrap_or_else(not_available)),
+        ("process-parent", info.parent.map_or_else(not_available, |pid| localize_number(&pid.to_string()))),
+        ("process-status", info.status.clone()),
+        ("process-user", info.user.clone().unwrap_or_else(not_available)),
+        ("process-started", format_unix_time(info.start_time)),
+        ("process-run-time", format_duration(info.run_time)),
+        ("process-cpu", format!("{}%", format_float(info.cpu_usage, 1))),
+        ("process-memory", format_bytes(info.memory)),
+        ("process-virtual-memory", format_bytes(info.virtual_memory)),
+        ("process-disk-read", format_bytes(info.disk_read)),
+        ("process-disk-written", format_bytes(info.disk_written)),
+        ("process-threads", info.threads.map_or_else(not_available, |n| localize_number(&n.to_string()))),
+    ];
+    for (key, value) in rows {
+        s.push_str(&format!("{:<25} {}\n", tr(key).yellow(), value));
+    }
+    if let Some(threads) = &info.thread_list {
+        s.push_str(&format!("\n{}", section_title("section-threads")));
+        let mut table = new_table("threads", vec![
+            ("tid", tr("col-tid")),
+            ("name", tr("col-name")),
+            ("status", tr("col-status")),
+            ("cpu", tr("col-cpu-pct")),
+        ]);
+        for thread in threads {
+            table.add_row(vec![
+                thread.tid.to_string(),
+                thread.name.clone(),
+                thread.status.clone(),
+                format_float(thread.cpu_usage, 1),
+            ]);
+        }
+        s.push_str(&format!("{}\n", table));
+    }
+    s.push_str(&format!("\n{}", section_title("section-environment")));
+    if info.environ.is_empty() {
+        s.push_str(&format!("{}\n", not_available()));
+    }
+    for var in &info.environ {
+        s.push_str(&format!("{}\n", var));
+    }
+    s
+}
+
+pub fn format_file_holders(info: &[FileHolder]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-file-holders"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-file-holders")));
+        return s;
+    }
+    let mut table = new_table("whoholds", vec![
+        ("pid", tr("col-pid")),
+        ("name", tr("col-name")),
+        ("held_as", tr("col-held-as")),
+        ("path", tr("col-path")),
+    ]);
+    for holder in info {
+        let path = if holder.deleted {
+            format!("{} {}", holder.path, format!("({})", tr("deleted")).red())
+        } else {
+            holder.path.clone()
+        };
+        table.add_row(vec![
+            localize_number(&holder.pid.to_string()).cyan().to_string(),
+            holder.name.clone(),
+            holder.usage.clone(),
+            path,
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_connections(info: &[Connection]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-connections"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-connections")));
+        return s;
+    }
+    let mut table = new_table("connections", vec![
+        ("protocol", tr("col-protocol")),
+        ("local", tr("col-local-address")),
+        ("remote", tr("col-remote-address")),
+        ("state", tr("col-state")),
+        ("pid", tr("col-pid")),
+        ("process", tr("col-name")),
+    ]);
+    for c in info {
+        table.add_row(vec![
+            socket_protocol(c.protocol).blue().to_string(),
+            socket_address(&c.local_address, c.local_port),
+            remote_socket_address(c),
+            colored_socket_state(c.state),
+            c.pid.map(|pid| localize_number(&pid.to_string())).unwrap_or_default(),
+            c.process.clone().unwrap_or_default(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_port_owners(info: &[PortOwner]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-port-owners"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-port-owners")));
+        return s;
+    }
+    let mut table = new_table("port", vec![
+        ("protocol", tr("col-protocol")),
+        ("local", tr("col-local-address")),
+        ("remote", tr("col-remote-address")),
+        ("state", tr("col-state")),
+        ("pid", tr("col-pid")),
+        ("process", tr("col-name")),
+        ("user", tr("col-user")),
+        ("command", tr("col-command")),
+    ]);
+    for owner in info {
+        let c = &owner.connection;
+        table.add_row(vec![
+            socket_protocol(c.protocol).blue().to_string(),
+            socket_address(&c.local_address, c.local_port),
+            remote_socket_address(c),
+            colored_socket_state(c.state),
+            c.pid.map(|pid| localize_number(&pid.to_string())).unwrap_or_default().cyan().to_string(),
+            c.process.clone().unwrap_or_default(),
+            owner.user.clone().unwrap_or_default(),
+            owner.command.clone().unwrap_or_default(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// Containers like `docker stats`, then with `processes` each one's host processes.
+pub fn format_docker_containers(info: &[DockerContainer], processes: bool) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-docker"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-containers")));
+        return s;
+    }
+    let mut table = new_table("docker", vec![
+        ("id", tr("col-id")),
+        ("name", tr("col-name")),
+        ("image", tr("col-image")),
+        ("status", tr("col-status")),
+        ("cpu", tr("col-cpu-pct")),
+        ("memory", tr("col-memory")),
+        ("net", tr("col-net-io")),
+        ("block", tr("col-block-io")),
+        ("pids", tr("col-pids")),
+    ]);
+    let pair = |a: Option<u64>, b: Option<u64>| match (a, b) {
+        (Some(a), Some(b)) => format!("{} / {}", format_bytes(a), format_bytes(b)),
+        (Some(a), None) => format_bytes(a),
+        _ => String::new(),
+    };
+    for c in info {
+        table.add_row(vec![
+            c.id.clone(),
+            c.name.cyan().to_string(),
+            c.image.clone(),
+            c.status.clone(),
+            c.cpu_usage.map(|u| format_float(u, 1)).unwrap_or_default(),
+            pair(c.memory_usage, c.memory_limit),
+            pair(c.network_received, c.network_transmitted),
+            pair(c.block_read, c.block_written),
+            localize_number(&c.processes.len().to_string()),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    if processes {
+        for c in info.iter().filter(|c| !c.processes.is_empty()) {
+            s.push_str(&format!("{}\n", format!("{} ({})", c.name, c.id).cyan().bold()));
+            let mut table = new_table("docker_processes", vec![
+                ("pid", tr("col-pid")),
+                ("name", tr("col-name")),
+                ("cpu", tr("col-cpu-pct")),
+                ("memory", tr("col-memory")),
+            ]);
+            for p in &c.processes {
+                table.add_row(vec![localize_number(&p.pid.to_string()), p.name.clone(), format_float(p.cpu_usage, 1), format_bytes(p.memory)]);
+            }
+            s.push_str(&format!("{}\n", table));
+        }
+    }
+    s
+}
+
+/// The node in the units of `kubectl top node` (millicores, MiB), then the processes
+/// in each pod.
+pub fn format_k8s_node(info: &K8sNodeReport) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-k8s-node"));
+    let mut table = new_table("k8s_node", vec![
+        ("name", tr("col-name")),
+        ("cpu_cores", tr("col-cpu-cores")),
+        ("cpu", tr("col-cpu-pct")),
+        ("memory", tr("col-memory-bytes")),
+        ("mem_pct", tr("col-mem-pct")),
+    ]);
+    table.add_row(vec![
+        info.name.cyan().to_string(),
+        format!("{}m", (info.cpu_cores * 1000.0).round() as u64),
+        format!("{:.0}%", info.cpu_percent),
+        format!("{}Mi", info.memory_bytes >> 20),
+        format!("{:.0}%", info.memory_percent),
+    ]);
+    s.push_str(&format!("{}\n", table));
+
+    s.push_str(&section_title("section-pod-processes"));
+    if info.processes.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-pod-processes")));
+        return s;
+    }
+    let mut table = new_table("k8s_pods", vec![
+        ("namespace", tr("col-namespace")),
+        ("pod", tr("col-pod")),
+        ("container", tr("col-container")),
+        ("qos", tr("col-qos")),
+        ("pid", tr("col-pid")),
+        ("name", tr("col-name")),
+        ("cpu", tr("col-cpu-pct")),
+        ("memory", tr("col-memory")),
+    ]);
+    for p in &info.processes {
         table.add_row(vec![
-            p.pid.cyan().to_string(),
-            name,
-            format!("{:>5.1}", p.cpu_usage),
+            p.namespace.clone().unwrap_or_default(),
+            p.pod.clone().unwrap_or_else(|| p.pod_uid.clone()).cyan().to_string(),
+            p.container_id.as_deref().map(|id| id.chars().take(12).collect()).unwrap_or_default(),
+            p.qos.clone(),
+            localize_number(&p.pid.to_string()),
+            p.name.clone(),
+            format_float(p.cpu_usage, 1),
             format_bytes(p.memory),
         ]);
     }
@@ -108,14 +988,881 @@ pub fn format_processes_info(info: &[ProcessInfo]) -> String {
     s
 }
 
+fn socket_protocol(protocol: SocketProtocol) -> &'static str {
+    match protocol {
+        SocketProtocol::Tcp => "tcp",
+        SocketProtocol::Udp => "udp",
+    }
+}
+
+/// `*` for the unspecified peer of listening and unconnected sockets.
+fn remote_socket_address(c: &Connection) -> String {
+    if c.remote_port == 0 && c.remote_address.parse::<std::net::IpAddr>().is_ok_and(|a| a.is_unspecified()) {
+        "*".to_string()
+    } else {
+        socket_address(&c.remote_address, c.remote_port)
+    }
+}
+
+fn colored_socket_state(state: SocketState) -> String {
+    match state {
+        SocketState::Listen | SocketState::Unconnected => socket_state(state).green().to_string(),
+        SocketState::Established => socket_state(state).cyan().to_string(),
+        _ => socket_state(state).to_string(),
+    }
+}
+
+/// The state names `ss` and `netstat` print, which aren't translated.
+fn socket_state(state: SocketState) -> &'static str {
+    match state {
+        SocketState::Established => "ESTABLISHED",
+        SocketState::SynSent => "SYN_SENT",
+        SocketState::SynReceived => "SYN_RECV",
+        SocketState::FinWait1 => "FIN_WAIT1",
+        SocketState::FinWait2 => "FIN_WAIT2",
+        SocketState::TimeWait => "TIME_WAIT",
+        SocketState::Closed => "CLOSED",
+        SocketState::CloseWait => "CLOSE_WAIT",
+        SocketState::LastAck => "LAST_ACK",
+        SocketState::Listen => "LISTEN",
+        SocketState::Closing => "CLOSING",
+        SocketState::Unconnected => "UNCONN",
+        SocketState::Unknown => "?",
+    }
+}
+
+/// `address:port`, with IPv6 addresses in brackets as in URLs.
+fn socket_address(address: &str, port: u16) -> String {
+    if address.contains(':') {
+        format!("[{}]:{}", address, port)
+    } else {
+        format!("{}:{}", address, port)
+    }
+}
+
+pub fn format_users_info(info: &[UserInfo]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-users"));
+    let mut table = new_table("users", vec![
+        ("name", tr("col-name")),
+        ("uid", tr("col-uid")),
+        ("gid", tr("col-gid")),
+        ("groups", tr("col-groups")),
+        ("sessions", tr("col-sessions")),
+    ]);
+    for user in info {
+        let sessions = localize_number(&user.sessions.to_string());
+        table.add_row(vec![
+            user.name.cyan().to_string(),
+            user.uid.clone(),
+            user.gid.clone(),
+            user.groups.join(", "),
+            if user.sessions > 0 { sessions.green().to_string() } else { sessions },
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_sessions(info: &[SessionInfo]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-sessions"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-sessions")));
+        return s;
+    }
+    let mut table = new_table("sessions", vec![
+        ("user", tr("col-user")),
+        ("tty", tr("col-tty")),
+        ("host", tr("col-host")),
+        ("login", tr("col-login")),
+    ]);
+    for session in info {
+        table.add_row(vec![
+            session.user.cyan().to_string(),
+            session.tty.clone(),
+            session.host.clone().unwrap_or_default(),
+            session.login_time.clone(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_all_report(info: &AllReport) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-system"));
+    s.push_str(&format_system_info(&info.system));
+    s.push('\n');
+    s.push_str(&format_cpu_info(&info.cpu));
+    s.push('\n');
+    s.push_str(&section_title("section-memory"));
+    s.push_str(&format_memory_info(&info.memory));
+    s.push('\n');
+    s.push_str(&format_disks_info(&info.disks));
+    s.push('\n');
+    s.push_str(&format_network_info(&info.network));
+    s.push('\n');
+    s.push_str(&format_components_info(&info.components));
+    s.push('\n');
+    s.push_str(&format_processes_info(&info.processes));
+    s
+}
+
+/// A snapshot fetched by `query`: the sections of `all`, headed by when it was taken.
+pub fn format_snapshot(info: &Snapshot) -> String {
+    let mut s = format!("{:<25} {}\n\n", tr("taken-at").yellow(), info.taken_at);
+    s.push_str(&section_title("section-system"));
+    s.push_str(&format_system_info(&info.system));
+    s.push('\n');
+    s.push_str(&format_cpu_info(&info.cpu));
+    s.push('\n');
+    s.push_str(&section_title("section-memory"));
+    s.push_str(&format_memory_info(&info.memory));
+    s.push('\n');
+    s.push_str(&format_disks_info(&info.disks));
+    s.push('\n');
+    s.push_str(&format_network_info(&info.network));
+    s.push('\n');
+    s.push_str(&format_processes_info(&info.processes));
+    s
+}
+
+/// One row per `--host` agent; hosts that couldn't be polled show the error in red.
+pub fn format_host_statuses(info: &[HostStatus]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-hosts"));
+    let mut table = new_table("hosts", vec![
+        ("address", tr("col-address")),
+        ("host", tr("col-host")),
+        ("status", tr("col-status")),
+        ("cpu", tr("col-cpu-pct")),
+        ("mem_pct", tr("col-mem-pct")),
+        ("load", tr("col-load")),
+        ("disks", tr("col-disk-alerts")),
+    ]);
+    for host in info {
+        let status = match &host.error {
+            Some(error) => error.red().to_string(),
+            None => tr("load-ok").green().to_string(),
+        };
+        let disks: Vec<String> = host.disk_alerts.iter()
+            .map(|d| format!("{} {}%", d.mount_point, format_float(d.used_percent, 0)))
+            .collect();
+        table.add_row(vec![
+            host.address.clone(),
+            host.host_name.clone().unwrap_or_default().cyan().to_string(),
+            status,
+            host.cpu_usage.map(|u| format_float(u, 1)).unwrap_or_default(),
+            host.memory_percent.map(|u| format_float(u, 1)).unwrap_or_default(),
+            host.load_average.map(|l| localize_number(&format!("{:.2}", l))).unwrap_or_default(),
+            disks.join(", ").red().to_string(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// "+1.50 GiB" or "-512.00 MiB".
+pub fn format_bytes_change(change: i64) -> String {
+    let sign = if change < 0 { "-" } else { "+" };
+    format!("{}{}", sign, format_bytes(change.unsigned_abs()))
+}
+
+pub fn format_snapshot_diff(info: &SnapshotDiff) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-diff"));
+    s.push_str(&format!("{:<25} {}\n", tr("diff-from").yellow(), info.from));
+    s.push_str(&format!("{:<25} {}\n", tr("diff-to").yellow(), info.to));
+    if info.rebooted {
+        s.push_str(&format!("{}\n", tr("diff-rebooted").red().bold()));
+    }
+    for (key, change) in [("used-memory", &info.used_memory), ("used-swap", &info.used_swap)] {
+        s.push_str(&format!("{:<25} {} → {} ({})\n", tr(key).yellow(), format_bytes(change.before), format_bytes(change.after), format_bytes_change(change.change)));
+    }
+
+    s.push('\n');
+    s.push_str(&section_title("section-disk-changes"));
+    if info.disks.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-changes")));
+    } else {
+        let mut table = new_table("diff_disks", vec![
+            ("name", tr("col-name")),
+            ("before", tr("col-before")),
+            ("after", tr("col-after")),
+            ("change", tr("col-change")),
+        ]);
+        for disk in &info.disks {
+            table.add_row(vec![
+                disk.name.clone(),
+                disk.before.map(format_bytes).unwrap_or_else(|| "-".to_string()),
+                disk.after.map(format_bytes).unwrap_or_else(|| "-".to_string()),
+                format_bytes_change(disk.change),
+            ]);
+        }
+        s.push_str(&format!("{}\n", table));
+    }
+
+    for (key, processes) in [("section-started", &info.started), ("section-exited", &info.exited)] {
+        s.push('\n');
+        s.push_str(&section_title(key));
+        if processes.is_empty() {
+            s.push_str(&format!("{}\n", tr("no-changes")));
+            continue;
+        }
+        let mut table = new_table("diff_processes", vec![
+            ("pid", tr("col-pid")),
+            ("name", tr("col-name")),
+            ("memory", tr("col-memory")),
+        ]);
+        for p in processes {
+            table.add_row(vec![localize_number(&p.pid.to_string()).cyan().to_string(), p.name.clone(), format_bytes(p.memory)]);
+        }
+        s.push_str(&format!("{}\n", table));
+    }
+    s
+}
+
+pub fn format_history(info: &[HistorySample]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-history"));
+    if info.is_empty() {
+        s.push_str(&format!("{}\n", tr("no-history")));
+        return s;
+    }
+    let with_target = info.iter().any(|h| h.target.is_some());
+    let mut columns = vec![("time", tr("col-time"))];
+    if with_target {
+        columns.push(("target", tr("col-target")));
+    }
+    columns.push(("value", tr("col-value")));
+    let mut table = new_table("history", columns);
+    for sample in info {
+        let mut row = vec![sample.time.clone()];
+        if with_target {
+            row.push(sample.target.clone().unwrap_or_default());
+        }
+        row.push(format_history_value(&sample.metric, sample.value));
+        table.add_row(row);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// A recorded value in the unit of its series.
+pub fn format_history_value(metric: &str, value: f64) -> String {
+    match metric {
+        "disk_avail" => format_bytes(value.max(0.0) as u64),
+        "net_rx" | "net_tx" => format_rate_per_sec(value),
+        "load1" | "load5" | "load15" => format_float(value as f32, 2),
+        _ => format_float(value as f32, 1),
+    }
+}
+
+pub fn format_kill_results(info: &[KillResult]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-kill"));
+    let mut table = new_table("kill", vec![
+        ("pid", tr("col-pid")),
+        ("name", tr("col-name")),
+        ("signal", tr("col-signal")),
+        ("outcome", tr("col-outcome")),
+    ]);
+    for target in info {
+        let outcome = match target.outcome {
+            KillOutcome::Matched => tr("outcome-matched").yellow(),
+            KillOutcome::Sent => tr("outcome-sent").green(),
+            KillOutcome::Failed => tr("outcome-failed").red(),
+            KillOutcome::Unsupported => tr("outcome-unsupported").red(),
+        };
+        table.add_row(vec![
+            localize_number(&target.pid.to_string()).cyan().to_string(),
+            target.name.clone(),
+            target.signal.clone(),
+            outcome.to_string(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// A plugin-style status line naming the rules that fired, then every check.
+/// The line exactly as Waybar reads it.
+pub fn format_statusbar(line: &StatusbarLine) -> String {
+    serde_json::to_string(line).unwrap_or_default()
+}
+
+pub fn format_alert_results(info: &[AlertResult]) -> String {
+    let rules_with = |status: AlertStatus| {
+        let mut rules: Vec<&str> = info.iter().filter(|r| r.status == status).map(|r| r.rule.as_str()).collect();
+        rules.dedup();
+        rules.join(", ")
+    };
+    let mut s = match crate::alert::exit_code(info) {
+        0 => tr("alert-ok").green().bold().to_string(),
+        2 => format!("{} {}", tr("alert-critical"), rules_with(AlertStatus::Fired)).red().bold().to_string(),
+        _ => format!("{} {}", tr("alert-unknown"), rules_with(AlertStatus::Unknown)).yellow().bold().to_string(),
+    };
+    s.push_str("\n\n");
+    let mut table = new_table("alerts", vec![
+        ("rule", tr("col-rule")),
+        ("target", tr("col-target")),
+        ("value", tr("col-value")),
+        ("status", tr("col-status")),
+    ]);
+    for result in info {
+        let value = match result.value {
+            Some(value) if crate::alert::is_bytes(&result.metric) => format_bytes(value.max(0.0) as u64),
+            Some(value) => format_float(value as f32, 1),
+            None => "-".to_string(),
+        };
+        let status = match result.status {
+            AlertStatus::Ok => tr("alert-status-ok").green(),
+            AlertStatus::Fired => tr("alert-status-fired").red().bold(),
+            AlertStatus::Unknown => tr("alert-status-unknown").yellow(),
+        };
+        table.add_row(vec![
+            result.rule.clone(),
+            result.target.clone().unwrap_or_default(),
+            value,
+            status.to_string(),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// One line per event, for `events`.
+pub fn format_hardware_event(event: &HardwareEvent) -> String {
+    let (id, attached) = match event.event {
+        HardwareEventKind::DiskAttached => ("event-disk-attached", true),
+        HardwareEventKind::DiskDetached => ("event-disk-detached", false),
+        HardwareEventKind::InterfaceAdded => ("event-interface-added", true),
+        HardwareEventKind::InterfaceRemoved => ("event-interface-removed", false),
+        HardwareEventKind::PowerChanged => ("event-power-changed", true),
+    };
+    let label = format!("{:<20}", tr(id));
+    let label = if attached { label.green() } else { label.red() };
+    let mut s = format!("{}  {}  {}", event.timestamp, label, event.device.cyan());
+    if let Some(detail) = &event.detail {
+        s.push_str(&format!(" ({})", detail));
+    }
+    s
+}
+
+/// One line per event, for `processes --follow`.
+pub fn format_process_event(event: &ProcessEvent) -> String {
+    let kind = match event.event {
+        ProcessEventKind::Start => format!("{:<8}", tr("event-started")).green(),
+        ProcessEventKind::Exit => format!("{:<8}", tr("event-exited")).red(),
+    };
+    let mut s = format!("{}  {}  {:>7}  {}", event.timestamp, kind, localize_number(&event.pid.to_string()).cyan(), event.name);
+    if let Some(parent) = event.parent {
+        s.push_str(&format!("  {} {}", tr("event-parent"), localize_number(&parent.to_string())));
+    }
+    if let Some(run_time) = event.run_time {
+        s.push_str(&format!("  {} {}", tr("event-ran-for"), format_duration(run_time)));
+    }
+    s
+}
+
+pub fn format_thermal_sample(info: &ThermalSample) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-thermal"));
+    let mut frequency = format!("{} MHz", localize_number(&info.frequency_mhz.to_string()));
+    if let Some(peak) = info.peak_frequency_mhz {
+        frequency.push_str(&format!(" ({} {} MHz)", tr("peak"), localize_number(&peak.to_string())));
+    }
+    s.push_str(&format!("{:<25} {}\n", tr("cpu-frequency").yellow(), frequency));
+    let temperature = info.temperature.map(|t| format!("{}°C", format_float(t, 1))).unwrap_or_else(|| tr("not-available"));
+    s.push_str(&format!("{:<25} {} ({} {}°C)\n", tr("temperature").yellow(), temperature, tr("critical"), format_float(info.critical, 1)));
+    let throttling = if info.throttling { tr("yes").red().bold() } else { tr("no").green() };
+    s.push_str(&format!("{:<25} {}\n", tr("throttling").yellow(), throttling));
+    s
+}
+
+pub fn format_throttle_summary(info: &ThrottleSummary) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-throttle-summary"));
+    s.push_str(&format!("{:<25} {}\n", tr("samples").yellow(), localize_number(&info.samples.to_string())));
+    s.push_str(&format!("{:<25} {} ({}%)\n", tr("throttled-samples").yellow(), localize_number(&info.throttled_samples.to_string()), format_float(info.throttled_pct, 1)));
+    s.push_str(&format!("{:<25} {}–{} MHz\n", tr("frequency-range").yellow(), localize_number(&info.min_frequency_mhz.to_string()), localize_number(&info.peak_frequency_mhz.to_string())));
+    let max_temperature = info.max_temperature.map(|t| format!("{}°C", format_float(t, 1))).unwrap_or_else(|| tr("not-available"));
+    s.push_str(&format!("{:<25} {}\n", tr("max-temperature").yellow(), max_temperature));
+    s
+}
+
+pub fn format_watch_summary(info: &WatchSummary) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-watch-summary"));
+    s.push_str(&format!("{:<25} {}\n", tr("samples").yellow(), localize_number(&info.samples.to_string())));
+    s.push_str(&format!("{:<25} {}\n", tr("elapsed").yellow(), format_duration(info.elapsed_seconds.round() as u64)));
+    s.push_str(&format!("{:<25} {}\n", tr("network-received").yellow(), format_bytes(info.network_received)));
+    s.push_str(&format!("{:<25} {}\n", tr("network-transmitted").yellow(), format_bytes(info.network_transmitted)));
+    let mut table = new_table("watch_summary", vec![
+        ("metric", tr("col-metric")),
+        ("min", tr("col-min")),
+        ("avg", tr("col-avg")),
+        ("p95", tr("col-p95")),
+        ("max", tr("col-max")),
+    ]);
+    let mut add_row = |key: &str, stats: &Option<MetricStats>, show: fn(f64) -> String| {
+        if let Some(stats) = stats {
+            table.add_row(vec![tr(key).cyan().to_string(), show(stats.min), show(stats.avg), show(stats.p95), show(stats.max)]);
+        }
+    };
+    add_row("metric-cpu-usage", &info.cpu_usage, |v| format!("{}%", format_float(v as f32, 1)));
+    add_row("metric-memory-used", &info.memory_used, |v| format_bytes(v as u64));
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_self_stats(info: &SelfStats) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-self-stats"));
+    s.push_str(&format!("{:<25} {}\n", tr("iterations").yellow(), localize_number(&info.iterations.to_string())));
+    if let Some(ms) = info.cpu_time_ms {
+        s.push_str(&format!("{:<25} {} ms\n", tr("cpu-time").yellow(), localize_number(&ms.to_string())));
+    }
+    if let Some(rss) = info.peak_rss {
+        s.push_str(&format!("{:<25} {}\n", tr("peak-memory").yellow(), format_bytes(rss)));
+    }
+    let mut table = new_table("self_stats", vec![
+        ("section", tr("col-section")),
+        ("samples", tr("col-samples")),
+        ("avg", tr("col-avg-ms")),
+        ("max", tr("col-max-ms")),
+    ]);
+    for section in &info.sections {
+        table.add_row(vec![
+            section.section.cyan().to_string(),
+            localize_number(&section.samples.to_string()),
+            format_float(section.avg_ms as f32, 1),
+            format_float(section.max_ms as f32, 1),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_collection_profile(info: &[StageTiming]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-profile-collection"));
+    let mut table = new_table("profile_collection", vec![
+        ("stage", tr("col-stage")),
+        ("items", tr("col-items")),
+        ("first", tr("col-first-ms")),
+        ("refreshes", tr("col-refreshes")),
+        ("avg", tr("col-avg-ms")),
+        ("max", tr("col-max-ms")),
+    ]);
+    for stage in info {
+        table.add_row(vec![
+            stage.stage.cyan().to_string(),
+            stage.items.map_or_else(|| "-".to_string(), |n| localize_number(&n.to_string())),
+            format_float(stage.first_ms as f32, 1),
+            localize_number(&stage.refreshes.to_string()),
+            format_float(stage.avg_ms as f32, 1),
+            format_float(stage.max_ms as f32, 1),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+pub fn format_bench_results(info: &[BenchResult]) -> String {
+    let mut s = String::new();
+    s.push_str(&section_title("section-bench"));
+    let mut table = new_table("bench", vec![
+        ("benchmark", tr("col-benchmark")),
+        ("result", tr("col-result")),
+        ("time", tr("col-time")),
+    ]);
+    for b in info {
+        table.add_row(vec![
+            tr(&format!("bench-{}", b.benchmark.replace('_', "-"))).cyan().to_string(),
+            format!("{} {}", localize_number(&format!("{:.1}", b.value)), b.unit),
+            format!("{} ms", localize_number(&format!("{:.0}", b.elapsed_ms))),
+        ]);
+    }
+    s.push_str(&format!("{}\n", table));
+    s
+}
+
+/// Renders `info` in `format`: JSON and YAML serialize it whole, led by its
+/// `schema_version`, CSV writes one line per item of `rows`, Prometheus writes its
+/// metrics, and text comes from `text`, which markdown reshapes into headings, lists,
+/// and tables.
+pub fn render<T, R>(format: OutputFormat, delimiter: char, info: &T, rows: &[R], text: impl FnOnce(&T) -> String) -> Result<String>
+where
+    T: Serialize + ToMetrics + ?Sized,
+    R: Serialize,
+{
+    let timestamp = TIMESTAMP.with(|t| t.borrow().clone());
+    let header = timestamp.as_ref().map(|t| format!("{}\n", format!("--- {} ---", t).bright_cyan().bold())).unwrap_or_default();
+    Ok(match format {
+        OutputFormat::Text => format!("{}{}", header, text(info)),
+        OutputFormat::Json => to_json(project(versioned(info, timestamp.as_deref())?), false)?,
+        OutputFormat::Yaml => serde_yaml_ng::to_string(&project(versioned(info, timestamp.as_deref())?))?.trim_end().to_string(),
+        OutputFormat::Csv => {
+            let stamp: Vec<(&str, serde_json::Value)> = timestamp.iter().map(|t| ("timestamp", t.as_str().into())).collect();
+            let rows = rows.iter().map(|r| stamped(r, &stamp).map(project)).collect::<Result<Vec<_>>>()?;
+            format_csv(&rows, delimiter)
+        }
+        // The textfile collector rejects samples with timestamps.
+        OutputFormat::Prometheus => format_prometheus(info),
+        OutputFormat::Markdown => {
+            set_markdown(true);
+            let text = format!("{}{}", header, text(info));
+            set_markdown(false);
+            to_markdown(&text)
+        }
+    })
+}
+
+thread_local! {
+    static TIMESTAMP: RefCell<Option<String>> = const { RefCell::new(None) };
+    static FIELDS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static QUERY: RefCell<Option<jq::Path>> = const { RefCell::new(None) };
+}
+
+/// Sets the time `render` stamps output with (`--timestamp`), or `None` for no stamp.
+pub fn set_timestamp(timestamp: Option<String>) {
+    TIMESTAMP.with(|t| *t.borrow_mut() = timestamp);
+}
+
+/// Sets the fields JSON, YAML, and CSV output is cut down to (`--fields`); empty for all.
+pub fn set_fields(fields: Vec<String>) {
+    FIELDS.with(|f| *f.borrow_mut() = fields);
+}
+
+/// Sets the path JSON output is replaced with the results of (`--query`), or `None` for all of it.
+pub fn set_query(query: Option<jq::Path>) {
+    QUERY.with(|q| *q.borrow_mut() = query);
+}
+
+/// `value` as JSON, or the results of the [`set_query`] query on it.
+fn to_json(value: serde_json::Value, compact: bool) -> Result<String> {
+    QUERY.with(|query| match &*query.borrow() {
+        Some(query) => Ok(format_results(&query.apply(&value).map_err(Error::Failed)?, compact)),
+        None if compact => Ok(serde_json::to_string(&value)?),
+        None => Ok(serde_json::to_string_pretty(&value)?),
+    })
+}
+
+/// `value` cut down to the [`set_fields`] fields, if any were set.
+fn project(value: serde_json::Value) -> serde_json::Value {
+    FIELDS.with(|fields| {
+        let fields = fields.borrow();
+        if fields.is_empty() { value } else { select_fields(&value, &fields) }
+    })
+}
+
+/// Only `fields` of `value`, in the order given, after `schema_version` and `timestamp`,
+/// which are always kept; in a list, of every item. A field is a key or a dotted path
+/// such as `load_average.one`, and a path through a list picks from each of its items,
+/// as `processes.name` does. Fields an object doesn't have are left out.
+pub fn select_fields(value: &serde_json::Value, fields: &[String]) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        Value::Array(items) => Value::Array(items.iter().map(|item| select_fields(item, fields)).collect()),
+        Value::Object(object) => {
+            let mut selected = Value::Object(
+                ["schema_version", "timestamp"].iter()
+                    .filter_map(|key| Some((key.to_string(), object.get(*key)?.clone())))
+                    .collect(),
+            );
+            for field in fields {
+                if let Some(picked) = pick(value, &field.split('.').collect::<Vec<_>>()) {
+                    merge(&mut selected, picked);
+                }
+            }
+            selected
+        }
+        other => other.clone(),
+    }
+}
+
+/// The value at `path` in `value`, wrapped back into its parent objects.
+fn pick(value: &serde_json::Value, path: &[&str]) -> Option<serde_json::Value> {
+    use serde_json::Value;
+    let Some((key, rest)) = path.split_first() else { return Some(value.clone()) };
+    match value {
+        // Items stay in place, if empty, so the lists of several paths still line up.
+        Value::Array(items) => Some(Value::Array(items.iter().map(|item| {
+            pick(item, path).unwrap_or_else(|| if item.is_object() { Value::Object(Default::default()) } else { Value::Null })
+        }).collect())),
+        Value::Object(object) => {
+            let inner = pick(object.get(*key)?, rest)?;
+            Some(Value::Object([(key.to_string(), inner)].into_iter().collect()))
+        }
+        _ => None,
+    }
+}
+
+/// Adds `from` into `into`, joining objects key by key and lists item by item.
+fn merge(into: &mut serde_json::Value, from: serde_json::Value) {
+    use serde_json::Value;
+    match (into, from) {
+        (Value::Object(into), Value::Object(from)) => {
+            for (key, value) in from {
+                match into.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        into.insert(key, value);
+                    }
+                }
+            }
+        }
+        (Value::Array(into), Value::Array(from)) if into.len() == from.len() => {
+            for (existing, value) in into.iter_mut().zip(from) {
+                merge(existing, value);
+            }
+        }
+        (into, from) => *into = from,
+    }
+}
+
+/// `value` as JSON and YAML output show it: led by `schema_version` and, when given,
+/// `timestamp`; in a list, every object gets them.
+pub fn versioned<T: Serialize + ?Sized>(value: &T, timestamp: Option<&str>) -> Result<serde_json::Value> {
+    let mut fields = vec![("schema_version", SCHEMA_VERSION.into())];
+    if let Some(timestamp) = timestamp {
+        fields.push(("timestamp", timestamp.into()));
+    }
+    stamped(value, &fields)
+}
+
+/// `value` as JSON with `fields` leading each object; in a list, every object gets
+/// them. Goes through the JSON text so `f32` fields keep their short form.
+fn stamped<T: Serialize + ?Sized>(value: &T, fields: &[(&str, serde_json::Value)]) -> Result<serde_json::Value> {
+    let stamp = |value: serde_json::Value| match value {
+        serde_json::Value::Object(rest) => {
+            let mut object: serde_json::Map<String, serde_json::Value> = fields.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
+            object.extend(rest);
+            serde_json::Value::Object(object)
+        }
+        other => other,
+    };
+    Ok(match serde_json::from_str(&serde_json::to_string(value)?)? {
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.into_iter().map(stamp).collect()),
+        other => stamp(other),
+    })
+}
+
+/// Renders one item of an event stream: a JSON line (NDJSON), a YAML document, or a text line.
+pub fn render_event<T: Serialize>(format: OutputFormat, event: &T, text: impl FnOnce(&T) -> String) -> Result<String> {
+    Ok(match format {
+        OutputFormat::Json => to_json(project(versioned(event, None)?), true)?,
+        OutputFormat::Yaml => format!("---\n{}", serde_yaml_ng::to_string(&project(versioned(event, None)?))?.trim_end()),
+        OutputFormat::Text | OutputFormat::Csv | OutputFormat::Prometheus | OutputFormat::Markdown => text(event),
+    })
+}
+
+/// Renders records as CSV with a header row taken from their serialized field names.
+/// Nested objects become `parent.child` columns and lists are kept as JSON. Fields
+/// containing the delimiter, quotes, or line breaks are quoted, with quotes doubled.
+pub fn format_csv<T: Serialize>(rows: &[T], delimiter: char) -> String {
+    let rows: Vec<serde_json::Map<String, serde_json::Value>> = rows.iter().map(|r| {
+        let mut flat = serde_json::Map::new();
+        flatten_json("", serde_json::to_value(r).unwrap_or_default(), &mut flat);
+        flat
+    }).collect();
+    let mut headers: Vec<String> = Vec::new();
+    for row in &rows {
+        for key in row.keys() {
+            if !headers.contains(key) {
+                headers.push(key.clone());
+            }
+        }
+    }
+
+    let mut s = String::new();
+    s.push_str(&csv_line(headers.iter().map(|h| h.as_str()), delimiter));
+    for row in &rows {
+        let fields: Vec<String> = headers.iter().map(|h| match row.get(h) {
+            None | Some(serde_json::Value::Null) => String::new(),
+            Some(serde_json::Value::String(v)) => v.clone(),
+            Some(v) => v.to_string(),
+        }).collect();
+        s.push_str(&csv_line(fields.iter().map(|f| f.as_str()), delimiter));
+    }
+    s
+}
+
+fn flatten_json(prefix: &str, value: serde_json::Value, out: &mut serde_json::Map<String, serde_json::Value>) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (key, value) in fields {
+                let key = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                flatten_json(&key, value, out);
+            }
+        }
+        value => {
+            out.insert(prefix.to_string(), value);
+        }
+    }
+}
+
+fn csv_line<'a>(fields: impl Iterator<Item = &'a str>, delimiter: char) -> String {
+    let fields: Vec<String> = fields.map(|f| {
+        if f.contains(delimiter) || f.contains(['"', '\n', '\r']) {
+            format!("\"{}\"", f.replace('"', "\"\""))
+        } else {
+            f.to_string()
+        }
+    }).collect();
+    let mut line = fields.join(&delimiter.to_string());
+    line.push('\n');
+    line
+}
+
+/// Removes the ANSI color escapes that `colored` adds, for plain-text copies.
+pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1B' {
+            // CSI sequences end with a letter, e.g. "\x1B[1;32m".
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[derive(PartialEq, Clone, Copy)]
+enum Block {
+    Heading,
+    List,
+    Table,
+    Code,
+}
+
+/// Turns text output into GitHub-flavored markdown: section titles become headings,
+/// "Label: value" lines list items, and anything else (such as a process tree) a code
+/// block. Tables are expected to be drawn in markdown already (see `set_markdown`).
+pub fn to_markdown(text: &str) -> String {
+    let mut out = String::new();
+    let mut last: Option<Block> = None;
+    for line in strip_ansi(text).lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if last == Some(Block::Code) {
+                out.push_str("```\n");
+            }
+            last = None;
+            continue;
+        }
+        let title = line.strip_prefix("=> ").and_then(|t| t.strip_suffix(':'))
+            .or_else(|| line.strip_prefix("--- ").and_then(|t| t.strip_suffix(" ---")));
+        let (block, rendered) = if let Some(title) = title {
+            (Block::Heading, format!("## {}", title))
+        } else if line.starts_with('|') {
+            (Block::Table, line.to_string())
+        } else if let Some((label, value)) = line.split_once(':')
+            && !label.starts_with(char::is_whitespace)
+            && value.starts_with(char::is_whitespace)
+            && !value.trim().is_empty() {
+            (Block::List, format!("- **{}:** {}", label, value.trim()))
+        } else {
+            (Block::Code, line.to_string())
+        };
+        if last != Some(block) || block == Block::Heading {
+            if last == Some(Block::Code) {
+                out.push_str("```\n");
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            if block == Block::Code {
+                out.push_str("```\n");
+            }
+        }
+        out.push_str(&rendered);
+        out.push('\n');
+        last = Some(block);
+    }
+    if last == Some(Block::Code) {
+        out.push_str("```\n");
+    }
+    out
+}
+
+/// Renders a localized section heading such as "=> CPUs:".
+pub fn section_title(id: &str) -> String {
+    format!("{}\n", format!("=> {}:", tr(id)).bright_green().bold())
+}
+
+thread_local! {
+    static UNITS: Cell<(Units, bool)> = const { Cell::new((Units::Auto, false)) };
+}
+
+/// Sets the units `format_bytes` uses on the current thread; `si` switches to powers
+/// of 1000 (`--si`).
+pub fn set_units(units: Units, si: bool) {
+    UNITS.with(|u| u.set((units, si)));
+}
+
+/// Renders a byte size in the `--units` scale, e.g. "1.50 GiB", or "1610612736" with
+/// `--units bytes`.
 pub fn format_bytes(bytes: u64) -> String {
-    if bytes == 0 {
-        return "0 B".to_string();
-    }
-    let units = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
-    let i = (bytes as f64).log(1024.0).floor() as usize;
-    let i = i.min(units.len() - 1);
-    let value = bytes as f64 / 1024.0f64.powi(i as i32);
-    format!("{:.2} {}", value, units[i])
+    let (units, si) = UNITS.with(Cell::get);
+    let (base, names) = if si || units == Units::Decimal {
+        (1000.0f64, ["B", "kB", "MB", "GB", "TB", "PB", "EB"])
+    } else {
+        (1024.0f64, ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"])
+    };
+    let i = match units {
+        Units::Bytes => return localize_number(&bytes.to_string()),
+        Units::Kib => 1,
+        Units::Mib => 2,
+        Units::Gib => 3,
+        Units::Auto | Units::Binary | Units::Decimal if bytes == 0 => return "0 B".to_string(),
+        Units::Auto | Units::Binary | Units::Decimal => ((bytes as f64).log(base).floor() as usize).min(names.len() - 1),
+    };
+    let value = bytes as f64 / base.powi(i as i32);
+    format!("{} {}", localize_number(&format!("{:.2}", value)), names[i])
+}
+
+/// Renders a bytes-per-second rate as a signed per-minute amount, red when space is being consumed.
+pub fn format_rate_per_min(bytes_per_sec: f64) -> String {
+    let per_min = format_bytes((bytes_per_sec.abs() * 60.0).round() as u64);
+    if bytes_per_sec < 0.0 {
+        format!("-{}", per_min).red().to_string()
+    } else if bytes_per_sec > 0.0 {
+        format!("+{}", per_min).green().to_string()
+    } else {
+        per_min
+    }
+}
+
+/// Renders a throughput such as disk reads as "1.50 MiB/s".
+pub fn format_rate_per_sec(bytes_per_sec: f64) -> String {
+    format!("{}/s", format_bytes(bytes_per_sec.round() as u64))
+}
+
+/// Renders a number of seconds as "3d 4h 12m", keeping seconds only for spans under an hour.
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, mins, secs) = (secs / 86_400, secs % 86_400 / 3_600, secs % 3_600 / 60, secs % 60);
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, mins)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m {}s", mins, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Formats a float with fixed precision, honoring `--locale-numbers`.
+pub fn format_float(value: f32, precision: usize) -> String {
+    localize_number(&format!("{:.*}", precision, value))
 }
 
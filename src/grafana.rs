@@ -0,0 +1,59 @@
+//! The Grafana simple-JSON datasource protocol, which the JSON and Infinity datasource
+//! plugins speak: `/search` answers with the series names for the metric picker, and
+//! `/query` asks for the points of some of them within a time range.
+
+use std::io;
+use std::time::UNIX_EPOCH;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+#[derive(Deserialize)]
+struct QueryRequest {
+    range: QueryRange,
+    #[serde(default)]
+    targets: Vec<QueryTarget>,
+}
+
+#[derive(Deserialize)]
+struct QueryRange {
+    from: String,
+    to: String,
+}
+
+#[derive(Deserialize)]
+struct QueryTarget {
+    #[serde(default)]
+    target: String,
+}
+
+/// What a `/query` request asks for: series names, and the time range in Unix seconds
+/// (inclusive).
+#[derive(Debug, PartialEq)]
+pub struct Query {
+    pub targets: Vec<String>,
+    pub from: u64,
+    pub to: u64,
+}
+
+/// Reads a `/query` request body. Fields Grafana sends besides the range and the targets'
+/// names, such as `maxDataPoints`, are ignored; a body that isn't such a request is an
+/// `InvalidInput` error.
+pub fn parse_query(body: &[u8]) -> io::Result<Query> {
+    let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidInput, e);
+    let request: QueryRequest = serde_json::from_slice(body).map_err(|e| invalid(format!("invalid query: {}", e)))?;
+    let time = |t: &str| humantime::parse_rfc3339_weak(t)
+        .map(|t| t.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()))
+        .map_err(|e| invalid(format!("invalid time `{}`: {}", t, e)));
+    Ok(Query {
+        from: time(&request.range.from)?,
+        to: time(&request.range.to)?,
+        targets: request.targets.into_iter().map(|t| t.target).collect(),
+    })
+}
+
+/// One series of a `/query` answer: `{"target": name, "datapoints": [[value, unix
+/// milliseconds], ...]}`, from `(Unix seconds, value)` points.
+pub fn timeserie(target: &str, points: &[(u64, f64)]) -> Value {
+    let datapoints: Vec<Value> = points.iter().map(|(time, value)| json!([value, time * 1000])).collect();
+    json!({ "target": target, "datapoints": datapoints })
+}
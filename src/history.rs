@@ -0,0 +1,185 @@
+//! A local metrics log: `record` appends samples to a SQLite database and `history`
+//! reads them back. Series use the same names as `alert` rules, plus the network rates.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use rusqlite::{params, Connection};
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
+use crate::alert::{get_readings, Readings};
+use crate::args::MetricGroup;
+use crate::chart::Line;
+use crate::models::{HistorySample, NetworkInfo};
+use crate::watch::{sleep_unless_stopped, NetworkRates};
+
+/// Every series `record` writes, with the group that enables it.
+pub const SERIES: &[(&str, MetricGroup)] = &[
+    ("cpu", MetricGroup::Cpu),
+    ("mem_used_pct", MetricGroup::Memory),
+    ("swap_used_pct", MetricGroup::Memory),
+    ("load1", MetricGroup::Load),
+    ("load5", MetricGroup::Load),
+    ("load15", MetricGroup::Load),
+    ("disk_avail", MetricGroup::Disks),
+    ("disk_used_pct", MetricGroup::Disks),
+    ("net_rx", MetricGroup::Network),
+    ("net_tx", MetricGroup::Network),
+    ("temp", MetricGroup::Temp),
+];
+
+/// `$XDG_DATA_HOME/sysinfo-cli/history.db`, falling back to `~/.local/share`; on
+/// Windows, under `%LOCALAPPDATA%`.
+pub fn default_db_path() -> Option<PathBuf> {
+    let dir = if cfg!(windows) {
+        env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local").join("share")))
+    };
+    dir.map(|dir| dir.join("sysinfo-cli").join("history.db"))
+}
+
+/// Opens (creating if needed) the database at `path`.
+pub fn open(path: &Path) -> rusqlite::Result<Connection> {
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let conn = Connection::open(path)?;
+    init(&conn)?;
+    Ok(conn)
+}
+
+pub fn init(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS samples (
+            time INTEGER NOT NULL,
+            metric TEXT NOT NULL,
+            target TEXT NOT NULL DEFAULT '',
+            value REAL NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS samples_metric_time ON samples (metric, time);"
+    )
+}
+
+/// One row per series and target in `groups`; targets are mount points and interface
+/// names, empty for machine-wide series.
+pub fn rows(readings: &Readings, networks: &[NetworkInfo], groups: &[MetricGroup]) -> Vec<(&'static str, String, f64)> {
+    let mut rows = Vec::new();
+    let mut push = |metric: &'static str, target: &str, value: Option<f64>| {
+        let enabled = SERIES.iter().any(|(m, group)| *m == metric && groups.contains(group));
+        if let (true, Some(value)) = (enabled, value) {
+            rows.push((metric, target.to_string(), value));
+        }
+    };
+    push("cpu", "", Some(readings.cpu));
+    push("mem_used_pct", "", readings.mem_used_pct);
+    push("swap_used_pct", "", readings.swap_used_pct);
+    push("load1", "", readings.load.map(|l| l.0));
+    push("load5", "", readings.load.map(|l| l.1));
+    push("load15", "", readings.load.map(|l| l.2));
+    for (mount, available, total) in &readings.disks {
+        push("disk_avail", mount, Some(*available as f64));
+        push("disk_used_pct", mount, (*total > 0).then(|| (total - available) as f64 / *total as f64 * 100.0));
+    }
+    for network in networks {
+        push("net_rx", &network.interface, network.rx_rate);
+        push("net_tx", &network.interface, network.tx_rate);
+    }
+    push("temp", "", readings.temp);
+    rows
+}
+
+pub fn insert(conn: &mut Connection, time: u64, rows: &[(&str, String, f64)]) -> rusqlite::Result<()> {
+    let tx = conn.transaction()?;
+    {
+        let mut stmt = tx.prepare_cached("INSERT INTO samples (time, metric, target, value) VALUES (?1, ?2, ?3, ?4)")?;
+        for (metric, target, value) in rows {
+            stmt.execute(params![time as i64, metric, target, value])?;
+        }
+    }
+    tx.commit()
+}
+
+/// Samples every `interval` until `stop` is set; returns how many samples were taken.
+pub fn record(conn: &mut Connection, interval: Duration, groups: &[MetricGroup], stop: &AtomicBool) -> rusqlite::Result<usize> {
+    let mut sys = System::new_with_specifics(
+        RefreshKind::nothing()
+            .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+            .with_memory(MemoryRefreshKind::nothing().with_ram().with_swap())
+    );
+    let mut network_rates = NetworkRates::default();
+    network_rates.sample(false);
+    // The first CPU usage and network rates need a baseline.
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    let mut count = 0;
+    while !stop.load(Ordering::SeqCst) {
+        sys.refresh_cpu_usage();
+        sys.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram().with_swap());
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        insert(conn, now, &rows(&get_readings(&sys), &network_rates.sample(false), groups))?;
+        count += 1;
+        sleep_unless_stopped(interval, stop);
+    }
+    Ok(count)
+}
+
+/// Every recorded `(metric, target)` pair, sorted.
+pub fn series(conn: &Connection) -> rusqlite::Result<Vec<(String, String)>> {
+    let mut stmt = conn.prepare("SELECT DISTINCT metric, target FROM samples ORDER BY metric, target")?;
+    let series = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    series.collect()
+}
+
+/// `(time, value)` pairs of one series from `from` to `to` (Unix seconds, inclusive),
+/// oldest first.
+pub fn values(conn: &Connection, metric: &str, target: &str, from: u64, to: u64) -> rusqlite::Result<Vec<(u64, f64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT time, value FROM samples
+         WHERE metric = ?1 AND target = ?2 AND time BETWEEN ?3 AND ?4
+         ORDER BY time"
+    )?;
+    let values = stmt.query_map(params![metric, target, from as i64, to as i64], |row| {
+        let time: i64 = row.get(0)?;
+        Ok((time.max(0) as u64, row.get(1)?))
+    })?;
+    values.collect()
+}
+
+/// Samples of `metric` taken at or after `since` (Unix seconds), oldest first.
+pub fn query(conn: &Connection, metric: &str, target: Option<&str>, since: u64) -> rusqlite::Result<Vec<HistorySample>> {
+    let mut stmt = conn.prepare(
+        "SELECT time, target, value FROM samples
+         WHERE metric = ?1 AND time >= ?2 AND (?3 IS NULL OR target = ?3)
+         ORDER BY time, target"
+    )?;
+    let samples = stmt.query_map(params![metric, since as i64, target], |row| {
+        let time: i64 = row.get(0)?;
+        let target: String = row.get(1)?;
+        Ok(HistorySample {
+            time: humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_secs(time.max(0) as u64)).to_string(),
+            metric: metric.to_string(),
+            target: (!target.is_empty()).then_some(target),
+            value: row.get(2)?,
+        })
+    })?;
+    samples.collect()
+}
+
+/// `samples` as [`crate::chart::svg`] lines, one per mount point or interface in the order they
+/// first appear (one unnamed line for series without targets).
+pub fn chart_lines(samples: &[HistorySample]) -> Vec<Line> {
+    let mut lines: Vec<Line> = Vec::new();
+    for sample in samples {
+        let Some(time) = humantime::parse_rfc3339(&sample.time).ok().and_then(|t| t.duration_since(UNIX_EPOCH).ok()) else { continue };
+        let target = sample.target.as_deref().unwrap_or("");
+        let point = (time.as_secs(), sample.value);
+        match lines.iter_mut().find(|(t, _)| t == target) {
+            Some((_, points)) => points.push(point),
+            None => lines.push((target.to_string(), vec![point])),
+        }
+    }
+    lines
+}
@@ -0,0 +1,53 @@
+//! `--host`: a dashboard of several agents, one row per host, polled side by side so
+//! a slow or unreachable host only holds up its own row.
+
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+use crate::agent;
+use crate::args::AgentAddr;
+use crate::models::{DiskAlert, HostStatus, Snapshot};
+
+/// Disks at least this full are listed as alerts.
+pub const DISK_ALERT_PERCENT: f32 = 90.0;
+/// How long each host has to answer before its row shows an error.
+pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A host's row from its snapshot, or from the error fetching it.
+pub fn host_status(addr: &AgentAddr, snapshot: io::Result<Snapshot>) -> HostStatus {
+    let address = addr.to_string();
+    match snapshot {
+        Ok(snapshot) => {
+            let memory = &snapshot.memory;
+            let mut disk_alerts: Vec<DiskAlert> = snapshot.disks.iter()
+                .filter(|d| d.used_percent >= DISK_ALERT_PERCENT)
+                .map(|d| DiskAlert { mount_point: d.mount_point.clone(), used_percent: d.used_percent })
+                .collect();
+            disk_alerts.sort_by(|a, b| b.used_percent.total_cmp(&a.used_percent));
+            HostStatus {
+                address,
+                host_name: snapshot.system.host_name,
+                error: None,
+                cpu_usage: Some(snapshot.cpu.total_usage),
+                memory_percent: (memory.total_memory > 0).then(|| (memory.used_memory as f64 / memory.total_memory as f64 * 100.0) as f32),
+                load_average: snapshot.system.load_average.map(|load| load.one),
+                disk_alerts,
+            }
+        }
+        Err(e) => HostStatus { address, error: Some(e.to_string()), ..Default::default() },
+    }
+}
+
+/// Every host's row, in the order given, each fetched on its own thread.
+pub fn poll(hosts: &[AgentAddr], token: Option<&str>, tls: Option<Arc<rustls::ClientConfig>>) -> Vec<HostStatus> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = hosts.iter().map(|addr| {
+            let tls = tls.clone();
+            // Processes aren't shown, so don't fetch them.
+            scope.spawn(move || host_status(addr, agent::query(addr, token, tls, Some(0), TIMEOUT)))
+        }).collect();
+        handles.into_iter().zip(hosts).map(|(handle, addr)| {
+            handle.join().unwrap_or_else(|_| host_status(addr, Err(io::Error::other("polling failed"))))
+        }).collect()
+    })
+}
@@ -0,0 +1,118 @@
+use fluent_bundle::{FluentBundle, FluentResource};
+use std::cell::RefCell;
+use unic_langid::LanguageIdentifier;
+use crate::args::Lang;
+
+const EN: &str = include_str!("../locales/en.ftl");
+const DE: &str = include_str!("../locales/de.ftl");
+const FR: &str = include_str!("../locales/fr.ftl");
+const ES: &str = include_str!("../locales/es.ftl");
+
+struct Localizer {
+    lang: Lang,
+    bundle: FluentBundle<FluentResource>,
+    fallback: FluentBundle<FluentResource>,
+    locale_numbers: bool,
+}
+
+thread_local! {
+    static LOCALIZER: RefCell<Localizer> = RefCell::new(Localizer {
+        lang: Lang::En,
+        bundle: build_bundle(Lang::En),
+        fallback: build_bundle(Lang::En),
+        locale_numbers: false,
+    });
+}
+
+fn build_bundle(lang: Lang) -> FluentBundle<FluentResource> {
+    let (id, source) = match lang {
+        Lang::En => ("en-US", EN),
+        Lang::De => ("de-DE", DE),
+        Lang::Fr => ("fr-FR", FR),
+        Lang::Es => ("es-ES", ES),
+    };
+    let langid: LanguageIdentifier = id.parse().expect("valid language identifier");
+    let resource = FluentResource::try_new(source.to_string()).expect("valid fluent resource");
+    let mut bundle = FluentBundle::new(vec![langid]);
+    // Unicode isolation marks break column alignment in terminal tables.
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).expect("no duplicate fluent messages");
+    bundle
+}
+
+/// Switches the language used by `tr` on the current thread.
+pub fn set_lang(lang: Lang) {
+    LOCALIZER.with(|l| {
+        let mut l = l.borrow_mut();
+        l.lang = lang;
+        l.bundle = build_bundle(lang);
+    });
+}
+
+/// Enables thousands separators and the language's decimal mark in `localize_number`.
+pub fn set_locale_numbers(enabled: bool) {
+    LOCALIZER.with(|l| l.borrow_mut().locale_numbers = enabled);
+}
+
+/// Group separator and decimal mark used by each language.
+fn number_marks(lang: Lang) -> (char, char) {
+    match lang {
+        Lang::En => (',', '.'),
+        Lang::De | Lang::Es => ('.', ','),
+        Lang::Fr => ('\u{a0}', ','),
+    }
+}
+
+/// Rewrites a plain number such as "1234567.89" with the active locale's
+/// separators. Anything that isn't a plain number is returned unchanged.
+pub fn localize_number(plain: &str) -> String {
+    let (enabled, lang) = LOCALIZER.with(|l| {
+        let l = l.borrow();
+        (l.locale_numbers, l.lang)
+    });
+    if !enabled {
+        return plain.to_string();
+    }
+
+    let (sign, rest) = match plain.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", plain),
+    };
+    let (int, frac) = match rest.split_once('.') {
+        Some((int, frac)) => (int, Some(frac)),
+        None => (rest, None),
+    };
+    if int.is_empty() || !int.bytes().all(|b| b.is_ascii_digit()) || !frac.is_none_or(|f| f.bytes().all(|b| b.is_ascii_digit())) {
+        return plain.to_string();
+    }
+
+    let (group, decimal) = number_marks(lang);
+    let mut out = String::from(sign);
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.push(group);
+        }
+        out.push(c);
+    }
+    if let Some(frac) = frac {
+        out.push(decimal);
+        out.push_str(frac);
+    }
+    out
+}
+
+fn lookup(bundle: &FluentBundle<FluentResource>, id: &str) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = vec![];
+    Some(bundle.format_pattern(pattern, None, &mut errors).into_owned())
+}
+
+/// Translates a label, falling back to English and then to the message id itself.
+pub fn tr(id: &str) -> String {
+    LOCALIZER.with(|l| {
+        let l = l.borrow();
+        lookup(&l.bundle, id)
+            .or_else(|| lookup(&l.fallback, id))
+            .unwrap_or_else(|| id.to_string())
+    })
+}
@@ -0,0 +1,146 @@
+//! `--query`: the path expressions of jq, such as `.[].available_space`, applied to the
+//! JSON output so scripts on machines without jq can still pick out a value.
+//!
+//! Supported are `.` on its own, `.name` and `."name"`, `.[N]` (negative from the end),
+//! `.["name"]`, and `.[]` for every item of a list or value of an object, chained in any
+//! order, as in `.processes[0].name`. Pipes, filters, and functions aren't.
+
+use std::fmt;
+use std::str::FromStr;
+use serde_json::Value;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Step {
+    Key(String),
+    Index(i64),
+    Iterate,
+}
+
+/// A parsed `--query` expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    expression: String,
+    steps: Vec<Step>,
+}
+
+impl Path {
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// Every value the path leads to in `value`, in order. As in jq, a key an object
+    /// doesn't have, an index past the end of a list, and anything in `null` give
+    /// `null`; indexing a number or string, or a list by name, is an error.
+    pub fn apply(&self, value: &Value) -> Result<Vec<Value>, String> {
+        let mut values = vec![value.clone()];
+        for step in &self.steps {
+            let mut next = Vec::new();
+            for value in values {
+                match (step, value) {
+                    (Step::Iterate, Value::Array(items)) => next.extend(items),
+                    (Step::Iterate, Value::Object(object)) => next.extend(object.into_iter().map(|(_, v)| v)),
+                    (Step::Key(_) | Step::Index(_), Value::Null) => next.push(Value::Null),
+                    (Step::Key(key), Value::Object(mut object)) => next.push(object.remove(key).unwrap_or(Value::Null)),
+                    (Step::Index(index), Value::Array(mut items)) => {
+                        let len = items.len() as i64;
+                        let index = if *index < 0 { index + len } else { *index };
+                        next.push(if (0..len).contains(&index) { items.swap_remove(index as usize) } else { Value::Null });
+                    }
+                    (step, value) => return Err(format!("{}: cannot {} {}", self.expression, describe(step), kind(&value))),
+                }
+            }
+            values = next;
+        }
+        Ok(values)
+    }
+}
+
+fn describe(step: &Step) -> String {
+    match step {
+        Step::Key(key) => format!("look up \"{}\" in", key),
+        Step::Index(index) => format!("take item {} of", index),
+        Step::Iterate => "iterate over".to_string(),
+    }
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "a list",
+        Value::Object(_) => "an object",
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.expression)
+    }
+}
+
+impl FromStr for Path {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, String> {
+        let error = |what: &str| format!("invalid query `{}`: {}", expression, what);
+        let mut rest = expression.trim();
+        if !rest.starts_with('.') {
+            return Err(error("it must start with `.`"));
+        }
+        let mut steps = Vec::new();
+        // `.` alone is the whole value; after it, a name or a bracket may follow directly.
+        if rest == "." {
+            rest = "";
+        }
+        while !rest.is_empty() {
+            if let Some(after) = rest.strip_prefix('[') {
+                let (inside, after) = after.split_once(']').ok_or_else(|| error("unclosed `[`"))?;
+                let inside = inside.trim();
+                steps.push(if inside.is_empty() {
+                    Step::Iterate
+                } else if let Some(key) = quoted(inside) {
+                    Step::Key(key?)
+                } else {
+                    Step::Index(inside.parse().map_err(|_| error(&format!("`[{}]` is not an index or a quoted name", inside)))?)
+                });
+                rest = after;
+            } else if let Some(after) = rest.strip_prefix('.') {
+                if after.starts_with('[') {
+                    rest = after;
+                } else if let Some(name) = after.strip_prefix('"') {
+                    let (key, after) = name.split_once('"').ok_or_else(|| error("unclosed `\"`"))?;
+                    steps.push(Step::Key(key.to_string()));
+                    rest = after;
+                } else {
+                    let end = after.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(after.len());
+                    if end == 0 {
+                        return Err(error("expected a name after `.`"));
+                    }
+                    steps.push(Step::Key(after[..end].to_string()));
+                    rest = &after[end..];
+                }
+            } else {
+                return Err(error(&format!("unexpected `{}`", rest)));
+            }
+        }
+        Ok(Path { expression: expression.trim().to_string(), steps })
+    }
+}
+
+/// The name in `"name"`, or `None` when `text` isn't quoted.
+fn quoted(text: &str) -> Option<Result<String, String>> {
+    let inner = text.strip_prefix('"')?;
+    Some(inner.strip_suffix('"').map(str::to_string).ok_or_else(|| format!("unclosed `\"` in `{}`", text)))
+}
+
+/// The query's results as lines of output: strings as they are, like `jq -r`, and
+/// anything else as JSON, pretty-printed unless `compact`.
+pub fn format_results(values: &[Value], compact: bool) -> String {
+    values.iter().map(|value| match value {
+        Value::String(text) => text.clone(),
+        other if compact => other.to_string(),
+        other => serde_json::to_string_pretty(other).unwrap_or_default(),
+    }).collect::<Vec<_>>().join("\n")
+}
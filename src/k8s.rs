@@ -0,0 +1,95 @@
+//! `k8s-node`: the node's usage in the layout of `kubectl top node`, and the processes
+//! running in pods, matched to their pod and container through their cgroup (Linux).
+
+use std::collections::HashMap;
+use std::fs;
+use sysinfo::System;
+use crate::container::{container_id, parse_cgroup_path};
+use crate::models::{K8sNodeReport, PodProcess};
+
+/// Where a process sits in the kubelet's cgroup tree.
+#[derive(Debug, PartialEq)]
+pub struct PodCgroup {
+    /// The pod's UID, with dashes.
+    pub uid: String,
+    /// The QoS class: `Guaranteed`, `Burstable`, or `BestEffort`.
+    pub qos: &'static str,
+    pub container_id: Option<String>,
+}
+
+/// The pod of a cgroup path, with either driver's naming: `/kubepods/burstable/pod<uid>/<id>`
+/// for cgroupfs, `/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod<uid>.slice/
+/// cri-containerd-<id>.scope` for systemd, which writes the UID with underscores.
+pub fn parse_pod_cgroup(path: &str) -> Option<PodCgroup> {
+    if !path.contains("kubepods") {
+        return None;
+    }
+    let uid = path.split('/').find_map(|segment| {
+        let segment = segment.trim_end_matches(".slice");
+        let uid = &segment[segment.rfind("pod")? + 3..];
+        (uid.len() == 36).then(|| uid.replace('_', "-"))
+    })?;
+    // Guaranteed pods sit straight under kubepods.
+    let qos = if path.contains("besteffort") {
+        "BestEffort"
+    } else if path.contains("burstable") {
+        "Burstable"
+    } else {
+        "Guaranteed"
+    };
+    Some(PodCgroup { uid, qos, container_id: path.rsplit('/').next().and_then(container_id) })
+}
+
+/// The namespace, name, and UID in a `/var/log/pods` directory name, which the kubelet
+/// makes `<namespace>_<name>_<uid>`; neither name can contain an underscore.
+pub fn parse_pod_log_dir(name: &str) -> Option<(String, String, String)> {
+    let mut parts = name.split('_');
+    let (namespace, pod, uid) = (parts.next()?, parts.next()?, parts.next()?);
+    parts.next().is_none().then(|| (namespace.to_string(), pod.to_string(), uid.to_string()))
+}
+
+/// Namespace and name by pod UID, from the kubelet's log directories.
+fn pod_names() -> HashMap<String, (String, String)> {
+    let Ok(entries) = fs::read_dir("/var/log/pods") else {
+        return HashMap::new();
+    };
+    entries.flatten()
+        .filter_map(|entry| parse_pod_log_dir(&entry.file_name().to_string_lossy()))
+        .map(|(namespace, pod, uid)| (uid, (namespace, pod)))
+        .collect()
+}
+
+/// The node's usage and the processes running in pods, sorted by namespace, pod, and
+/// container. Pods whose logs we can't see are named by UID alone.
+pub fn get_node_report(sys: &System) -> K8sNodeReport {
+    let names = pod_names();
+    let mut processes: Vec<PodProcess> = sys.processes().iter().filter_map(|(pid, process)| {
+        let text = fs::read_to_string(format!("/proc/{}/cgroup", pid)).ok()?;
+        let (_, path) = parse_cgroup_path(&text)?;
+        let cgroup = parse_pod_cgroup(&path)?;
+        let (namespace, pod) = names.get(&cgroup.uid).cloned().unzip();
+        Some(PodProcess {
+            namespace,
+            pod,
+            pod_uid: cgroup.uid,
+            qos: cgroup.qos.to_string(),
+            container_id: cgroup.container_id,
+            pid: pid.as_u32(),
+            name: process.name().to_string_lossy().into_owned(),
+            cpu_usage: process.cpu_usage(),
+            memory: process.memory(),
+        })
+    }).collect();
+    processes.sort_by(|a, b| {
+        (&a.namespace, &a.pod, &a.pod_uid, &a.container_id, a.pid).cmp(&(&b.namespace, &b.pod, &b.pod_uid, &b.container_id, b.pid))
+    });
+    let total_memory = sys.total_memory();
+    K8sNodeReport {
+        name: System::host_name().unwrap_or_default(),
+        cpu_cores: f64::from(sys.global_cpu_usage()) * sys.cpus().len() as f64 / 100.0,
+        cpu_percent: sys.global_cpu_usage(),
+        memory_bytes: sys.used_memory(),
+        memory_percent: if total_memory > 0 { (sys.used_memory() as f64 / total_memory as f64 * 100.0) as f32 } else { 0.0 },
+        processes,
+    }
+}
@@ -0,0 +1,93 @@
+//! System-wide kernel resource usage from procfs (Linux): file handles, sockets,
+//! and epoll/inotify instances. Other platforms report nothing.
+
+use std::collections::BTreeMap;
+use std::fs;
+use crate::models::{KernelResources, SocketCount};
+
+/// TCP states as numbered in `/proc/net/tcp` (`include/net/tcp_states.h`).
+const TCP_STATES: [&str; 11] = [
+    "established", "syn_sent", "syn_recv", "fin_wait1", "fin_wait2", "time_wait",
+    "close", "close_wait", "last_ack", "listen", "closing",
+];
+
+pub fn get_kernel_resources() -> KernelResources {
+    let read = |path: &str| fs::read_to_string(path).ok();
+    let number = |path: &str| read(path).and_then(|s| s.trim().parse().ok());
+    let file_nr = read("/proc/sys/fs/file-nr").and_then(|s| parse_file_nr(&s));
+
+    let mut sockets = Vec::new();
+    for protocol in ["tcp", "tcp6", "udp", "udp6"] {
+        if let Some(text) = read(&format!("/proc/net/{}", protocol)) {
+            sockets.extend(count_socket_states(&text, protocol));
+        }
+    }
+    if let Some(text) = read("/proc/net/unix") {
+        sockets.push(SocketCount { protocol: "unix".to_string(), state: "all".to_string(), count: text.lines().skip(1).count() as u64 });
+    }
+    let (epoll, inotify) = count_anon_inodes();
+
+    KernelResources {
+        file_handles_allocated: file_nr.map(|(allocated, _)| allocated),
+        file_handles_max: file_nr.map(|(_, max)| max),
+        sockets,
+        epoll_instances: epoll,
+        inotify_instances: inotify,
+        inotify_max_user_instances: number("/proc/sys/fs/inotify/max_user_instances"),
+        inotify_max_user_watches: number("/proc/sys/fs/inotify/max_user_watches"),
+        epoll_max_user_watches: number("/proc/sys/fs/epoll/max_user_watches"),
+    }
+}
+
+/// Parses `/proc/sys/fs/file-nr` ("allocated unused max") into `(in use, max)`.
+pub fn parse_file_nr(text: &str) -> Option<(u64, u64)> {
+    let fields: Vec<u64> = text.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+    match fields[..] {
+        [allocated, unused, max] => Some((allocated.saturating_sub(unused), max)),
+        _ => None,
+    }
+}
+
+/// Counts the sockets in a `/proc/net/{tcp,udp}[6]` table by state.
+pub fn count_socket_states(text: &str, protocol: &str) -> Vec<SocketCount> {
+    let mut counts: BTreeMap<&str, u64> = BTreeMap::new();
+    for line in text.lines().skip(1) {
+        let Some(st) = line.split_whitespace().nth(3) else { continue };
+        let Ok(code) = u8::from_str_radix(st, 16) else { continue };
+        let state = TCP_STATES.get(usize::from(code).wrapping_sub(1)).copied().unwrap_or("unknown");
+        // UDP reuses the TCP numbering: 1 is connected, 7 is unconnected.
+        let state = match (protocol.starts_with("udp"), state) {
+            (true, "close") => "unconnected",
+            (_, state) => state,
+        };
+        *counts.entry(state).or_default() += 1;
+    }
+    counts.into_iter().map(|(state, count)| SocketCount {
+        protocol: protocol.to_string(),
+        state: state.to_string(),
+        count,
+    }).collect()
+}
+
+/// Counts epoll and inotify instances by walking every readable `/proc/<pid>/fd`.
+/// Processes we may not inspect are left out, so run as root for a full count.
+fn count_anon_inodes() -> (Option<u64>, Option<u64>) {
+    let Ok(procs) = fs::read_dir("/proc") else {
+        return (None, None);
+    };
+    let (mut epoll, mut inotify) = (0, 0);
+    for proc in procs.flatten() {
+        if !proc.file_name().to_string_lossy().bytes().all(|b| b.is_ascii_digit()) {
+            continue;
+        }
+        let Ok(fds) = fs::read_dir(proc.path().join("fd")) else { continue };
+        for fd in fds.flatten() {
+            match fs::read_link(fd.path()) {
+                Ok(target) if target.as_os_str() == "anon_inode:[eventpoll]" => epoll += 1,
+                Ok(target) if target.as_os_str() == "anon_inode:inotify" => inotify += 1,
+                _ => {}
+            }
+        }
+    }
+    (Some(epoll), Some(inotify))
+}
@@ -0,0 +1,49 @@
+use sysinfo::{Signal, System};
+use crate::models::{KillOutcome, KillResult};
+
+/// Signals accepted by `kill --signal`, by their name without the `SIG` prefix.
+pub const SIGNALS: &[(&str, Signal)] = &[
+    ("HUP", Signal::Hangup),
+    ("INT", Signal::Interrupt),
+    ("QUIT", Signal::Quit),
+    ("KILL", Signal::Kill),
+    ("USR1", Signal::User1),
+    ("USR2", Signal::User2),
+    ("TERM", Signal::Term),
+    ("CONT", Signal::Continue),
+    ("STOP", Signal::Stop),
+];
+
+pub fn signal_name(signal: Signal) -> &'static str {
+    SIGNALS.iter().find(|(_, s)| *s == signal).map_or("?", |(name, _)| name)
+}
+
+/// Processes with the given PID or exactly the given name, lowest PID first. This
+/// process is never a target.
+pub fn find_targets(sys: &System, pid: Option<u32>, name: Option<&str>, signal: Signal) -> Vec<KillResult> {
+    let own = sysinfo::get_current_pid().ok();
+    let mut targets: Vec<KillResult> = sys.processes().values()
+        .filter(|p| Some(p.pid()) != own)
+        .filter(|p| pid.is_some_and(|pid| p.pid().as_u32() == pid) || name.is_some_and(|name| p.name() == name))
+        .map(|p| KillResult {
+            pid: p.pid().as_u32(),
+            name: p.name().to_string_lossy().into_owned(),
+            signal: signal_name(signal).to_string(),
+            outcome: KillOutcome::Matched,
+        })
+        .collect();
+    targets.sort_by_key(|t| t.pid);
+    targets
+}
+
+/// Sends `signal` to every target and records whether it was delivered.
+pub fn send(sys: &System, targets: &mut [KillResult], signal: Signal) {
+    for target in targets {
+        target.outcome = match sys.process(sysinfo::Pid::from_u32(target.pid)).map(|p| p.kill_with(signal)) {
+            Some(Some(true)) => KillOutcome::Sent,
+            Some(None) => KillOutcome::Unsupported,
+            // Gone since the refresh, or not ours to signal.
+            _ => KillOutcome::Failed,
+        };
+    }
+}
@@ -0,0 +1,116 @@
+use comfy_table::presets::ASCII_MARKDOWN;
+use comfy_table::{CellAlignment, ColumnConstraint, ContentArrangement, Table, Width};
+use serde::Deserialize;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Table layout overrides, loaded from a TOML file such as:
+///
+/// ```toml
+/// max_width = 77
+///
+/// [processes.name]
+/// max_width = 20
+/// header = "Command"
+///
+/// [disks.available]
+/// align = "right"
+///
+/// [columns]
+/// processes = ["pid", "name", "cpu"]
+/// ```
+#[derive(Deserialize, Debug, Default, Clone)]
+pub struct Layout {
+    /// Maximum total table width; cells wrap to fit.
+    pub max_width: Option<u16>,
+    /// Columns to show per table id; the table's other columns are hidden.
+    #[serde(default)]
+    pub columns: HashMap<String, Vec<String>>,
+    /// Per-table column overrides, keyed by table id and then column id.
+    #[serde(flatten)]
+    pub tables: HashMap<String, HashMap<String, ColumnLayout>>,
+}
+
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnLayout {
+    pub max_width: Option<u16>,
+    pub align: Option<Align>,
+    pub header: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+thread_local! {
+    static LAYOUT: RefCell<Layout> = RefCell::new(Layout::default());
+    static MARKDOWN: Cell<bool> = const { Cell::new(false) };
+}
+
+pub fn load_layout(path: &Path) -> Result<Layout, String> {
+    let source = fs::read_to_string(path).map_err(|e| e.to_string())?;
+    toml::from_str(&source).map_err(|e| e.to_string())
+}
+
+/// Sets the layout used by `new_table` on the current thread.
+pub fn set_layout(layout: Layout) {
+    LAYOUT.with(|l| *l.borrow_mut() = layout);
+}
+
+/// Makes `new_table` draw GitHub-flavored markdown tables on the current thread.
+pub fn set_markdown(markdown: bool) {
+    MARKDOWN.with(|m| m.set(markdown));
+}
+
+/// Creates a table with one header per `(column id, default header)` pair, applying
+/// any header renames, width limits, and alignment configured for `table`.
+pub fn new_table(table: &str, columns: Vec<(&str, String)>) -> Table {
+    LAYOUT.with(|l| {
+        let layout = l.borrow();
+        let overrides = layout.tables.get(table);
+        let column = |id: &str| overrides.and_then(|o| o.get(id));
+
+        let markdown = MARKDOWN.with(Cell::get);
+        let mut t = Table::new();
+        if markdown {
+            t.load_preset(ASCII_MARKDOWN);
+        }
+        // A wrapped cell spans several lines, which markdown tables can't hold.
+        if let Some(width) = layout.max_width && !markdown {
+            t.set_content_arrangement(ContentArrangement::Dynamic);
+            t.set_width(width);
+        }
+        t.set_header(columns.iter().map(|(id, header)| {
+            column(id).and_then(|c| c.header.clone()).unwrap_or_else(|| header.clone())
+        }).collect::<Vec<_>>());
+
+        let shown = layout.columns.get(table);
+        for (i, (id, _)) in columns.iter().enumerate() {
+            if let Some(col) = t.column_mut(i) && shown.is_some_and(|shown| !shown.iter().any(|s| s == id)) {
+                col.set_constraint(ColumnConstraint::Hidden);
+                continue;
+            }
+            let (Some(c), Some(col)) = (column(id), t.column_mut(i)) else { continue };
+            if let Some(width) = c.max_width && !markdown {
+                // comfy-table counts cell padding towards the limit; ours is content only.
+                let width = width.saturating_add(col.padding_width());
+                col.set_constraint(ColumnConstraint::UpperBoundary(Width::Fixed(width)));
+            }
+            if let Some(align) = c.align {
+                col.set_cell_alignment(match align {
+                    Align::Left => CellAlignment::Left,
+                    Align::Center => CellAlignment::Center,
+                    Align::Right => CellAlignment::Right,
+                });
+            }
+        }
+        t
+    })
+}
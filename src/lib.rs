@@ -0,0 +1,118 @@
+//! The collection and formatting behind the `sysinfo-cli` binary, for embedding in
+//! other programs without shelling out.
+//!
+//! [`collector`] reads the system into the plain structs in [`models`], which
+//! serialize with serde, and [`fmt`] renders them the way the CLI does:
+//!
+//! ```no_run
+//! use sysinfo_cli::args::OutputFormat;
+//! use sysinfo_cli::collector::{get_memory_info, init_system};
+//! use sysinfo_cli::fmt::{format_memory_info, render};
+//!
+//! let sys = init_system(&None, None);
+//! let memory = get_memory_info(&sys);
+//! println!("{} of {} bytes used", memory.used_memory, memory.total_memory);
+//! println!("{}", render(OutputFormat::Json, ',', &memory, &[&memory], format_memory_info)?);
+//! # Ok::<(), sysinfo_cli::error::Error>(())
+//! ```
+//!
+//! The functions take a [`sysinfo::System`]; [`sysinfo`] is re-exported so it's the
+//! same version. Modules marked hidden serve the binary's subcommands and may change
+//! in any release.
+
+pub use sysinfo;
+
+pub mod args;
+pub mod collector;
+pub mod error;
+pub mod fmt;
+pub mod i18n;
+pub mod models;
+pub mod prometheus;
+pub mod schema;
+pub mod watch;
+
+#[doc(hidden)]
+pub mod agent;
+#[doc(hidden)]
+pub mod alert;
+#[doc(hidden)]
+pub mod analyze;
+#[doc(hidden)]
+pub mod bench;
+#[doc(hidden)]
+pub mod bundle;
+#[doc(hidden)]
+pub mod capabilities;
+#[doc(hidden)]
+pub mod chart;
+#[doc(hidden)]
+pub mod config;
+#[doc(hidden)]
+pub mod connections;
+#[doc(hidden)]
+pub mod container;
+#[doc(hidden)]
+pub mod daemon;
+#[doc(hidden)]
+pub mod docker;
+#[doc(hidden)]
+pub mod examples;
+#[doc(hidden)]
+pub mod fetch;
+#[doc(hidden)]
+pub mod grafana;
+#[doc(hidden)]
+pub mod history;
+#[doc(hidden)]
+pub mod hosts;
+#[doc(hidden)]
+pub mod jq;
+#[doc(hidden)]
+pub mod k8s;
+#[doc(hidden)]
+pub mod kernel;
+#[doc(hidden)]
+pub mod kill;
+#[doc(hidden)]
+pub mod layout;
+#[doc(hidden)]
+pub mod mqtt;
+#[doc(hidden)]
+pub mod netns;
+#[doc(hidden)]
+pub mod otlp;
+#[doc(hidden)]
+pub mod output;
+#[doc(hidden)]
+pub mod push;
+#[doc(hidden)]
+pub mod redact;
+#[doc(hidden)]
+pub mod sensors;
+#[doc(hidden)]
+pub mod serve;
+#[doc(hidden)]
+pub mod sink;
+#[doc(hidden)]
+pub mod snapshot;
+#[doc(hidden)]
+pub mod status;
+#[doc(hidden)]
+pub mod statusbar;
+#[doc(hidden)]
+pub mod threads;
+#[doc(hidden)]
+pub mod tui;
+#[doc(hidden)]
+pub mod users;
+#[doc(hidden)]
+pub mod whoholds;
+mod cpufreq;
+mod cpustat;
+mod power;
+mod sbc;
+mod winperf;
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod tests;
@@ -1,130 +1,877 @@
-mod args;
-mod models;
-mod collector;
-mod fmt;
-#[cfg(test)]
-mod tests;
-
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches};
 use colored::*;
-use std::thread;
-use std::time::Duration;
-use std::fs::File;
 use std::io::Write;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
-use crate::args::{Cli, Commands};
-use crate::collector::*;
-use crate::fmt::*;
+use sysinfo_cli::args::{parse_schedule, parse_sink, Cli, ColorMode, Commands, CpuMode, DisksAction, HistoryAction, MetricGroup, OutputFormat, SinkUrl, SortBy};
+use clap::ValueEnum;
+use sysinfo_cli::{agent, alert, analyze, bench, bundle, capabilities, chart, config, connections, container, daemon, docker, examples, fetch, history, hosts, i18n, k8s, kernel, kill, layout, netns, output, prometheus, push, schema, sensors, serve, sink, snapshot, status, statusbar, tui, users, whoholds};
+use sysinfo_cli::collector::*;
+use sysinfo_cli::error::{Error, Result};
+use sysinfo_cli::fmt::*;
+use sysinfo_cli::i18n::{localize_number, tr};
+use sysinfo_cli::models::UsageLevel;
+use sysinfo_cli::redact::{redact, Redactor};
+use sysinfo_cli::threads::ThreadSampler;
+use sysinfo_cli::watch::{sleep_unless_stopped, CollectionProfiler, CpuHistory, CpuTimeSampler, DiskRates, HardwareWatcher, NetworkRates, ProcessChanges, ProcessFollower, ProcessIoRates, RunStats, SelfStatsTracker, ThrottleTracker};
+
+fn main() -> ExitCode {
+    let matches = examples::add_to_help(Cli::command()).try_get_matches().unwrap_or_else(|e| usage_error(e));
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| usage_error(e));
+    match run(&mut cli, &matches) {
+        Ok(()) => ExitCode::SUCCESS,
+        // The reader going away, as with `| head`, isn't a failure.
+        Err(e) if e.is_broken_pipe() => ExitCode::SUCCESS,
+        Err(e) => {
+            if let Some(report) = e.report(cli.json) {
+                eprintln!("{}", report);
+            }
+            ExitCode::from(e.status() as u8)
+        }
+    }
+}
+
+/// Runs the command in `cli`, applying the config file to the options `matches` says
+/// weren't given.
+fn run(cli: &mut Cli, matches: &clap::ArgMatches) -> Result<()> {
+    let config = config::load_config(cli.config.as_deref()).map_err(|e| Error::Failed(format!("reading config file {}", e)))?;
+    config::apply(&config, cli, matches);
+    let watch = cli.watch.map(|interval| interval.unwrap_or(Duration::from_secs(1)));
+    if cli.query.is_some() && cli.format == OutputFormat::Text {
+        cli.format = OutputFormat::Json;
+    }
+    if cli.format == OutputFormat::Json {
+        cli.json = true;
+    } else if cli.json {
+        cli.format = OutputFormat::Json;
+    }
+    let cli = &*cli;
+    if cli.format == OutputFormat::Csv && !supports_csv(&cli.command) {
+        return Err(Error::Usage(format!("CSV output is not available for {}", join_names(NON_CSV_COMMANDS))));
+    }
+    if cli.format == OutputFormat::Prometheus && !supports_prometheus(&cli.command) {
+        return Err(Error::Usage(format!("Prometheus output is only available for the {}", join_names(PROMETHEUS_COMMANDS))));
+    }
+    if !cli.fields.is_empty() {
+        if !matches!(cli.format, OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv) {
+            return Err(Error::Usage("--fields is only available for JSON, YAML, and CSV output".to_string()));
+        }
+        set_fields(cli.fields.clone());
+    }
+    if cli.query.is_some() {
+        if cli.format != OutputFormat::Json {
+            return Err(Error::Usage("--query works on JSON output; it can't be combined with another --format".to_string()));
+        }
+        set_query(cli.query.clone());
+    }
+    // CPU usage over less than sysinfo's minimum interval is noise.
+    let minimum = sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+    let watch = watch.map(|interval| {
+        if interval < minimum && samples_cpu_usage(&cli.command) {
+            eprintln!("Refreshing every {} instead, the shortest interval CPU usage can be measured over", humantime::format_duration(minimum));
+            minimum
+        } else {
+            interval
+        }
+    });
+    i18n::set_lang(cli.lang);
+    i18n::set_locale_numbers(cli.locale_numbers);
+    set_units(cli.units, cli.si);
+    let color = if cli.no_color { ColorMode::Never } else { cli.color };
+    let clicolor_force = std::env::var("CLICOLOR_FORCE").is_ok_and(|v| !v.is_empty() && v != "0");
+    if let Some(color) = color.color_override(cli.output.is_some() && !cli.tee, clicolor_force) {
+        colored::control::set_override(color);
+    }
+    if let Some(path) = &cli.layout {
+        let l = layout::load_layout(path).map_err(|e| Error::Failed(format!("reading layout file {}: {}", path.display(), e)))?;
+        layout::set_layout(l);
+    } else if let Some(l) = &config.layout {
+        layout::set_layout(l.clone());
+    }
+
+    let sink_url = cli.sink.clone().or_else(|| cli.otlp_endpoint.clone().map(SinkUrl::Otlp));
+    if let Some(url) = &sink_url {
+        let sink = connect_sink(url, cli)?;
+        sink::set_sink(Some(sink), cli.command.as_ref().map_or("summary", Commands::name));
+    }
+
+    let mut cpu_history = match &cli.command {
+        Some(Commands::Cpu { history, .. }) => Some(CpuHistory::new(*history)),
+        _ => None,
+    };
+    let alert_rules = match &cli.command {
+        Some(Commands::Alert { rules, rules_file }) => {
+            // Rules on the command line replace those from the config file.
+            let rules = if rules.is_empty() && rules_file.is_none() { &config.alert.rules } else { rules };
+            let mut parsed: Result<Vec<_>, String> = rules.iter().map(|r| alert::parse_rule(r)).collect();
+            if let (Ok(parsed), Some(path)) = (&mut parsed, rules_file) {
+                parsed.extend(alert::load_rules(path).map_err(|e| Error::Unavailable(format!("reading alert rules: {}", e)))?);
+            }
+            let parsed = parsed.map_err(Error::Unavailable)?;
+            if parsed.is_empty() {
+                return Err(Error::Unavailable("no alert rules given on the command line, with --rules-file, or in the config file".to_string()));
+            }
+            parsed
+        }
+        _ => Vec::new(),
+    };
+    let mut process_filter = match &cli.command {
+        Some(Commands::Processes { filter, regex, ignore_case, cmdline, state, min_cpu, min_mem, .. }) => {
+            let pattern = filter.as_ref().map(|pattern| ProcessFilter::new(pattern, *regex, *ignore_case, *cmdline));
+            let mut process_filter = pattern.transpose()
+                .map_err(|e| Error::Usage(format!("invalid --filter pattern: {}", e)))?
+                .unwrap_or_default();
+            process_filter.states = state.clone();
+            process_filter.min_cpu = *min_cpu;
+            process_filter.min_memory = *min_mem;
+            Some(process_filter)
+        }
+        _ => None,
+    };
+    let mut output = output::Output::new(cli.output.as_deref(), cli.append, cli.max_size, cli.rotate, cli.compress, cli.tee, cli.json);
+    let mut disk_rates = DiskRates::default();
+    let mut network_rates = NetworkRates::default();
+    let mut process_io_rates = ProcessIoRates::default();
+    // What the previous watch iteration showed, for --highlight.
+    let highlight = cli.highlight && watch.is_some();
+    let mut previous_cpu = None;
+    let mut previous_memory = None;
+    let mut process_changes = highlight.then(ProcessChanges::default);
+    let mut redactor = cli.redact.then(Redactor::new);
+    let follow = matches!(cli.command, Some(Commands::Processes { follow: true, .. }));
+    let mut process_follower = follow.then(ProcessFollower::default);
+    let mut hardware_watcher = HardwareWatcher::default();
+    let hide_idle = cli.hide_idle && !cli.show_all;
+    let mut self_stats = SelfStatsTracker::default();
+    // Event streams poll until Ctrl-C and append instead of redrawing.
+    let streaming = follow || cli.command == Some(Commands::Events);
+    // A status bar reads a line per refresh, so those are appended too.
+    let statusbar = matches!(cli.command, Some(Commands::Statusbar { .. }));
+    let mut pusher = match &cli.command {
+        Some(Commands::Push { url, token, batch, retries }) => {
+            let token = token.clone().or_else(|| std::env::var("INFLUX_TOKEN").ok().filter(|t| !t.is_empty()));
+            Some(push::Pusher::new(url, token, *batch, *retries))
+        }
+        _ => None,
+    };
+    let mut pushed = 0;
+    let mut throttle_tracker = match &cli.command {
+        Some(Commands::Thermal { margin, drop, .. }) => Some(ThrottleTracker::new(*margin, *drop)),
+        _ => None,
+    };
+
+    // Kept for the whole run: on Linux the copied text is only served while it exists.
+    let mut clipboard = if cli.copy {
+        arboard::Clipboard::new().map_err(|e| eprintln!("Error opening clipboard: {}", e)).ok()
+    } else {
+        None
+    };
+    // For failures that shouldn't cut a run short; checked once it ends.
+    let mut exit_status = if cli.copy && clipboard.is_none() { status::ERROR } else { 0 };
+
+    // Ctrl-C ends a watch session gracefully so end-of-run summaries can be printed.
+    let stop = Arc::new(AtomicBool::new(false));
+    let recording = matches!(cli.command, Some(Commands::Record { .. }) | Some(Commands::Daemon { .. }));
+    if watch.is_some() || streaming || recording {
+        let stop = stop.clone();
+        if let Err(e) = ctrlc::set_handler(move || stop.store(true, Ordering::SeqCst)) {
+            eprintln!("Error installing Ctrl-C handler: {}", e);
+        }
+    }
+
+    // Read before the first refresh so the breakdown spans the same time as the usage.
+    let mut container_cpu = container::CpuSampler::default();
+    if cli.command == Some(Commands::Container) {
+        container_cpu.sample();
+    }
+    let mut cpu_times = CpuTimeSampler::default();
+    if let Some(Commands::Cpu { detailed: true, .. }) = &cli.command {
+        if !cfg!(target_os = "linux") {
+            return Err(Error::Unavailable("cpu --detailed is only available on Linux".to_string()));
+        }
+        cpu_times.start();
+    }
+    // Read before the first refresh too, so thread CPU usage spans the same sample.
+    let mut thread_sampler = ThreadSampler::default();
+    if let Some(Commands::Process { pid, threads: true }) = &cli.command {
+        if !cfg!(target_os = "linux") {
+            return Err(Error::Unavailable("process --threads is only available on Linux".to_string()));
+        }
+        thread_sampler.start(*pid);
+    }
+
+    // With --host, the agents do the sampling.
+    let remote = !cli.host.is_empty();
+    if remote && cli.command.is_some() {
+        return Err(Error::Usage("--host shows a row per agent in place of the summary; use `query` for one agent's details".to_string()));
+    }
+    if cli.profile_collection && (remote || cli.command.is_some()) {
+        return Err(Error::Usage("--profile-collection times every stage in place of a report; leave out the subcommand and --host".to_string()));
+    }
+    let mut profiler: Option<CollectionProfiler> = None;
+    let host_token = cli.host_token.clone().or_else(|| std::env::var("SYSINFO_AGENT_TOKEN").ok().filter(|t| !t.is_empty()));
+    let host_tls = match &cli.host_tls_ca {
+        Some(ca) => Some(agent::client_tls(Some(ca)).map_err(|e| Error::io("loading the TLS certificates", e))?),
+        None => None,
+    };
+
+    // --count and --duration end the run themselves; however it ends, a watch run gets a
+    // summary. Event streams aren't samples of usage.
+    let deadline = cli.duration.map(|duration| Instant::now() + duration);
+    let mut samples = 0;
+
+    // Created once and refreshed in place, so CPU usage in watch mode spans the interval.
+    let started = Instant::now();
+    let (mut sys, mut prefetched) = if remote || cli.profile_collection {
+        (sysinfo::System::new(), None)
+    } else {
+        init_system_with(&cli.command, cli.sample, || prefetch(&cli.command, hide_idle))
+    };
+    let mut refresh_time = started.elapsed();
+    let mut run_stats = (watch.is_some() && !streaming && !statusbar).then(|| {
+        // With --host and --profile-collection the loop leaves `sys` alone.
+        let kind = if remote || cli.profile_collection { sysinfo::RefreshKind::nothing() } else { refresh_kind(&cli.command) };
+        RunStats::start(kind, &mut sys, &mut network_rates)
+    });
+    // --min-cpu is in the units shown; the filter compares sysinfo's per-core figures.
+    if let (Some(filter), Some(Commands::Processes { cpu_mode: CpuMode::Total, .. })) = (process_filter.as_mut(), &cli.command) {
+        filter.min_cpu = filter.min_cpu.map(|min| min * sys.cpus().len().max(1) as f32);
+    }
 
-fn main() {
-    let cli = Cli::parse();
-    
     loop {
-        let sys = init_system(&cli.command);
+        self_stats.record("refresh", refresh_time);
         let mut output_str = String::new();
-        
+        // Event streams carry their own timestamps.
+        if cli.timestamp && !streaming {
+            set_timestamp(Some(humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()));
+        }
+
+        let started = Instant::now();
         match &cli.command {
             Some(Commands::System) => {
-                let info = get_system_info();
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
-                } else {
-                    output_str.push_str(&format_system_info(&info));
+                let mut info = get_system_info();
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_system_info)?);
+            }
+            Some(Commands::Cpu { topology, detailed, .. }) => {
+                let mut info = get_cpu_info(&sys);
+                if *topology {
+                    add_cpu_topology(&mut info);
+                }
+                if *detailed {
+                    cpu_times.record(&mut info);
+                }
+                redact(&mut info, redactor.as_mut());
+                if let (Some(history), Some(_)) = (cpu_history.as_mut(), watch) {
+                    history.record(&mut info);
+                }
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info.cpus, |i| format_cpu_info_since(i, previous_cpu.as_ref()))?);
+                if highlight {
+                    previous_cpu = Some(info);
                 }
             }
-            Some(Commands::Cpu) => {
-                let info = get_cpu_info(&sys);
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
-                } else {
-                    output_str.push_str(&format_cpu_info(&info));
+            Some(Commands::Uptime { seconds: true }) if cli.format == OutputFormat::Text => {
+                output_str.push_str(&get_uptime_info().uptime.to_string());
+            }
+            Some(Commands::Uptime { .. }) => {
+                let info = get_uptime_info();
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_uptime_info)?);
+            }
+            Some(Commands::Alert { .. }) => {
+                let mut results = alert::evaluate(&alert_rules, &alert::get_readings(&sys));
+                redact(&mut results, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &results, &results, |r| format_alert_results(r))?);
+                let code = alert::exit_code(&results);
+                if code != 0 && watch.is_none() {
+                    output.emit(&output_str, false)?;
+                    return Err(Error::Exit(code));
+                }
+            }
+            Some(Commands::Statusbar { text, tooltip, percentage, warning, critical }) => {
+                let line = statusbar::line(text, tooltip, percentage, warning, critical, &alert::get_readings(&sys));
+                output_str.push_str(&render_event(cli.format, &line, format_statusbar)?);
+            }
+            Some(Commands::Load { threshold }) => {
+                let info = get_load_report(&sys, *threshold)
+                    .ok_or_else(|| Error::Unavailable("load averages are not available on this platform".to_string()))?;
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_load_report)?);
+                if info.exceeded == Some(true) && watch.is_none() {
+                    output.emit(&output_str, false)?;
+                    return Err(Error::Exit(status::THRESHOLD));
                 }
             }
             Some(Commands::Memory) => {
-                let info = get_memory_info(&sys);
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
-                } else {
-                    output_str.push_str(&format_memory_info(&info));
+                let mut info = get_memory_info(&sys);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], |i| format_memory_info_since(i, previous_memory.as_ref()))?);
+                if highlight {
+                    previous_memory = Some(info);
                 }
             }
-            Some(Commands::Disks) => {
-                let info = get_disks_info();
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
+            Some(Commands::Disks { action: Some(DisksAction::Analyze { mount, depth, top }), .. }) => {
+                let mut info = analyze::analyze(mount, *depth, *top).map_err(|e| Error::io(format!("analyzing {}", mount.display()), e))?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info.directories, format_disk_usage)?);
+            }
+            Some(Commands::Disks { io, warn_pct, crit_pct, mount, fs, exclude, action: None }) => {
+                let mut info = if watch.is_some() {
+                    disk_rates.sample(*io)
                 } else {
-                    output_str.push_str(&format_disks_info(&info));
+                    get_disks_info(*io)
+                };
+                let filter = DiskFilter { mounts: mount.clone(), file_systems: fs.clone(), exclude: exclude.clone() };
+                info.retain(|disk| filter.matches(disk));
+                let level = check_disk_usage(&mut info, *warn_pct, *crit_pct);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_disks_info(i))?);
+                if level.is_some_and(|level| level > UsageLevel::Ok) && watch.is_none() {
+                    output.emit(&output_str, false)?;
+                    return Err(Error::Exit(status::THRESHOLD));
                 }
             }
-            Some(Commands::Network) => {
-                let info = get_network_info();
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
+            Some(Commands::Network { all_namespaces, netns, interface, delta }) => {
+                let mut info = if *all_namespaces || netns.is_some() {
+                    netns::get_namespaced_network_info(netns.as_deref()).map_err(|e| Error::io("reading network namespaces", e))?
+                } else if watch.is_some() {
+                    network_rates.sample(*delta)
+                } else if *delta {
+                    // A single run measures its deltas over one second.
+                    network_rates.sample(true);
+                    std::thread::sleep(Duration::from_secs(1));
+                    network_rates.sample(true)
                 } else {
-                    output_str.push_str(&format_network_info(&info));
+                    get_network_info()
+                };
+                if !interface.is_empty() {
+                    info.retain(|net| interface.contains(&net.interface));
+                }
+                retain_active(&mut info, hide_idle);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_network_info(i))?);
+            }
+            Some(Commands::Components { warn, crit }) => {
+                let mut info = get_components_info();
+                if info.is_empty() && watch.is_none() {
+                    exit_status = status::UNAVAILABLE;
+                }
+                let level = check_temperatures(&mut info, *warn, *crit);
+                retain_active(&mut info, hide_idle);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_components_info(i))?);
+                if level.is_some_and(|level| level > UsageLevel::Ok) && watch.is_none() {
+                    output.emit(&output_str, false)?;
+                    return Err(Error::Exit(status::THRESHOLD));
+                }
+            }
+            Some(Commands::Container) => {
+                let mut info = container::get_container_info(container_cpu.sample(), sys.cpus().len(), sys.total_memory());
+                if info.cgroup_version.is_none() && watch.is_none() {
+                    exit_status = status::UNAVAILABLE;
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_container_info)?);
+            }
+            Some(Commands::Sensors) => {
+                let mut info = sensors::get_sensor_readings().map_err(|e| Error::io("reading sensors", e))?;
+                if info.is_empty() && watch.is_none() {
+                    exit_status = status::UNAVAILABLE;
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_sensor_readings(i))?);
+            }
+            Some(Commands::Resources) => {
+                let info = kernel::get_kernel_resources();
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info.sockets, format_kernel_resources)?);
+            }
+            Some(Commands::Fetch { no_logo }) => {
+                let mut info = fetch::get_fetch_info(&sys);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], |i| format_fetch_info(i, !no_logo))?);
+            }
+            Some(Commands::Capabilities) => {
+                let info = capabilities::get_capabilities();
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_capabilities(i))?);
+            }
+            Some(Commands::Events) => {
+                let timestamp = humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string();
+                for mut event in hardware_watcher.record(get_hardware_snapshot(), &timestamp) {
+                    redact(&mut event, redactor.as_mut());
+                    output_str.push_str(&render_event(cli.format, &event, format_hardware_event)?);
+                    output_str.push('\n');
+                }
+            }
+            Some(Commands::Processes { follow: true, .. }) => {
+                let timestamp = humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string();
+                let follower = process_follower.as_mut().expect("created for --follow");
+                for mut event in follower.record(get_seen_processes(&sys, process_filter.as_ref()), &timestamp) {
+                    redact(&mut event, redactor.as_mut());
+                    output_str.push_str(&render_event(cli.format, &event, format_process_event)?);
+                    output_str.push('\n');
                 }
             }
-            Some(Commands::Components) => {
-                let info = get_components_info();
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
+            Some(Commands::Processes { limit, sort, asc, desc, columns, io, cpu_mode, .. }) => {
+                let disk_sort = sort.iter().any(|s| matches!(s, SortBy::DiskRead | SortBy::DiskWrite));
+                let descending = (*asc || *desc).then_some(*desc);
+                let mut info = get_processes_info(&sys, process_filter.as_ref(), *limit, sort, descending, hide_idle, ProcessExtras::for_table(cli.format, columns, *io || disk_sort));
+                if *cpu_mode == CpuMode::Total {
+                    normalize_cpu_usage(&mut info, sys.cpus().len());
+                }
+                if *io && watch.is_some() {
+                    process_io_rates.record(&mut info, Instant::now());
+                }
+                if let Some(changes) = process_changes.as_mut() {
+                    let timestamp = humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string();
+                    changes.record(get_seen_processes(&sys, process_filter.as_ref()), &timestamp);
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_process_table(i, columns, process_changes.as_ref(), Some((*cpu_mode, sys.cpus().len()))))?);
+                if let Some(changes) = process_changes.as_mut() {
+                    changes.remember(&info);
+                }
+            }
+            Some(Commands::Users { sessions: true }) => {
+                let mut info = users::get_sessions();
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_sessions(i))?);
+            }
+            Some(Commands::Users { sessions: false }) => {
+                let mut info = users::get_users_info(&users::get_sessions());
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_users_info(i))?);
+            }
+            Some(Commands::Process { pid, threads }) => {
+                let mut info = get_process_detail(&sys, *pid).ok_or_else(|| Error::Failed(format!("no process with PID {}", pid)))?;
+                if *threads {
+                    info.thread_list = Some(thread_sampler.sample(*pid));
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_process_detail)?);
+            }
+            Some(Commands::Whoholds { path }) => {
+                let mut info = whoholds::find_holders(&sys, path).map_err(|e| Error::io(format!("checking who holds {}", path.display()), e))?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_file_holders(i))?);
+            }
+            Some(Commands::Connections { listening, port, tcp, udp }) => {
+                let filter = connections::ConnectionFilter { tcp: *tcp, udp: *udp, listening: *listening, ports: port.clone() };
+                let mut info = connections::get_connections(&sys, &filter).map_err(|e| Error::io("listing connections", e))?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_connections(i))?);
+            }
+            Some(Commands::Docker { socket, processes }) => {
+                let socket = docker::socket_path(socket.as_deref());
+                let mut info = docker::get_containers(&sys, &socket).map_err(|e| Error::io("listing Docker containers", e))?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_docker_containers(i, *processes))?);
+            }
+            Some(Commands::K8sNode) => {
+                let mut info = k8s::get_node_report(&sys);
+                // Not a node, or its pods' processes aren't visible from here.
+                if info.processes.is_empty() && watch.is_none() {
+                    exit_status = status::UNAVAILABLE;
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info.processes, format_k8s_node)?);
+            }
+            Some(Commands::Port { port }) => {
+                let mut info = connections::get_port_owners(&sys, *port).map_err(|e| Error::io(format!("checking port {}", port), e))?;
+                // Like `lsof -i`, finding nothing is a failure scripts can test for.
+                if info.is_empty() && watch.is_none() {
+                    exit_status = status::ERROR;
+                }
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_port_owners(i))?);
+            }
+            Some(Commands::Thermal { critical, .. }) => {
+                let mut info = get_thermal_sample(&sys, *critical);
+                if let Some(tracker) = throttle_tracker.as_mut() {
+                    tracker.record(&mut info);
+                }
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_thermal_sample)?);
+            }
+            Some(Commands::Bench { duration, dir, size }) => {
+                let dir = dir.clone().unwrap_or_else(std::env::temp_dir);
+                let info = bench::run_benchmarks(*duration, &dir, *size).map_err(|e| Error::io(format!("running disk benchmark in {}", dir.display()), e))?;
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_bench_results(i))?);
+            }
+            Some(Commands::Record { db, interval, metrics }) => {
+                let path = db.clone().or_else(history::default_db_path).unwrap_or_else(|| "history.db".into());
+                eprintln!("Recording to {} every {}; press Ctrl-C to stop", path.display(), humantime::format_duration(*interval));
+                let count = history::open(&path).and_then(|mut conn| history::record(&mut conn, *interval, metrics, &stop))
+                    .map_err(|e| Error::Failed(format!("recording to {}: {}", path.display(), e)))?;
+                output_str.push_str(&format!("Recorded {} samples to {}", count, path.display()));
+            }
+            Some(Commands::Daemon { every, to }) => {
+                // The command line replaces the config file's schedules and sinks.
+                let every = if every.is_empty() {
+                    config.daemon.every.iter().map(|s| parse_schedule(s)).collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| Error::Failed(format!("in the config file's daemon schedules: {}", e)))?
                 } else {
-                    output_str.push_str(&format_components_info(&info));
+                    every.clone()
+                };
+                let every = if every.is_empty() {
+                    MetricGroup::value_variants().iter().map(|g| (*g, Duration::from_secs(10))).collect()
+                } else {
+                    every
+                };
+                let mut to = if to.is_empty() {
+                    config.daemon.to.iter().map(|s| parse_sink(s)).collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| Error::Failed(format!("in the config file's daemon sinks: {}", e)))?
+                } else {
+                    to.clone()
+                };
+                to.extend(cli.otlp_endpoint.clone().map(SinkUrl::Otlp));
+                let mut sinks = to.iter().map(|url| connect_sink(url, cli)).collect::<Result<Vec<_>>>()?;
+                let count = daemon::run(&every, &mut sinks, &stop);
+                if !sinks.is_empty() {
+                    output_str.push_str(&format!("Sent {} samples to {} sinks", count, sinks.len()));
                 }
             }
-            Some(Commands::Processes { filter, limit, sort }) => {
-                let info = get_processes_info(&sys, filter, *limit, *sort);
-                if cli.json {
-                    output_str.push_str(&serde_json::to_string_pretty(&info).unwrap());
+            Some(Commands::History { action: Some(HistoryAction::Chart { metric, out, since, target, db }), .. }) => {
+                let mut info = read_history(metric, *since, target.as_deref(), db.as_deref())?;
+                if info.is_empty() {
+                    return Err(Error::Failed(format!("no {} samples in the last {}", metric, humantime::format_duration(*since))));
+                }
+                redact(&mut info, redactor.as_mut());
+                std::fs::write(out, chart::svg(metric, &history::chart_lines(&info), |v| format_history_value(metric, v))).map_err(|e| Error::io(format!("writing chart {}", out.display()), e))?;
+                output_str.push_str(&format!("Chart written to {}", out.display()));
+            }
+            Some(Commands::History { metric, since, target, db, action: None }) => {
+                let metric = metric.as_deref().expect("required without a subcommand");
+                let mut info = read_history(metric, *since, target.as_deref(), db.as_deref())?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_history(i))?);
+            }
+            Some(Commands::All { top }) => {
+                let parts = match prefetched.take() {
+                    Some(Prefetched::All(parts)) => parts,
+                    _ => get_all_parts(hide_idle),
+                };
+                let mut info = get_all_report(&sys, parts, *top, hide_idle);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_all_report)?);
+            }
+            Some(Commands::Snapshot { path }) => {
+                let path = path.clone().unwrap_or_else(|| {
+                    let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                    format!("sysinfo-snapshot-{}.json", secs).into()
+                });
+                let mut info = snapshot::take(&sys);
+                redact(&mut info, redactor.as_mut());
+                let json = serde_json::to_string_pretty(&versioned(&info, None)?)?;
+                std::fs::write(&path, json + "\n").map_err(|e| Error::io(format!("writing snapshot {}", path.display()), e))?;
+                output_str.push_str(&format!("Snapshot written to {}", path.display()));
+            }
+            Some(Commands::Diff { before, after }) => {
+                let read = |path| snapshot::read(path).map_err(|e| Error::Failed(format!("reading snapshot {}", e)));
+                let mut info = snapshot::diff(&read(before)?, &read(after)?);
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_snapshot_diff)?);
+            }
+            Some(Commands::Bundle { path }) => {
+                let path = path.clone().unwrap_or_else(|| {
+                    let secs = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+                    format!("sysinfo-bundle-{}.tar.gz", secs)
+                });
+                bundle::write_bundle(&path, &sys, redactor.as_mut()).map_err(|e| Error::io(format!("writing support bundle {}", path), e))?;
+                output_str.push_str(&format!("Support bundle written to {}", path));
+            }
+            Some(Commands::Serve { port, bind, db }) => {
+                let db = db.clone().or_else(history::default_db_path).unwrap_or_else(|| "history.db".into());
+                serve::serve((*bind, *port).into(), cli.sample, &db, redactor.as_mut()).map_err(|e| Error::io(format!("serving on {}:{}", bind, port), e))?;
+            }
+            Some(Commands::Agent { listen, token, tls_cert, tls_key }) => {
+                let token = token.clone().or_else(|| std::env::var("SYSINFO_AGENT_TOKEN").ok().filter(|t| !t.is_empty()));
+                let tls = match (tls_cert, tls_key) {
+                    (Some(cert), Some(key)) => Some(agent::server_tls(cert, key).map_err(|e| Error::io("loading the TLS certificate", e))?),
+                    _ => None,
+                };
+                agent::run(listen, token.as_deref(), tls, cli.sample, redactor.as_mut()).map_err(|e| Error::io(format!("serving on {}", listen), e))?;
+            }
+            Some(Commands::Query { addr, token, tls, tls_ca, top }) => {
+                let token = token.clone().or_else(|| std::env::var("SYSINFO_AGENT_TOKEN").ok().filter(|t| !t.is_empty()));
+                let tls = if *tls || tls_ca.is_some() {
+                    Some(agent::client_tls(tls_ca.as_deref()).map_err(|e| Error::io("loading the TLS certificates", e))?)
                 } else {
-                    output_str.push_str(&format_processes_info(&info));
+                    None
+                };
+                let mut info = agent::query(addr, token.as_deref(), tls, *top, agent::TIMEOUT).map_err(|e| Error::io(format!("querying {}", addr), e))?;
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &[&info], format_snapshot)?);
+            }
+            Some(Commands::Push { url, .. }) => {
+                let pusher = pusher.as_mut().expect("created for push");
+                let mut system = get_system_info();
+                redact(&mut system, redactor.as_mut());
+                let metrics = prometheus::collect(&sys, &system, &get_memory_info(&sys));
+                let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_nanos());
+                pusher.add(push::line_protocol(&metrics, system.host_name.as_deref().unwrap_or_default(), now));
+                // A single run sends its sample once the loop ends.
+                if watch.is_some() && pusher.ready() {
+                    match pusher.flush(&stop) {
+                        Ok(count) => pushed += count,
+                        Err(e) => eprintln!("Error pushing to {}: {}", url, e),
+                    }
+                }
+            }
+            Some(Commands::Kill { pid, name, signal, dry_run, yes }) => {
+                let mut targets = kill::find_targets(&sys, *pid, name.as_deref(), *signal);
+                if targets.is_empty() {
+                    return Err(Error::Failed("no matching process".to_string()));
+                }
+                let refused = targets.len() > 1 && !yes && !dry_run;
+                if !dry_run && !refused {
+                    kill::send(&sys, &mut targets, *signal);
+                }
+                redact(&mut targets, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &targets, &targets, |t| format_kill_results(t))?);
+                if refused {
+                    output.emit(&output_str, false)?;
+                    return Err(Error::Usage(format!("{} processes match; pass --yes to signal all of them", targets.len())));
+                }
+            }
+            Some(Commands::Top { interval, sort }) => {
+                tui::run(*interval, *sort).map_err(|e| Error::io("running interactive view", e))?;
+                // The view refreshes itself; there is nothing left to print or repeat.
+                return Ok(());
+            }
+            Some(Commands::Schema { report }) => {
+                let schema = report.map_or_else(schema::schemas, schema::schema);
+                output_str.push_str(&match cli.format {
+                    OutputFormat::Yaml => serde_yaml_ng::to_string(&schema)?.trim_end().to_string(),
+                    _ => serde_json::to_string_pretty(&schema)?,
+                });
+            }
+            Some(Commands::Examples { area }) => {
+                let info = examples::get_examples(area.as_deref()).ok_or_else(|| {
+                    let areas: Vec<&str> = examples::AREAS.iter().map(|(id, _)| *id).collect();
+                    Error::Usage(format!("no examples for `{}`; areas are {}, or name a subcommand", area.as_deref().unwrap_or_default(), areas.join(", ")))
+                })?;
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_examples(i))?);
+            }
+            Some(Commands::Completions { shell }) => {
+                let mut script = Vec::new();
+                clap_complete::generate(*shell, &mut examples::add_to_help(Cli::command()), "sysinfo-cli", &mut script);
+                output_str.push_str(&String::from_utf8_lossy(&script));
+            }
+            Some(Commands::Manpage) => {
+                let mut page = Vec::new();
+                clap_mangen::Man::new(examples::add_to_help(Cli::command())).source(concat!("sysinfo-cli ", env!("CARGO_PKG_VERSION"))).render(&mut page).map_err(|e| Error::io("rendering the man page", e))?;
+                output_str.push_str(&String::from_utf8_lossy(&page));
+            }
+            None if remote => {
+                let mut info = hosts::poll(&cli.host, host_token.as_deref(), host_tls.clone());
+                redact(&mut info, redactor.as_mut());
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_host_statuses(i))?);
+                if watch.is_none() {
+                    if info.iter().any(|h| h.error.is_some()) {
+                        exit_status = status::ERROR;
+                    } else if info.iter().any(|h| !h.disk_alerts.is_empty()) {
+                        exit_status = status::THRESHOLD;
+                    }
                 }
             }
+            None if cli.profile_collection => {
+                // The first tick reads everything from scratch, then refreshes it in place.
+                let profiler = profiler.get_or_insert_with(CollectionProfiler::new);
+                profiler.sample();
+                let info = profiler.report();
+                output_str.push_str(&send_and_render(cli.format, cli.delimiter, &info, &info, |i| format_collection_profile(i))?);
+            }
             None => {
-                if cli.json {
-                    let summary = serde_json::json!({
-                        "system": get_system_info(),
-                        "memory": get_memory_info(&sys),
-                        "cpu_total_usage": sys.global_cpu_usage(),
-                        "nb_cpus": sys.cpus().len(),
-                    });
-                    output_str.push_str(&serde_json::to_string_pretty(&summary).unwrap());
+                let mut system = match prefetched.take() {
+                    Some(Prefetched::Summary(system)) => system,
+                    _ => get_system_info(),
+                };
+                redact(&mut system, redactor.as_mut());
+                let mem = get_memory_info(&sys);
+                let summary = get_summary(&sys, &system, &mem);
+                if sink_url.is_some() {
+                    sink::send_with_metrics(&summary, &prometheus::collect(&sys, &system, &mem));
+                }
+                if cli.format == OutputFormat::Prometheus {
+                    output_str.push_str(&prometheus::scrape(&sys, &system, &mem));
                 } else {
-                    let mut s = String::new();
-                    s.push_str(&format!("{}\n", "--- System Summary ---".bright_cyan().bold()));
-                    s.push_str(&format_system_info(&get_system_info()));
-                    s.push_str(&format!("\n{}\n", "--- Memory Summary ---".bright_cyan().bold()));
-                    let mem = get_memory_info(&sys);
-                    s.push_str(&format!("{:<25} {}\n", "Total memory:".yellow(), format_bytes(mem.total_memory)));
-                    s.push_str(&format!("{:<25} {}\n", "Used memory:".yellow(), format_bytes(mem.used_memory)));
-                    s.push_str(&format!("\n{}\n", "--- CPU Summary ---".bright_cyan().bold()));
-                    s.push_str(&format!("{:<25} {}\n", "NB CPUs:".yellow(), sys.cpus().len()));
-                    s.push_str(&format!("{:<25} {:.1}%\n", "Total CPU usage:".yellow(), sys.global_cpu_usage()));
-                    output_str.push_str(&s);
+                    output_str.push_str(&render(cli.format, cli.delimiter, &summary, &[&summary], |_| {
+                        let mut s = String::new();
+                        s.push_str(&format!("{}\n", format!("--- {} ---", tr("section-summary-system")).bright_cyan().bold()));
+                        s.push_str(&format_system_info(&system));
+                        s.push_str(&format!("\n{}\n", format!("--- {} ---", tr("section-summary-memory")).bright_cyan().bold()));
+                        s.push_str(&format!("{:<25} {}\n", tr("total-memory").yellow(), format_bytes(mem.total_memory)));
+                        s.push_str(&format!("{:<25} {}\n", tr("used-memory").yellow(), format_bytes(mem.used_memory)));
+                        s.push_str(&format!("\n{}\n", format!("--- {} ---", tr("section-summary-cpu")).bright_cyan().bold()));
+                        s.push_str(&format!("{:<25} {}\n", tr("nb-cpus").yellow(), localize_number(&sys.cpus().len().to_string())));
+                        s.push_str(&format!("{:<25} {}%\n", tr("total-cpu-usage").yellow(), format_float(sys.global_cpu_usage(), 1)));
+                        s
+                    })?);
                 }
             }
         }
 
-        if let Some(path) = &cli.output {
-            if let Ok(mut file) = File::create(path) {
-                if let Err(e) = write!(file, "{}", output_str) {
-                    eprintln!("Error writing to file: {}", e);
-                }
-            } else {
-                eprintln!("Error creating file: {}", path);
+        self_stats.record(cli.command.as_ref().map_or("summary", Commands::name), started.elapsed());
+        self_stats.finish_iteration();
+
+        if streaming || statusbar || pusher.is_some() || matches!(cli.command, Some(Commands::Daemon { .. })) {
+            if !output_str.is_empty() {
+                output.emit(output_str.trim_end_matches('\n'), true)?;
             }
         } else {
-            println!("{}", output_str);
+            output.emit(&output_str, false)?;
+        }
+        if let Some(clipboard) = clipboard.as_mut() && !output_str.is_empty()
+            && let Err(e) = clipboard.set_text(strip_ansi(&output_str)) {
+            eprintln!("Error copying to clipboard: {}", e);
+            exit_status = status::ERROR;
+        }
+
+        samples += 1;
+        if let Some(stats) = run_stats.as_mut() {
+            stats.sample(&mut sys, &mut network_rates);
+        }
+        let past_deadline = || deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if cli.count.is_some_and(|count| samples >= count) || past_deadline() {
+            break;
         }
 
-        if let Some(interval) = cli.watch {
-            thread::sleep(Duration::from_secs(interval));
-            if !cli.json && cli.output.is_none() {
+        if let Some(mut interval) = watch.or(streaming.then(|| config.watch.unwrap_or(Duration::from_secs(1)))) {
+            if let Some(deadline) = deadline {
+                interval = interval.min(deadline.saturating_duration_since(Instant::now()));
+            }
+            sleep_unless_stopped(interval, &stop);
+            if stop.load(Ordering::SeqCst) || past_deadline() {
+                break;
+            }
+            if cli.format == OutputFormat::Text && (cli.output.is_none() || cli.tee) && !streaming && !statusbar && pusher.is_none() {
                 // Clear screen for watch mode if not in JSON or File mode
-                print!("\x1B[2J\x1B[1;1H");
+                write!(std::io::stdout(), "\x1B[2J\x1B[1;1H").map_err(|e| Error::io("writing to stdout", e))?;
+            }
+            let started = Instant::now();
+            if !remote && !cli.profile_collection {
+                refresh_system(&mut sys, &cli.command);
             }
+            refresh_time = started.elapsed();
         } else {
             break;
         }
     }
+
+    // End-of-run summaries aren't samples.
+    sink::set_sink(None, "");
+
+    if let (Some(pusher), Some(Commands::Push { url, .. })) = (pusher.as_mut(), &cli.command) {
+        let count = pusher.flush(&stop).map_err(|e| Error::Failed(format!("pushing to {}: {}", url, e)))?;
+        output.emit(&format!("Pushed {} samples to {}", pushed + count, url), true)?;
+    }
+
+    // The textfile collector would reject it.
+    if let Some(stats) = &run_stats && cli.format != OutputFormat::Prometheus {
+        let summary = stats.summary();
+        output.emit(&render(cli.format, cli.delimiter, &summary, &[&summary], format_watch_summary)?, true)?;
+    }
+
+    if watch.is_some() && let Some(tracker) = &throttle_tracker {
+        let summary = tracker.summary();
+        output.emit(&render(cli.format, cli.delimiter, &summary, &[&summary], format_throttle_summary)?, true)?;
+    }
+
+    // Goes to stderr so it never mixes with the data, even in JSON mode.
+    if cli.self_stats {
+        let (cpu_time_ms, peak_rss) = get_own_usage();
+        let summary = self_stats.summary(cpu_time_ms, peak_rss);
+        eprintln!("{}", render(cli.format, cli.delimiter, &summary, &summary.sections, format_self_stats)?.trim_end());
+    }
+
+    if output.failed() {
+        exit_status = status::ERROR;
+    }
+    if exit_status != 0 {
+        return Err(Error::Exit(exit_status));
+    }
+    Ok(())
+}
+
+/// Prints a command-line error (or `--help`/`--version`) and exits with
+/// [`status::USAGE`] for errors.
+fn usage_error(e: clap::Error) -> ! {
+    let _ = e.print();
+    std::process::exit(if e.use_stderr() { status::USAGE } else { 0 });
+}
+
+/// The samples of `metric` from the last `since` in the history database at `db`
+/// (default: [`history::default_db_path`]).
+fn read_history(metric: &str, since: Duration, target: Option<&str>, db: Option<&std::path::Path>) -> Result<Vec<sysinfo_cli::models::HistorySample>> {
+    if !history::SERIES.iter().any(|(m, _)| *m == metric) {
+        let names: Vec<&str> = history::SERIES.iter().map(|(m, _)| *m).collect();
+        return Err(Error::Usage(format!("unknown metric `{}`; expected one of {}", metric, names.join(", "))));
+    }
+    let path = db.map(|db| db.to_path_buf()).or_else(history::default_db_path).unwrap_or_else(|| "history.db".into());
+    if !path.exists() {
+        return Err(Error::Failed(format!("no history at {}; start one with `sysinfo-cli record`", path.display())));
+    }
+    let since = std::time::SystemTime::now().checked_sub(since).unwrap_or(std::time::UNIX_EPOCH);
+    let since = since.duration_since(std::time::UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    history::open(&path).and_then(|conn| history::query(&conn, metric, target, since))
+        .map_err(|e| Error::Failed(format!("reading history from {}: {}", path.display(), e)))
+}
+
+/// Sends `info` to the `--sink`, if one is set, and renders it like [`render`].
+fn send_and_render<T, R>(format: OutputFormat, delimiter: char, info: &T, rows: &[R], text: impl FnOnce(&T) -> String) -> Result<String>
+where
+    T: serde::Serialize + prometheus::ToMetrics + ?Sized,
+    R: serde::Serialize,
+{
+    sink::send(info);
+    render(format, delimiter, info, rows, text)
+}
+
+/// Connects to `url` with the sink options from `cli`.
+fn connect_sink(url: &SinkUrl, cli: &Cli) -> Result<sink::Sink> {
+    let prefix = if matches!(url, SinkUrl::Mqtt { .. }) { &cli.topic_prefix } else { &cli.prefix };
+    sink::Sink::connect(url, prefix, cli.ha_discovery).map_err(|e| Error::io(format!("connecting to sink {}", url), e))
+}
+
+/// Commands, by [`output_name`], with numeric readings worth exposing as Prometheus metrics.
+const PROMETHEUS_COMMANDS: &[&str] = &[
+    "summary", "system", "uptime", "cpu", "load", "memory", "disks", "network", "components", "container",
+    "docker", "k8s-node", "query", "sensors", "resources", "processes", "thermal", "all",
+];
+
+/// Commands, by [`output_name`], whose records don't map onto CSV rows. Event streams
+/// don't either: their columns vary from event to event.
+const NON_CSV_COMMANDS: &[&str] = &[
+    "all", "bundle", "record", "daemon", "snapshot", "diff", "events", "serve", "agent", "query",
+    "push", "statusbar", "top", "schema", "completions", "manpage", "history chart", "processes --follow",
+];
+
+/// How `command` is named in the lists above, with the forms whose output differs from
+/// the plain subcommand's spelled out.
+fn output_name(command: &Option<Commands>) -> &'static str {
+    match command {
+        None => "summary",
+        Some(Commands::Processes { follow: true, .. }) => "processes --follow",
+        Some(Commands::Disks { action: Some(DisksAction::Analyze { .. }), .. }) => "disks analyze",
+        Some(Commands::History { action: Some(HistoryAction::Chart { .. }), .. }) => "history chart",
+        Some(command) => command.name(),
+    }
+}
+
+fn supports_prometheus(command: &Option<Commands>) -> bool {
+    PROMETHEUS_COMMANDS.contains(&output_name(command))
+}
+
+fn supports_csv(command: &Option<Commands>) -> bool {
+    !NON_CSV_COMMANDS.contains(&output_name(command))
+}
+
+/// `a, b, and c`.
+fn join_names(names: &[&str]) -> String {
+    match names {
+        [] => String::new(),
+        [name] => name.to_string(),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+    }
 }
+
@@ -1,64 +1,999 @@
-use serde::Serialize;
+//! The reports every subcommand produces. Their serde form is the JSON and YAML
+//! output, so renaming a field is a breaking change.
 
-#[derive(Serialize, Debug)]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The `schema_version` of JSON and YAML output. Raised whenever a field is removed,
+/// renamed, or changes type; new fields don't change it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct SystemInfo {
     pub name: Option<String>,
     pub kernel_version: Option<String>,
     pub os_version: Option<String>,
     pub host_name: Option<String>,
+    /// Seconds since boot.
+    pub uptime: u64,
+    /// Unix time of the last boot, in seconds.
+    pub boot_time: u64,
+    /// Not available on Windows.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average: Option<LoadAverage>,
+}
+
+/// What `fetch` shows beside the logo.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct FetchInfo {
+    /// The user running the tool, from `$USER` (`%USERNAME%` on Windows).
+    pub user: Option<String>,
+    pub host_name: Option<String>,
+    /// Distribution or OS, version, and CPU architecture, e.g. `Ubuntu 24.04 x86_64`.
+    pub os: Option<String>,
+    pub kernel_version: Option<String>,
+    /// Seconds since boot.
+    pub uptime: u64,
+    /// The CPU's model name.
+    pub cpu: Option<String>,
+    pub nb_cpus: usize,
+    pub used_memory: u64,
+    pub total_memory: u64,
+    pub disks: Vec<FetchDisk>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct FetchDisk {
+    pub mount_point: String,
+    pub used_space: u64,
+    pub total_space: u64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct UptimeInfo {
+    /// Seconds since boot.
+    pub uptime: u64,
+    /// Unix time of the last boot, in seconds.
+    pub boot_time: u64,
+}
+
+/// Average number of runnable (and, on Linux, uninterruptible) tasks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, JsonSchema)]
+pub struct LoadAverage {
+    pub one: f64,
+    pub five: f64,
+    pub fifteen: f64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct LoadReport {
+    pub load_average: LoadAverage,
+    pub nb_cpus: usize,
+    /// Limit for the one-minute average (`--threshold`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exceeded: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
 pub struct CpuInfo {
     pub nb_cpus: usize,
+    /// Physical cores, when the platform reports them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub physical_cores: Option<usize>,
+    /// CPUs the cgroup's quota allows, as in a container with a CPU limit (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<f64>,
+    /// Sockets, cores, and threads, with `cpu --topology`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topology: Option<CpuTopology>,
     pub cpus: Vec<SingleCpuInfo>,
     pub total_usage: f32,
+    /// Where time went across all CPUs, with `cpu --detailed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<CpuBreakdown>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average: Option<LoadAverage>,
+    /// Core voltage in volts (single-board computers with `vcgencmd`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub core_voltage: Option<f32>,
+    /// Active throttling conditions, plus `_since_boot` ones (single-board computers with `vcgencmd`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub throttling: Option<Vec<String>>,
 }
 
-#[derive(Serialize, Debug)]
+/// Where CPU time went over the sample, in percent; `cpu --detailed` (Linux).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, JsonSchema)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    /// Time a hypervisor gave to other guests while this one was ready to run.
+    pub steal: f32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+pub struct CpuTopology {
+    /// `None` where the platform doesn't say which socket a CPU is on.
+    pub sockets: Option<usize>,
+    pub cores: Option<usize>,
+    pub threads: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
 pub struct SingleCpuInfo {
     pub id: usize,
     pub usage: f32,
     pub vendor: String,
     pub brand: String,
+    /// Current frequency in MHz, or 0 when not reported.
+    #[serde(default)]
+    pub frequency: u64,
+    /// Lowest frequency in MHz the CPU can run at (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_frequency: Option<u64>,
+    /// Highest frequency in MHz the CPU can run at (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_frequency: Option<u64>,
+    /// Socket (physical package) id, with `cpu --topology` (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub socket: Option<u64>,
+    /// Core id within the socket, with `cpu --topology` (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub core: Option<u64>,
+    /// Where this CPU's time went, with `cpu --detailed`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub breakdown: Option<CpuBreakdown>,
+    /// Average usage over the recent watch samples.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_usage: Option<f32>,
+    /// Peak usage over the recent watch samples.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_usage: Option<f32>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
 pub struct MemoryInfo {
     pub total_memory: u64,
+    /// Memory in use, as `free` reports it: the total less what's available.
     pub used_memory: u64,
+    /// Memory that can be given to programs without swapping, counting reclaimable caches.
+    #[serde(default)]
+    pub available_memory: u64,
+    /// Memory not used for anything, not even caches.
+    #[serde(default)]
+    pub free_memory: u64,
+    /// Kernel buffers (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buffers: Option<u64>,
+    /// Page cache plus reclaimable kernel slabs, `free`'s "cache" (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached: Option<u64>,
     pub total_swap: u64,
     pub used_swap: u64,
+    /// Committed memory (Windows only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub committed_memory: Option<u64>,
+    /// Commit limit (Windows only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit_limit: Option<u64>,
+    /// The memory cgroup's limit, when it's below the host's memory, as in a container (Linux).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cgroup: Option<CgroupMemory>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ContainerInfo {
+    pub in_container: bool,
+    /// `docker`, `podman`, `kubernetes`, `containerd`, or `lxc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<String>,
+    /// The container's id, when the cgroup path or mounts show it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// 1 or 2.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup_version: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cgroup_path: Option<String>,
+    /// CPUs the quota allows, e.g. 1.5; `None` without a quota.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_limit: Option<f64>,
+    /// CPUs the cgroup used on average over the sample.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpus_used: Option<f64>,
+    /// `cpus_used` as a share of `cpu_limit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_limit_percent: Option<f32>,
+    /// `None` without a limit.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+    /// Memory charged to the cgroup, page cache included.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_used: Option<u64>,
+    /// `memory_used` as a share of `memory_limit`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit_percent: Option<f32>,
+    pub host_cpus: usize,
+    pub host_memory: u64,
+}
+
+#[derive(Serialize, Debug, Default, JsonSchema)]
+pub struct DockerContainer {
+    /// The short, 12-character id.
+    pub id: String,
+    pub name: String,
+    pub image: String,
+    /// Docker's summary, e.g. `Up 3 hours (healthy)`.
+    pub status: String,
+    /// Percent of one CPU, as `docker stats` shows it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_usage: Option<f32>,
+    /// Memory in use, not counting inactive page cache.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_usage: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_limit: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_received: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network_transmitted: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_read: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_written: Option<u64>,
+    /// The container's processes as the host sees them.
+    pub processes: Vec<DockerProcess>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct DockerProcess {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+}
+
+/// `k8s-node`: the node's row of `kubectl top node`, and the processes in pods.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct K8sNodeReport {
+    /// The host name, which the kubelet uses as the node name unless overridden.
+    pub name: String,
+    /// CPU in use, in cores.
+    pub cpu_cores: f64,
+    pub cpu_percent: f32,
+    /// Memory in use, counted as `free` does rather than as the pods' working set.
+    pub memory_bytes: u64,
+    pub memory_percent: f32,
+    pub processes: Vec<PodProcess>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct PodProcess {
+    /// From the kubelet's pod log directories; missing when those can't be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pod: Option<String>,
+    pub pod_uid: String,
+    /// `Guaranteed`, `Burstable`, or `BestEffort`.
+    pub qos: String,
+    /// The full container id; missing for processes in the pod's own cgroup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container_id: Option<String>,
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+pub struct CgroupMemory {
+    pub limit: u64,
+    /// Memory charged to the cgroup, page cache included.
+    pub used: u64,
+    /// Anonymous memory of the cgroup's processes.
+    pub rss: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct DiskInfo {
     pub name: String,
+    #[serde(default)]
+    pub mount_point: String,
     pub kind: String,
     pub file_system: String,
+    #[serde(default)]
+    pub is_removable: bool,
+    #[serde(default)]
+    pub is_read_only: bool,
     pub available_space: u64,
     pub total_space: u64,
+    /// Share of the total space not available, as `df` reports it.
+    #[serde(default)]
+    pub used_percent: f32,
+    /// Where `used_percent` stands against `--warn-pct` and `--crit-pct`, when given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_level: Option<UsageLevel>,
+    /// Current disk queue length (Windows only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_length: Option<f64>,
+    /// Change in available space in bytes per second between watch samples (negative while filling).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_rate: Option<f64>,
+    /// Seconds until the disk is full at the current rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_to_full: Option<u64>,
+    /// Bytes read and written since boot (`--io`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub written_bytes: Option<u64>,
+    /// Bytes per second read and written since the previous watch iteration (`--io`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub write_rate: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageLevel {
+    Ok,
+    Warning,
+    Critical,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct DiskUsageReport {
+    pub root: String,
+    pub total_size: u64,
+    pub total_files: u64,
+    /// Directories and entries that could not be read.
+    pub skipped: u64,
+    pub directories: Vec<DirUsage>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct DirUsage {
+    pub path: String,
+    /// Levels below the scanned root.
+    pub depth: usize,
+    /// Allocated size of everything below the directory, in bytes.
+    pub size: u64,
+    pub files: u64,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Default, JsonSchema)]
 pub struct NetworkInfo {
     pub interface: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mac_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mtu: Option<u64>,
+    /// Addresses with their prefix length, e.g. `192.168.1.20/24`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ip_addresses: Vec<String>,
     pub received: u64,
     pub transmitted: u64,
+    #[serde(default)]
+    pub packets_received: u64,
+    #[serde(default)]
+    pub packets_transmitted: u64,
+    #[serde(default)]
+    pub errors_received: u64,
+    #[serde(default)]
+    pub errors_transmitted: u64,
+    /// Packets dropped since boot (Linux only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drops_received: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drops_transmitted: Option<u64>,
+    /// Bytes per second received and transmitted since the previous watch iteration.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rx_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_rate: Option<f64>,
+    /// Counters since the previous refresh (`--delta`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<NetworkDelta>,
+    /// Network namespace the interface lives in (with `--all-namespaces` / `--netns`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
 }
 
-#[derive(Serialize, Debug)]
+/// What an interface received and transmitted between two refreshes.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema)]
+pub struct NetworkDelta {
+    pub received: u64,
+    pub transmitted: u64,
+    pub packets_received: u64,
+    pub packets_transmitted: u64,
+    pub errors_received: u64,
+    pub errors_transmitted: u64,
+}
+
+#[derive(Serialize, Debug, Default, JsonSchema)]
 pub struct ComponentInfo {
     pub label: String,
     pub temperature: Option<f32>,
+    /// Highest temperature seen.
     pub max: Option<f32>,
+    /// Temperature the hardware treats as critical, when the sensor reports one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub critical: Option<f32>,
+    /// Current temperature as a share of `max`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percent_of_max: Option<f32>,
+    /// Where the temperature stands against `--warn` and `--crit`, when given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature_level: Option<UsageLevel>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SensorKind {
+    Temperature,
+    Fan,
+    Voltage,
+    Current,
+    Power,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SensorReading {
+    /// The driver's name for the chip, e.g. `nct6798` or `amdgpu`.
+    pub chip: String,
+    /// The device the chip belongs to, such as a PCI address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<String>,
+    pub label: String,
+    pub kind: SensorKind,
+    /// In °C, RPM, volts, amperes, or watts, by `kind`.
+    pub value: f64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct KernelResources {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_handles_allocated: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_handles_max: Option<u64>,
+    pub sockets: Vec<SocketCount>,
+    /// Counted over the processes whose file descriptors we can read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoll_instances: Option<u64>,
+    /// Counted over the processes whose file descriptors we can read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inotify_instances: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inotify_max_user_instances: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inotify_max_user_watches: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub epoll_max_user_watches: Option<u64>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SocketCount {
+    pub protocol: String,
+    pub state: String,
+    pub count: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, JsonSchema)]
 pub struct ProcessInfo {
-    pub pid: String,
+    /// Snapshots saved before PIDs became numbers hold them as strings.
+    #[serde(deserialize_with = "number_or_string")]
+    #[schemars(with = "u32")]
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory: u64,
+    /// Seconds since the process started.
+    pub run_time: u64,
+    /// Open handle count (Windows only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub handles: Option<u32>,
+    /// Owner's user name, when it can be resolved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Resident memory as a percentage of total memory.
+    #[serde(default)]
+    pub memory_usage: f32,
+    #[serde(default)]
+    pub virtual_memory: u64,
+    /// Seconds since the Unix epoch.
+    #[serde(default)]
+    pub start_time: u64,
+    #[serde(default)]
+    pub status: String,
+    /// Thread count (Linux only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+    /// Command line arguments; empty when they can't be read.
+    #[serde(default)]
+    pub cmd: Vec<String>,
+    /// Bytes read from and written to disk since the process started (`--io`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub written_bytes: Option<u64>,
+    /// Bytes per second read and written since the previous watch iteration (`--io`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_rate: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_rate: Option<f64>,
+}
+
+fn number_or_string<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<u32, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Pid {
+        Number(u32),
+        String(String),
+    }
+    match Pid::deserialize(deserializer)? {
+        Pid::Number(pid) => Ok(pid),
+        Pid::String(pid) => pid.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ProcessDetail {
+    pub pid: u32,
     pub name: String,
+    pub cmd: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exe: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u32>,
+    pub status: String,
+    /// Owner's user name, when it can be resolved.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub start_time: u64,
+    pub run_time: u64,
     pub cpu_usage: f32,
     pub memory: u64,
+    pub virtual_memory: u64,
+    /// Bytes read from and written to disk over the process's lifetime.
+    pub disk_read: u64,
+    pub disk_written: u64,
+    /// Linux only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u32>,
+    /// Each thread, busiest first, with `--threads`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread_list: Option<Vec<ThreadInfo>>,
+    /// `KEY=value` entries; empty when they can't be read (usually another user's process).
+    pub environ: Vec<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    pub status: String,
+    /// Since the previous reading, in percent of one core.
+    pub cpu_usage: f32,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ProcessEventKind {
+    Start,
+    Exit,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ProcessEvent {
+    /// When the change was observed (RFC 3339), not when it happened.
+    pub timestamp: String,
+    pub event: ProcessEventKind,
+    pub pid: u32,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<u32>,
+    /// Seconds the process had been running when it was last seen (exit events).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_time: Option<u64>,
 }
 
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HardwareEventKind {
+    DiskAttached,
+    DiskDetached,
+    InterfaceAdded,
+    InterfaceRemoved,
+    PowerChanged,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct HardwareEvent {
+    /// When the change was observed (RFC 3339).
+    pub timestamp: String,
+    pub event: HardwareEventKind,
+    /// Disk name, interface name, or power supply name.
+    pub device: String,
+    /// Mount point and size for disks, the new state for power supplies.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CapabilityStatus {
+    Supported,
+    Unsupported,
+    NeedsPrivilege,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct Capability {
+    pub metric: String,
+    pub status: CapabilityStatus,
+    /// Why a metric is unsupported or limited.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct FileHolder {
+    pub pid: u32,
+    pub name: String,
+    /// How the file is held: `fd N`, `cwd`, `root`, `exe`, or `mmap`.
+    pub usage: String,
+    pub path: String,
+    /// The file was deleted but is still open.
+    pub deleted: bool,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketProtocol {
+    Tcp,
+    Udp,
+}
+
+/// TCP states, plus `unconnected` for UDP sockets without a fixed peer.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SocketState {
+    Established,
+    SynSent,
+    SynReceived,
+    FinWait1,
+    FinWait2,
+    TimeWait,
+    Closed,
+    CloseWait,
+    LastAck,
+    Listen,
+    Closing,
+    Unconnected,
+    Unknown,
+}
+
+#[derive(Serialize, Debug, Clone, JsonSchema)]
+pub struct Connection {
+    pub protocol: SocketProtocol,
+    pub local_address: String,
+    pub local_port: u16,
+    /// Unspecified (`0.0.0.0` or `::`, port 0) for listening and unconnected sockets.
+    pub remote_address: String,
+    pub remote_port: u16,
+    pub state: SocketState,
+    /// The owning process; on Linux, only processes this user may inspect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process: Option<String>,
+}
+
+/// A socket on the port `port` asked about, with the owning process's details.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct PortOwner {
+    #[serde(flatten)]
+    pub connection: Connection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KillOutcome {
+    /// Listed by `--dry-run` or held back for lack of `--yes`; nothing was sent.
+    Matched,
+    Sent,
+    Failed,
+    /// The signal doesn't exist on this platform.
+    Unsupported,
+}
+
+/// The default report, without a subcommand: system details, memory, and overall
+/// CPU usage.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct Summary<'a> {
+    pub system: &'a SystemInfo,
+    pub memory: &'a MemoryInfo,
+    pub cpu_total_usage: f32,
+    pub nb_cpus: usize,
+}
+
+/// Every category in one document, for `all`.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct AllReport {
+    pub system: SystemInfo,
+    pub cpu: CpuInfo,
+    pub memory: MemoryInfo,
+    pub disks: Vec<DiskInfo>,
+    pub network: Vec<NetworkInfo>,
+    pub components: Vec<ComponentInfo>,
+    /// The busiest processes by CPU usage.
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// Everything `snapshot` saves, and `diff` compares.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct Snapshot {
+    pub taken_at: String,
+    pub system: SystemInfo,
+    pub cpu: CpuInfo,
+    pub memory: MemoryInfo,
+    pub disks: Vec<DiskInfo>,
+    pub network: Vec<NetworkInfo>,
+    pub processes: Vec<ProcessInfo>,
+}
+
+/// One `--host` row: an agent's headline figures, or why it couldn't be reached.
+#[derive(Serialize, Debug, Default, JsonSchema)]
+pub struct HostStatus {
+    /// The agent's address as given.
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_name: Option<String>,
+    /// Why the host couldn't be polled; the figures are missing then.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_usage: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_percent: Option<f32>,
+    /// The one-minute load average; not on Windows hosts.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub load_average: Option<f64>,
+    /// Disks at least 90% full, fullest first.
+    pub disk_alerts: Vec<DiskAlert>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct DiskAlert {
+    pub mount_point: String,
+    pub used_percent: f32,
+}
+
+#[derive(Serialize, Debug, PartialEq, JsonSchema)]
+pub struct Change {
+    pub before: u64,
+    pub after: u64,
+    pub change: i64,
+}
+
+/// Available space of a disk that changed, appeared, or went away between snapshots.
+#[derive(Serialize, Debug, PartialEq, JsonSchema)]
+pub struct DiskChange {
+    pub name: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+    pub change: i64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SnapshotDiff {
+    pub from: String,
+    pub to: String,
+    /// The boot times differ, so the machine restarted between the two snapshots.
+    pub rebooted: bool,
+    pub used_memory: Change,
+    pub used_swap: Change,
+    pub disks: Vec<DiskChange>,
+    pub started: Vec<ProcessInfo>,
+    pub exited: Vec<ProcessInfo>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, JsonSchema)]
+pub struct HistorySample {
+    pub time: String,
+    pub metric: String,
+    /// Mount point or interface, for per-disk and per-interface series.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub value: f64,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertStatus {
+    Ok,
+    Fired,
+    /// The metric can't be read here, e.g. load averages on Windows.
+    Unknown,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct AlertResult {
+    pub rule: String,
+    pub metric: String,
+    /// Mount point the value was read from, for disk rules.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub value: Option<f64>,
+    pub status: AlertStatus,
+}
+
+/// A line for a Waybar custom module with `"return-type": "json"`.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct StatusbarLine {
+    pub text: String,
+    pub tooltip: String,
+    /// `warning` or `critical` while a rule of that level fires, else `ok`.
+    pub class: UsageLevel,
+    /// The `--percentage` metric, 0–100; absent when it can't be read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub percentage: Option<u8>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ExampleInfo {
+    pub area: String,
+    /// The invocation, quoted for a POSIX shell.
+    pub command: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct KillResult {
+    pub pid: u32,
+    pub name: String,
+    pub signal: String,
+    pub outcome: KillOutcome,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct UserInfo {
+    pub name: String,
+    pub uid: String,
+    pub gid: String,
+    pub groups: Vec<String>,
+    /// Open login sessions.
+    pub sessions: usize,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SessionInfo {
+    pub user: String,
+    pub tty: String,
+    /// Remote host for SSH and similar logins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    pub login_time: String,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ThermalSample {
+    /// Average frequency across all cores.
+    pub frequency_mhz: u64,
+    /// Highest frequency seen so far in a watch session.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_frequency_mhz: Option<u64>,
+    /// Temperature of the hottest sensor.
+    pub temperature: Option<f32>,
+    /// Critical temperature the reading is compared against.
+    pub critical: f32,
+    pub throttling: bool,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct ThrottleSummary {
+    pub samples: usize,
+    pub throttled_samples: usize,
+    pub throttled_pct: f32,
+    pub peak_frequency_mhz: u64,
+    pub min_frequency_mhz: u64,
+    pub max_temperature: Option<f32>,
+}
+
+/// The lowest, mean, 95th percentile, and highest value of one metric over a watch run.
+#[derive(Serialize, Debug, PartialEq, JsonSchema)]
+pub struct MetricStats {
+    pub min: f64,
+    pub avg: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct WatchSummary {
+    pub samples: usize,
+    pub elapsed_seconds: f64,
+    /// Overall CPU usage in percent.
+    pub cpu_usage: Option<MetricStats>,
+    /// Memory in use, in bytes; `max` is the peak.
+    pub memory_used: Option<MetricStats>,
+    /// Bytes received over the run, summed over every interface.
+    pub network_received: u64,
+    /// Bytes transmitted over the run, summed over every interface.
+    pub network_transmitted: u64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct BenchResult {
+    pub benchmark: String,
+    pub value: f64,
+    pub unit: String,
+    pub elapsed_ms: f64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct BundleDiagnostics {
+    pub tool_version: String,
+    pub generated_at: String,
+    pub args: Vec<String>,
+    pub cpu_arch: String,
+    pub long_os_version: Option<String>,
+    pub supported_system: bool,
+    pub collection_times: Vec<SectionTiming>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SelfStats {
+    pub iterations: usize,
+    /// CPU time used by the tool itself, in milliseconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu_time_ms: Option<u64>,
+    /// Peak resident memory in bytes (current resident memory where the peak is unknown).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_rss: Option<u64>,
+    pub sections: Vec<SectionLatency>,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SectionLatency {
+    pub section: String,
+    pub samples: usize,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+/// How long one of sysinfo's refreshes takes, for `--profile-collection`.
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct StageTiming {
+    pub stage: String,
+    /// CPUs, processes, disks, interfaces, or sensors read; absent for memory.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<usize>,
+    /// Listing and reading everything from scratch, as a one-off run does.
+    pub first_ms: f64,
+    /// Refreshing in place, as every `--watch` tick does.
+    pub refreshes: usize,
+    pub avg_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Serialize, Debug, JsonSchema)]
+pub struct SectionTiming {
+    pub section: String,
+    pub ms: f64,
+}
@@ -0,0 +1,130 @@
+//! Just enough MQTT 3.1.1 to publish: CONNECT with optional credentials, then
+//! QoS 0 PUBLISH packets over a plain TCP connection.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::time::Duration;
+use sysinfo::System;
+
+pub struct MqttClient {
+    host: String,
+    port: u16,
+    user: Option<String>,
+    password: Option<String>,
+    client_id: String,
+    /// `None` after a failed write; the next publish reconnects.
+    stream: Option<TcpStream>,
+}
+
+impl MqttClient {
+    /// Connects right away, so a wrong address or rejected login is reported up front.
+    pub fn connect(host: &str, port: u16, user: Option<String>, password: Option<String>) -> io::Result<Self> {
+        let mut client = MqttClient {
+            host: host.to_string(),
+            port,
+            user,
+            password,
+            client_id: client_id(System::host_name().as_deref().unwrap_or(""), std::process::id()),
+            stream: None,
+        };
+        client.stream = Some(client.open()?);
+        Ok(client)
+    }
+
+    fn open(&self) -> io::Result<TcpStream> {
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        stream.write_all(&connect_packet(&self.client_id, self.user.as_deref(), self.password.as_deref()))?;
+        let mut connack = [0; 4];
+        stream.read_exact(&mut connack)?;
+        match connack {
+            [0x20, 2, _, 0] => Ok(stream),
+            [0x20, 2, _, 4 | 5] => Err(io::Error::new(io::ErrorKind::PermissionDenied, "broker refused the credentials")),
+            [0x20, 2, _, code] => Err(io::Error::other(format!("broker refused the connection (code {})", code))),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidData, "not an MQTT broker")),
+        }
+    }
+
+    /// Publishes at most once; a broken connection is reopened once before giving up.
+    pub fn publish(&mut self, topic: &str, payload: &[u8], retain: bool) -> io::Result<()> {
+        let packet = publish_packet(topic, payload, retain);
+        if let Some(stream) = &mut self.stream && stream.write_all(&packet).is_ok() {
+            return Ok(());
+        }
+        self.stream = None;
+        let mut stream = self.open()?;
+        stream.write_all(&packet)?;
+        self.stream = Some(stream);
+        Ok(())
+    }
+}
+
+impl Drop for MqttClient {
+    fn drop(&mut self) {
+        if let Some(stream) = &mut self.stream {
+            let _ = stream.write_all(&[0xE0, 0]);
+        }
+    }
+}
+
+/// The variable-length "remaining length" of a fixed header.
+fn encode_length(mut length: usize, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (length % 128) as u8;
+        length /= 128;
+        if length > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if length == 0 {
+            break;
+        }
+    }
+}
+
+fn encode_string(value: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    out.extend_from_slice(value);
+}
+
+fn packet(first_byte: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![first_byte];
+    encode_length(body.len(), &mut out);
+    out.extend_from_slice(body);
+    out
+}
+
+/// The client identifier: the host name and process ID, since a broker drops the older
+/// of two connections with the same identifier, and PIDs repeat across hosts and
+/// containers.
+pub fn client_id(host_name: &str, pid: u32) -> String {
+    let host: String = host_name.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    format!("sysinfo-cli-{}-{}", host, pid)
+}
+
+/// A clean-session CONNECT without keep-alive, since ticks can be further apart than
+/// any keep-alive the broker would enforce.
+pub fn connect_packet(client_id: &str, user: Option<&str>, password: Option<&str>) -> Vec<u8> {
+    let mut flags = 0x02;
+    if user.is_some() {
+        flags |= 0x80;
+    }
+    if password.is_some() {
+        flags |= 0x40;
+    }
+    let mut body = Vec::new();
+    encode_string(b"MQTT", &mut body);
+    body.extend_from_slice(&[4, flags, 0, 0]);
+    encode_string(client_id.as_bytes(), &mut body);
+    for field in [user, password].into_iter().flatten() {
+        encode_string(field.as_bytes(), &mut body);
+    }
+    packet(0x10, &body)
+}
+
+pub fn publish_packet(topic: &str, payload: &[u8], retain: bool) -> Vec<u8> {
+    let mut body = Vec::new();
+    encode_string(topic.as_bytes(), &mut body);
+    body.extend_from_slice(payload);
+    packet(if retain { 0x31 } else { 0x30 }, &body)
+}
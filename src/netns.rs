@@ -0,0 +1,166 @@
+//! Linux network namespaces: named ones from `ip netns` (`/run/netns`) and the
+//! anonymous ones that containers and sandboxed processes run in.
+
+use std::collections::HashMap;
+use std::io;
+use crate::models::NetworkInfo;
+
+/// Interfaces of every network namespace, or only of the one called `name`.
+pub fn get_namespaced_network_info(name: Option<&str>) -> io::Result<Vec<NetworkInfo>> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::collect(name)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = name;
+        Err(io::Error::new(io::ErrorKind::Unsupported, "network namespaces are only available on Linux"))
+    }
+}
+
+/// Parses the per-interface counters in `/proc/<pid>/net/dev`.
+pub fn parse_net_dev(text: &str, namespace: &str) -> Vec<NetworkInfo> {
+    text.lines().skip(2).filter_map(|line| {
+        let (interface, counters) = line.split_once(':')?;
+        let fields: Vec<u64> = counters.split_whitespace().filter_map(|f| f.parse().ok()).collect();
+        // Receive counters come first (bytes, packets, errors, drops at 0-3), then
+        // transmit (the same at 8-11).
+        Some(NetworkInfo {
+            interface: interface.trim().to_string(),
+            received: *fields.first()?,
+            transmitted: *fields.get(8)?,
+            packets_received: *fields.get(1)?,
+            packets_transmitted: *fields.get(9)?,
+            errors_received: *fields.get(2)?,
+            errors_transmitted: *fields.get(10)?,
+            drops_received: Some(*fields.get(3)?),
+            drops_transmitted: Some(*fields.get(11)?),
+            namespace: Some(namespace.to_string()),
+            ..Default::default()
+        })
+    }).collect()
+}
+
+/// Receive and transmit drop counters per interface of this process's namespace, which
+/// sysinfo doesn't report. Empty where `/proc/net/dev` doesn't exist.
+pub fn interface_drops() -> HashMap<String, (Option<u64>, Option<u64>)> {
+    let text = std::fs::read_to_string("/proc/net/dev").unwrap_or_default();
+    parse_net_dev(&text, "").into_iter().map(|net| (net.interface, (net.drops_received, net.drops_transmitted))).collect()
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::BTreeMap;
+    use std::fs::{self, File};
+    use std::io;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::PathBuf;
+    use std::thread;
+    use crate::collector::{get_network_info, natural_cmp};
+    use crate::models::NetworkInfo;
+    use super::parse_net_dev;
+
+    const NAMED_DIR: &str = "/run/netns";
+
+    struct Namespace {
+        name: String,
+        /// Lowest pid running in the namespace, if any.
+        pid: Option<u32>,
+        /// Bind-mounted namespace file for named namespaces.
+        path: Option<PathBuf>,
+    }
+
+    fn inode(path: &str) -> Option<u64> {
+        fs::metadata(path).ok().map(|m| m.ino())
+    }
+
+    fn process_name(pid: u32) -> String {
+        fs::read_to_string(format!("/proc/{}/comm", pid)).map(|c| c.trim().to_string()).unwrap_or_default()
+    }
+
+    /// Every namespace visible to us, keyed by inode. Unnamed ones are called `root`
+    /// (init's), `current` (ours), or after the lowest pid running in them.
+    fn namespaces(own: Option<u64>) -> BTreeMap<u64, Namespace> {
+        let mut found: BTreeMap<u64, Namespace> = BTreeMap::new();
+        if let Ok(entries) = fs::read_dir(NAMED_DIR) {
+            for entry in entries.flatten() {
+                if let Ok(meta) = entry.metadata() {
+                    found.insert(meta.ino(), Namespace {
+                        name: entry.file_name().to_string_lossy().into_owned(),
+                        pid: None,
+                        path: Some(entry.path()),
+                    });
+                }
+            }
+        }
+        let mut pids: Vec<u32> = fs::read_dir("/proc").into_iter().flatten().flatten()
+            .filter_map(|e| e.file_name().to_str()?.parse().ok())
+            .collect();
+        pids.sort_unstable();
+        for pid in pids {
+            let Some(ino) = inode(&format!("/proc/{}/ns/net", pid)) else { continue };
+            let ns = found.entry(ino).or_insert_with(|| Namespace {
+                name: format!("pid {} ({})", pid, process_name(pid)),
+                pid: None,
+                path: None,
+            });
+            ns.pid.get_or_insert(pid);
+        }
+        if let Some(ns) = inode("/proc/1/ns/net").and_then(|ino| found.get_mut(&ino)) && ns.path.is_none() {
+            ns.name = "root".to_string();
+        } else if let Some(ns) = own.and_then(|ino| found.get_mut(&ino)) && ns.path.is_none() {
+            ns.name = "current".to_string();
+        }
+        found
+    }
+
+    /// Reads `net/dev` from inside a namespace nobody is running in by joining it on a
+    /// short-lived thread, which needs CAP_SYS_ADMIN.
+    fn read_net_dev_in(path: &PathBuf) -> io::Result<String> {
+        let file = File::open(path)?;
+        thread::spawn(move || {
+            // SAFETY: setns only affects this thread, which exits right after the read.
+            if unsafe { libc::setns(file.as_raw_fd(), libc::CLONE_NEWNET) } != 0 {
+                return Err(io::Error::last_os_error());
+            }
+            fs::read_to_string("/proc/thread-self/net/dev")
+        }).join().unwrap_or_else(|_| Err(io::Error::other("namespace reader thread panicked")))
+    }
+
+    pub fn collect(name: Option<&str>) -> io::Result<Vec<NetworkInfo>> {
+        let own = inode("/proc/self/ns/net");
+        let mut namespaces = namespaces(own);
+        if let Some(name) = name {
+            namespaces.retain(|_, ns| ns.name == name);
+            if namespaces.is_empty() {
+                return Err(io::Error::new(io::ErrorKind::NotFound, format!("no network namespace named `{}`", name)));
+            }
+        }
+
+        let mut info = Vec::new();
+        for (ino, ns) in namespaces {
+            if Some(ino) == own {
+                info.extend(get_network_info().into_iter().map(|mut net| {
+                    net.namespace = Some(ns.name.clone());
+                    net
+                }));
+                continue;
+            }
+            let text = match (ns.pid, &ns.path) {
+                (Some(pid), _) => fs::read_to_string(format!("/proc/{}/net/dev", pid)),
+                (None, Some(path)) => read_net_dev_in(path),
+                (None, None) => continue,
+            };
+            match text {
+                Ok(text) => info.extend(parse_net_dev(&text, &ns.name)),
+                Err(e) => eprintln!("Error reading network namespace {}: {}", ns.name, e),
+            }
+        }
+        info.sort_by(|a, b| {
+            natural_cmp(a.namespace.as_deref().unwrap_or(""), b.namespace.as_deref().unwrap_or(""))
+                .then_with(|| natural_cmp(&a.interface, &b.interface))
+        });
+        Ok(info)
+    }
+}
@@ -0,0 +1,180 @@
+//! `--otlp-endpoint`: metrics exported to an OpenTelemetry collector over OTLP/HTTP in
+//! its JSON encoding, with the machine described by resource attributes. Collectors
+//! take this on their HTTP receiver (port 4318); OTLP/gRPC isn't spoken.
+
+use std::io;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde_json::{json, Value};
+use sysinfo::System;
+use crate::prometheus::Exposition;
+
+/// The metrics signal's path under a collector's base URL.
+const METRICS_PATH: &str = "/v1/metrics";
+
+/// The URL to POST to: `endpoint` as given when it already ends in `/v1/metrics`, else
+/// with that appended, the way `OTEL_EXPORTER_OTLP_ENDPOINT` is treated.
+pub fn metrics_url(endpoint: &str) -> String {
+    let endpoint = endpoint.trim_end_matches('/');
+    if endpoint.ends_with(METRICS_PATH) {
+        endpoint.to_string()
+    } else {
+        format!("{}{}", endpoint, METRICS_PATH)
+    }
+}
+
+/// The `os.type` semantic convention's name for `os`, as in [`std::env::consts::OS`].
+pub fn os_type(os: &str) -> &str {
+    match os {
+        "macos" => "darwin",
+        "dragonfly" => "dragonflybsd",
+        "illumos" => "solaris",
+        other => other,
+    }
+}
+
+/// `key=value` pairs separated by commas, as in `OTEL_RESOURCE_ATTRIBUTES` and
+/// `OTEL_EXPORTER_OTLP_HEADERS`, with values percent-decoded. Malformed pairs are skipped.
+pub fn parse_pairs(value: &str) -> Vec<(String, String)> {
+    value.split(',').filter_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        let key = key.trim();
+        (!key.is_empty()).then(|| (key.to_string(), percent_decode(value.trim())))
+    }).collect()
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = (bytes[i] == b'%').then(|| value.get(i + 1..i + 3)).flatten().and_then(|h| u8::from_str_radix(h, 16).ok());
+        match hex {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// What describes this machine to the collector: `host.name`, `os.type`, `os.version`,
+/// `service.name`, and `service.version`, then `extra` (from `OTEL_RESOURCE_ATTRIBUTES`),
+/// which replaces any of them it repeats.
+pub fn resource_attributes(extra: &[(String, String)]) -> Vec<(String, String)> {
+    let mut attributes: Vec<(String, String)> = [
+        ("host.name", System::host_name()),
+        ("os.type", Some(os_type(std::env::consts::OS).to_string())),
+        ("os.version", System::os_version()),
+        ("service.name", Some(env!("CARGO_PKG_NAME").to_string())),
+        ("service.version", Some(env!("CARGO_PKG_VERSION").to_string())),
+    ].into_iter().filter_map(|(key, value)| Some((key.to_string(), value?))).collect();
+    for (key, value) in extra {
+        attributes.retain(|(k, _)| k != key);
+        attributes.push((key.clone(), value.clone()));
+    }
+    attributes
+}
+
+fn attributes<'a>(pairs: impl Iterator<Item = (&'a str, &'a str)>) -> Value {
+    pairs.map(|(key, value)| json!({"key": key, "value": {"stringValue": value}})).collect()
+}
+
+/// The UCUM unit OTLP expects, from the metric name's suffix.
+fn unit(name: &str) -> &'static str {
+    if name.contains("_percent") {
+        "%"
+    } else if name.contains("_bytes") {
+        "By"
+    } else if name.contains("_seconds") {
+        "s"
+    } else if name.contains("_celsius") {
+        "Cel"
+    } else {
+        ""
+    }
+}
+
+/// An `ExportMetricsServiceRequest` in OTLP's JSON encoding, which writes 64-bit
+/// integers as strings. Gauges stay gauges; counters become cumulative, monotonic sums
+/// counted since `start`. Times are in nanoseconds since the epoch; samples that
+/// aren't finite numbers are left out, since JSON can't carry them.
+pub fn metrics_request(metrics: &Exposition, resource: &[(String, String)], time: u64, start: u64) -> Value {
+    let metrics: Vec<Value> = metrics.families().filter_map(|(name, help, kind, samples)| {
+        let points: Vec<Value> = samples.iter().filter(|(_, value)| value.is_finite()).map(|(labels, value)| {
+            let mut point = json!({
+                "attributes": attributes(labels.iter().map(|(k, v)| (k.as_str(), v.as_str()))),
+                "timeUnixNano": time.to_string(),
+                "asDouble": value,
+            });
+            if kind == "counter" {
+                point["startTimeUnixNano"] = json!(start.to_string());
+            }
+            point
+        }).collect();
+        if points.is_empty() {
+            return None;
+        }
+        let mut metric = json!({"name": name, "description": help, "unit": unit(name)});
+        if kind == "counter" {
+            // 2 is AGGREGATION_TEMPORALITY_CUMULATIVE.
+            metric["sum"] = json!({"dataPoints": points, "aggregationTemporality": 2, "isMonotonic": true});
+        } else {
+            metric["gauge"] = json!({"dataPoints": points});
+        }
+        Some(metric)
+    }).collect();
+    json!({
+        "resourceMetrics": [{
+            "resource": {"attributes": attributes(resource.iter().map(|(k, v)| (k.as_str(), v.as_str())))},
+            "scopeMetrics": [{
+                "scope": {"name": env!("CARGO_PKG_NAME"), "version": env!("CARGO_PKG_VERSION")},
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+pub struct Exporter {
+    agent: ureq::Agent,
+    url: String,
+    /// Sent with every request, e.g. an API key, from `OTEL_EXPORTER_OTLP_HEADERS`.
+    headers: Vec<(String, String)>,
+    resource: Vec<(String, String)>,
+    /// When the counters started counting: the last boot, in nanoseconds.
+    start: u64,
+}
+
+impl Exporter {
+    /// An exporter to the collector at `endpoint`, with headers and extra resource
+    /// attributes from the usual `OTEL_*` environment variables.
+    pub fn new(endpoint: &str) -> Self {
+        let config = ureq::Agent::config_builder().timeout_global(Some(Duration::from_secs(10))).build();
+        let env_pairs = |name: &str| std::env::var(name).map(|v| parse_pairs(&v)).unwrap_or_default();
+        Exporter {
+            agent: config.into(),
+            url: metrics_url(endpoint),
+            headers: env_pairs("OTEL_EXPORTER_OTLP_HEADERS"),
+            resource: resource_attributes(&env_pairs("OTEL_RESOURCE_ATTRIBUTES")),
+            start: System::boot_time().saturating_mul(1_000_000_000),
+        }
+    }
+
+    /// POSTs `metrics`, unless there are none. Any status but 2xx is an error.
+    pub fn export(&self, metrics: &Exposition) -> io::Result<()> {
+        if metrics.samples().next().is_none() {
+            return Ok(());
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+        let mut request = self.agent.post(self.url.as_str()).header("Content-Type", "application/json");
+        for (name, value) in &self.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request.send(metrics_request(metrics, &self.resource, now, self.start).to_string()).map_err(io::Error::other)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+//! Writing results to stdout or to the `--output` file (or both, with `--tee`), with
+//! appending, size-based rotation, compression, and `{time}` placeholders in the file
+//! name.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+use crate::args::Compression;
+use crate::error::{Error, Result};
+use crate::fmt::strip_ansi;
+
+pub struct Output {
+    file: Option<FileSink>,
+    /// Print to stdout as well as writing the file (`--tee`).
+    tee: bool,
+    /// Set once a write fails; the run carries on but exits with an error.
+    failed: bool,
+    /// Report write errors as JSON (`--json`).
+    json: bool,
+}
+
+struct FileSink {
+    template: String,
+    /// Keep adding to the file instead of replacing it on every write (`--append`).
+    append: bool,
+    max_size: Option<u64>,
+    rotate: usize,
+    compression: Option<Compression>,
+    /// The file being appended to; `None` until the first write and after a rotation.
+    current: Option<PathBuf>,
+}
+
+impl Output {
+    /// `template` is the `--output` path; without one, or with `tee`, everything goes to stdout.
+    pub fn new(template: Option<&str>, append: bool, max_size: Option<u64>, rotate: usize, compress: Option<Compression>, tee: bool, json: bool) -> Self {
+        Output {
+            file: template.map(|template| FileSink {
+                template: template.to_string(),
+                append,
+                max_size,
+                rotate,
+                compression: compression(template, compress),
+                current: None,
+            }),
+            tee,
+            failed: false,
+            json,
+        }
+    }
+
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Prints `text`, or writes it to the output file, or both with `--tee`; the file
+    /// never gets colors. With `append` (event streams) or `--append`, the text is added
+    /// after what the last call wrote instead of replacing it. A failed file write is
+    /// reported and the run goes on; failing to print ends it, since there's no one left
+    /// to print for.
+    pub fn emit(&mut self, text: &str, append: bool) -> Result<()> {
+        if let Some(sink) = &mut self.file {
+            let written = if self.tee { sink.write(&strip_ansi(text), append) } else { sink.write(text, append) };
+            if let Err(e) = written {
+                let error = Error::io(format!("writing to {}", sink.template), e);
+                eprintln!("{}", error.report(self.json).unwrap_or_default());
+                self.failed = true;
+            }
+        }
+        if self.file.is_none() || self.tee {
+            writeln!(io::stdout(), "{}", text).map_err(|e| Error::io("writing to stdout", e))?;
+        }
+        Ok(())
+    }
+}
+
+impl FileSink {
+    fn write(&mut self, text: &str, append: bool) -> io::Result<()> {
+        let append = append || self.append;
+        if !append {
+            let mut file = File::create(expand_time(&self.template, SystemTime::now()))?;
+            return file.write_all(&compress(text.as_bytes(), self.compression)?);
+        }
+        let mut text = text.to_string();
+        if self.append && !text.ends_with('\n') {
+            // Keep iterations apart; event streams already end each record with a newline.
+            text.push('\n');
+        }
+        let data = compress(text.as_bytes(), self.compression)?;
+        if let (Some(max_size), Some(path)) = (self.max_size, &self.current) {
+            let size = fs::metadata(path).map_or(0, |m| m.len());
+            if size > 0 && size + data.len() as u64 > max_size {
+                self.rotate()?;
+            }
+        }
+        let path = self.current.get_or_insert_with(|| expand_time(&self.template, SystemTime::now()));
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(&data)
+    }
+
+    /// Starts a new file. A `{time}` name gets a fresh timestamp; otherwise the current
+    /// file moves to `<name>.1`, older ones shift up, and those beyond `rotate` are removed.
+    fn rotate(&mut self) -> io::Result<()> {
+        let Some(path) = self.current.take() else { return Ok(()) };
+        if self.template.contains("{time}") {
+            return Ok(());
+        }
+        let numbered = |n: usize| PathBuf::from(format!("{}.{}", path.display(), n));
+        if self.rotate == 0 {
+            return fs::remove_file(&path);
+        }
+        let _ = fs::remove_file(numbered(self.rotate));
+        for n in (1..self.rotate).rev() {
+            if numbered(n).exists() {
+                fs::rename(numbered(n), numbered(n + 1))?;
+            }
+        }
+        fs::rename(&path, numbered(1))
+    }
+}
+
+/// How to compress the file `template` names: as `compress` says, or by a `.gz` or
+/// `.zst` extension, or not at all.
+pub fn compression(template: &str, compress: Option<Compression>) -> Option<Compression> {
+    let lower = template.to_ascii_lowercase();
+    compress.or(if lower.ends_with(".gz") {
+        Some(Compression::Gzip)
+    } else if lower.ends_with(".zst") {
+        Some(Compression::Zstd)
+    } else {
+        None
+    })
+}
+
+/// `data` as one complete gzip member or zstd frame. Both formats decode a file of
+/// several as their concatenation, so appended writes are readable with `zcat` or
+/// `zstdcat` even if the run is cut short.
+pub fn compress(data: &[u8], compression: Option<Compression>) -> io::Result<Vec<u8>> {
+    match compression {
+        None => Ok(data.to_vec()),
+        Some(Compression::Gzip) => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(data)?;
+            encoder.finish()
+        }
+        Some(Compression::Zstd) => zstd::encode_all(data, 0),
+    }
+}
+
+/// Replaces `{time}` in `template` with a compact UTC timestamp such as `20261016T130524Z`.
+pub fn expand_time(template: &str, now: SystemTime) -> PathBuf {
+    let stamp: String = humantime::format_rfc3339_seconds(now).to_string().chars().filter(|c| *c != '-' && *c != ':').collect();
+    PathBuf::from(template.replace("{time}", &stamp))
+}
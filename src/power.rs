@@ -0,0 +1,34 @@
+//! Power supplies (AC adapters and batteries) from `/sys/class/power_supply` on Linux.
+
+use std::fs;
+
+/// Whether any supply is a battery, as on a laptop.
+pub fn has_battery() -> bool {
+    fs::read_dir("/sys/class/power_supply").is_ok_and(|entries| {
+        entries.flatten().any(|entry| fs::read_to_string(entry.path().join("type")).is_ok_and(|t| t.trim() == "Battery"))
+    })
+}
+
+/// Each supply's name and state: `online`/`offline` for adapters, the charge status
+/// (e.g. `Charging`, `Discharging`, `Full`) plus capacity for batteries.
+pub fn power_supplies() -> Vec<(String, String)> {
+    let Ok(entries) = fs::read_dir("/sys/class/power_supply") else {
+        return Vec::new();
+    };
+    let mut supplies: Vec<(String, String)> = entries.flatten().filter_map(|entry| {
+        let read = |file: &str| fs::read_to_string(entry.path().join(file)).ok().map(|s| s.trim().to_string());
+        let state = match read("type")?.as_str() {
+            "Battery" => match read("capacity") {
+                Some(capacity) => format!("{} {}%", read("status")?, capacity),
+                None => read("status")?,
+            },
+            _ => match read("online")?.as_str() {
+                "1" => "online".to_string(),
+                _ => "offline".to_string(),
+            },
+        };
+        Some((entry.file_name().to_string_lossy().into_owned(), state))
+    }).collect();
+    supplies.sort();
+    supplies
+}
@@ -0,0 +1,443 @@
+//! Prometheus text exposition format, e.g. for node_exporter's textfile collector.
+
+use sysinfo::System;
+use crate::collector::{get_cpu_info, get_disks_info, get_network_info};
+use crate::models::*;
+
+/// Metric families in the order they were first written, each with its samples.
+#[derive(Default)]
+pub struct Exposition {
+    families: Vec<Family>,
+}
+
+/// A sample's labels and value.
+pub type Sample = (Vec<(String, String)>, f64);
+
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    kind: &'static str,
+    samples: Vec<Sample>,
+}
+
+impl Exposition {
+    pub fn gauge(&mut self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.sample(name, help, "gauge", labels, value);
+    }
+
+    pub fn counter(&mut self, name: &'static str, help: &'static str, labels: &[(&str, &str)], value: f64) {
+        self.sample(name, help, "counter", labels, value);
+    }
+
+    fn sample(&mut self, name: &'static str, help: &'static str, kind: &'static str, labels: &[(&str, &str)], value: f64) {
+        let labels = labels.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let family = match self.families.iter().position(|f| f.name == name) {
+            Some(i) => &mut self.families[i],
+            None => {
+                self.families.push(Family { name, help, kind, samples: Vec::new() });
+                self.families.last_mut().expect("just pushed")
+            }
+        };
+        family.samples.push((labels, value));
+    }
+
+    pub fn render(&self) -> String {
+        let mut s = String::new();
+        for family in &self.families {
+            s.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n", family.name, family.help, family.name, family.kind));
+            for (labels, value) in &family.samples {
+                let labels = if labels.is_empty() {
+                    String::new()
+                } else {
+                    let pairs: Vec<String> = labels.iter().map(|(k, v)| format!("{}=\"{}\"", k, escape_label(v))).collect();
+                    format!("{{{}}}", pairs.join(","))
+                };
+                s.push_str(&format!("{}{} {}\n", family.name, labels, value));
+            }
+        }
+        s
+    }
+
+    /// Every family as its name, help text, type (`gauge` or `counter`), and samples.
+    pub fn families(&self) -> impl Iterator<Item = (&'static str, &'static str, &'static str, &[Sample])> {
+        self.families.iter().map(|f| (f.name, f.help, f.kind, f.samples.as_slice()))
+    }
+
+    /// Every sample as its metric name, labels, and value, in exposition order.
+    pub fn samples(&self) -> impl Iterator<Item = (&'static str, &[(String, String)], f64)> {
+        self.families.iter().flat_map(|f| f.samples.iter().map(|(labels, value)| (f.name, labels.as_slice(), *value)))
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Models that can be exposed as Prometheus metrics.
+pub trait ToMetrics {
+    fn write_metrics(&self, _out: &mut Exposition) {}
+}
+
+/// Renders `info` as a complete exposition.
+pub fn format_prometheus<T: ToMetrics + ?Sized>(info: &T) -> String {
+    let mut out = Exposition::default();
+    info.write_metrics(&mut out);
+    out.render()
+}
+
+/// The summary scrape: `system` and `mem` plus everything else cheap to collect in one pass.
+pub fn scrape(sys: &System, system: &SystemInfo, mem: &MemoryInfo) -> String {
+    collect(sys, system, mem).render()
+}
+
+/// The metrics behind [`scrape`], also sent by `push`.
+pub fn collect(sys: &System, system: &SystemInfo, mem: &MemoryInfo) -> Exposition {
+    let mut out = Exposition::default();
+    system.write_metrics(&mut out);
+    get_cpu_info(sys).write_metrics(&mut out);
+    mem.write_metrics(&mut out);
+    get_disks_info(true).write_metrics(&mut out);
+    get_network_info().write_metrics(&mut out);
+    out
+}
+
+impl<T: ToMetrics> ToMetrics for Vec<T> {
+    fn write_metrics(&self, out: &mut Exposition) {
+        self.iter().for_each(|item| item.write_metrics(out));
+    }
+}
+
+impl<T: ToMetrics + ?Sized> ToMetrics for &T {
+    fn write_metrics(&self, out: &mut Exposition) {
+        (**self).write_metrics(out);
+    }
+}
+
+impl ToMetrics for SystemInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let field = |v: &Option<String>| v.clone().unwrap_or_default();
+        out.gauge("sysinfo_system_info", "Operating system details as labels; always 1.", &[
+            ("name", &field(&self.name)),
+            ("kernel_version", &field(&self.kernel_version)),
+            ("os_version", &field(&self.os_version)),
+            ("host_name", &field(&self.host_name)),
+        ], 1.0);
+        out.gauge("sysinfo_uptime_seconds", "Time since boot.", &[], self.uptime as f64);
+        out.gauge("sysinfo_boot_time_seconds", "Unix time of the last boot.", &[], self.boot_time as f64);
+        if let Some(load) = &self.load_average {
+            load.write_metrics(out);
+        }
+    }
+}
+
+impl ToMetrics for UptimeInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_uptime_seconds", "Time since boot.", &[], self.uptime as f64);
+        out.gauge("sysinfo_boot_time_seconds", "Unix time of the last boot.", &[], self.boot_time as f64);
+    }
+}
+
+impl ToMetrics for AllReport {
+    fn write_metrics(&self, out: &mut Exposition) {
+        self.system.write_metrics(out);
+        self.cpu.write_metrics(out);
+        self.memory.write_metrics(out);
+        self.disks.write_metrics(out);
+        self.network.write_metrics(out);
+        self.components.write_metrics(out);
+        self.processes.write_metrics(out);
+    }
+}
+
+impl ToMetrics for Snapshot {
+    fn write_metrics(&self, out: &mut Exposition) {
+        self.system.write_metrics(out);
+        self.cpu.write_metrics(out);
+        self.memory.write_metrics(out);
+        self.disks.write_metrics(out);
+        self.network.write_metrics(out);
+        self.processes.write_metrics(out);
+    }
+}
+
+impl ToMetrics for HostStatus {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let labels = [("host", self.address.as_str())];
+        out.gauge("sysinfo_host_up", "Whether the host's agent answered.", &labels, if self.error.is_none() { 1.0 } else { 0.0 });
+        if let Some(cpu) = self.cpu_usage {
+            out.gauge("sysinfo_host_cpu_usage_percent", "CPU usage per host.", &labels, f64::from(cpu));
+        }
+        if let Some(memory) = self.memory_percent {
+            out.gauge("sysinfo_host_memory_used_percent", "Memory in use per host.", &labels, f64::from(memory));
+        }
+        if let Some(load) = self.load_average {
+            out.gauge("sysinfo_host_load1", "One-minute load average per host.", &labels, load);
+        }
+        out.gauge("sysinfo_host_disk_alerts", "Disks at least 90% full per host.", &labels, self.disk_alerts.len() as f64);
+    }
+}
+
+impl ToMetrics for LoadAverage {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_load1", "One-minute load average.", &[], self.one);
+        out.gauge("sysinfo_load5", "Five-minute load average.", &[], self.five);
+        out.gauge("sysinfo_load15", "Fifteen-minute load average.", &[], self.fifteen);
+    }
+}
+
+impl ToMetrics for LoadReport {
+    fn write_metrics(&self, out: &mut Exposition) {
+        self.load_average.write_metrics(out);
+        out.gauge("sysinfo_cpu_count", "Number of logical CPUs.", &[], self.nb_cpus as f64);
+    }
+}
+
+impl ToMetrics for CpuInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_cpu_count", "Number of logical CPUs.", &[], self.nb_cpus as f64);
+        if let Some(cores) = self.physical_cores {
+            out.gauge("sysinfo_cpu_physical_cores", "Number of physical cores.", &[], cores as f64);
+        }
+        out.gauge("sysinfo_cpu_total_usage_percent", "Usage across all CPUs.", &[], f64::from(self.total_usage));
+        for cpu in &self.cpus {
+            let id = cpu.id.to_string();
+            out.gauge("sysinfo_cpu_usage_percent", "Usage per logical CPU.", &[("cpu", &id)], f64::from(cpu.usage));
+            if cpu.frequency > 0 {
+                out.gauge("sysinfo_cpu_frequency_hertz", "Current frequency per logical CPU.", &[("cpu", &id)], cpu.frequency as f64 * 1e6);
+            }
+            if let Some(b) = &cpu.breakdown {
+                let modes = [
+                    ("user", b.user), ("nice", b.nice), ("system", b.system), ("idle", b.idle),
+                    ("iowait", b.iowait), ("irq", b.irq), ("softirq", b.softirq), ("steal", b.steal),
+                ];
+                for (mode, pct) in modes {
+                    out.gauge("sysinfo_cpu_mode_percent", "Share of time per logical CPU and mode.", &[("cpu", &id), ("mode", mode)], f64::from(pct));
+                }
+            }
+        }
+        if let Some(volts) = self.core_voltage {
+            out.gauge("sysinfo_cpu_core_voltage_volts", "SoC core voltage.", &[], f64::from(volts));
+        }
+    }
+}
+
+impl ToMetrics for MemoryInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_memory_total_bytes", "Total RAM.", &[], self.total_memory as f64);
+        out.gauge("sysinfo_memory_used_bytes", "Used RAM.", &[], self.used_memory as f64);
+        out.gauge("sysinfo_memory_available_bytes", "RAM available without swapping.", &[], self.available_memory as f64);
+        out.gauge("sysinfo_memory_free_bytes", "Unused RAM.", &[], self.free_memory as f64);
+        if let Some(buffers) = self.buffers {
+            out.gauge("sysinfo_memory_buffers_bytes", "Kernel buffers.", &[], buffers as f64);
+        }
+        if let Some(cached) = self.cached {
+            out.gauge("sysinfo_memory_cached_bytes", "Page cache and reclaimable slabs.", &[], cached as f64);
+        }
+        out.gauge("sysinfo_swap_total_bytes", "Total swap.", &[], self.total_swap as f64);
+        out.gauge("sysinfo_swap_used_bytes", "Used swap.", &[], self.used_swap as f64);
+        if let Some(committed) = self.committed_memory {
+            out.gauge("sysinfo_memory_committed_bytes", "Committed memory.", &[], committed as f64);
+        }
+        if let Some(limit) = self.commit_limit {
+            out.gauge("sysinfo_memory_commit_limit_bytes", "Commit limit.", &[], limit as f64);
+        }
+        if let Some(cgroup) = &self.cgroup {
+            out.gauge("sysinfo_cgroup_memory_limit_bytes", "Memory limit of the cgroup.", &[], cgroup.limit as f64);
+            out.gauge("sysinfo_cgroup_memory_used_bytes", "Memory charged to the cgroup.", &[], cgroup.used as f64);
+        }
+    }
+}
+
+impl ToMetrics for DiskInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let labels = [("disk", self.name.as_str()), ("mountpoint", self.mount_point.as_str()), ("fs", self.file_system.as_str()), ("kind", self.kind.as_str())];
+        out.gauge("sysinfo_disk_available_bytes", "Available space per disk.", &labels, self.available_space as f64);
+        out.gauge("sysinfo_disk_total_bytes", "Total space per disk.", &labels, self.total_space as f64);
+        out.gauge("sysinfo_disk_read_only", "Whether the disk is mounted read-only (1) or not (0).", &labels, self.is_read_only as u8 as f64);
+        out.gauge("sysinfo_disk_used_percent", "Share of the total space not available per disk.", &labels, self.used_percent as f64);
+        if let Some(read) = self.read_bytes {
+            out.counter("sysinfo_disk_read_bytes_total", "Bytes read per disk.", &labels, read as f64);
+        }
+        if let Some(written) = self.written_bytes {
+            out.counter("sysinfo_disk_written_bytes_total", "Bytes written per disk.", &labels, written as f64);
+        }
+        if let Some(queue) = self.queue_length {
+            out.gauge("sysinfo_disk_queue_length", "Current disk queue length.", &labels, queue);
+        }
+    }
+}
+
+impl ToMetrics for NetworkInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let mut labels = vec![("interface", self.interface.as_str())];
+        if let Some(namespace) = &self.namespace {
+            labels.push(("namespace", namespace));
+        }
+        out.counter("sysinfo_network_received_bytes_total", "Bytes received per interface.", &labels, self.received as f64);
+        out.counter("sysinfo_network_transmitted_bytes_total", "Bytes transmitted per interface.", &labels, self.transmitted as f64);
+        out.counter("sysinfo_network_received_packets_total", "Packets received per interface.", &labels, self.packets_received as f64);
+        out.counter("sysinfo_network_transmitted_packets_total", "Packets transmitted per interface.", &labels, self.packets_transmitted as f64);
+        out.counter("sysinfo_network_receive_errors_total", "Receive errors per interface.", &labels, self.errors_received as f64);
+        out.counter("sysinfo_network_transmit_errors_total", "Transmit errors per interface.", &labels, self.errors_transmitted as f64);
+        if let Some(drops) = self.drops_received {
+            out.counter("sysinfo_network_receive_drops_total", "Received packets dropped per interface.", &labels, drops as f64);
+        }
+        if let Some(drops) = self.drops_transmitted {
+            out.counter("sysinfo_network_transmit_drops_total", "Transmitted packets dropped per interface.", &labels, drops as f64);
+        }
+        if let Some(mtu) = self.mtu {
+            out.gauge("sysinfo_network_mtu_bytes", "MTU per interface.", &labels, mtu as f64);
+        }
+    }
+}
+
+impl ToMetrics for ComponentInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let labels = [("label", self.label.as_str())];
+        if let Some(t) = self.temperature {
+            out.gauge("sysinfo_component_temperature_celsius", "Temperature per sensor.", &labels, f64::from(t));
+        }
+        if let Some(critical) = self.critical {
+            out.gauge("sysinfo_component_critical_celsius", "Critical temperature per sensor.", &labels, f64::from(critical));
+        }
+    }
+}
+
+impl ToMetrics for ContainerInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        if let Some(limit) = self.cpu_limit {
+            out.gauge("sysinfo_container_cpu_limit_cpus", "CPUs the cgroup quota allows.", &[], limit);
+        }
+        if let Some(used) = self.cpus_used {
+            out.gauge("sysinfo_container_cpus_used", "CPUs the cgroup used over the sample.", &[], used);
+        }
+        if let Some(limit) = self.memory_limit {
+            out.gauge("sysinfo_container_memory_limit_bytes", "Memory limit of the cgroup.", &[], limit as f64);
+        }
+        if let Some(used) = self.memory_used {
+            out.gauge("sysinfo_container_memory_used_bytes", "Memory charged to the cgroup.", &[], used as f64);
+        }
+    }
+}
+
+impl ToMetrics for DockerContainer {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let labels = [("container", self.name.as_str()), ("image", self.image.as_str())];
+        if let Some(cpu) = self.cpu_usage {
+            out.gauge("sysinfo_docker_cpu_usage_percent", "CPU usage per container, 100 per CPU.", &labels, f64::from(cpu));
+        }
+        if let Some(memory) = self.memory_usage {
+            out.gauge("sysinfo_docker_memory_usage_bytes", "Memory in use per container.", &labels, memory as f64);
+        }
+        if let Some(limit) = self.memory_limit {
+            out.gauge("sysinfo_docker_memory_limit_bytes", "Memory limit per container.", &labels, limit as f64);
+        }
+        if let Some(received) = self.network_received {
+            out.counter("sysinfo_docker_network_received_bytes_total", "Bytes received per container.", &labels, received as f64);
+        }
+        if let Some(transmitted) = self.network_transmitted {
+            out.counter("sysinfo_docker_network_transmitted_bytes_total", "Bytes transmitted per container.", &labels, transmitted as f64);
+        }
+        if let Some(read) = self.block_read {
+            out.counter("sysinfo_docker_block_read_bytes_total", "Bytes read from block devices per container.", &labels, read as f64);
+        }
+        if let Some(written) = self.block_written {
+            out.counter("sysinfo_docker_block_written_bytes_total", "Bytes written to block devices per container.", &labels, written as f64);
+        }
+    }
+}
+
+impl ToMetrics for K8sNodeReport {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_k8s_node_cpu_cores", "CPU in use on the node, in cores.", &[], self.cpu_cores);
+        out.gauge("sysinfo_k8s_node_memory_bytes", "Memory in use on the node.", &[], self.memory_bytes as f64);
+        // Summed per container, as per-process series would churn.
+        for container in self.processes.chunk_by(|a, b| (&a.pod_uid, &a.container_id) == (&b.pod_uid, &b.container_id)) {
+            let first = &container[0];
+            let labels = [
+                ("namespace", first.namespace.as_deref().unwrap_or_default()),
+                ("pod", first.pod.as_deref().unwrap_or(first.pod_uid.as_str())),
+                ("container_id", first.container_id.as_deref().unwrap_or_default()),
+            ];
+            let cpu: f32 = container.iter().map(|p| p.cpu_usage).sum();
+            let memory: u64 = container.iter().map(|p| p.memory).sum();
+            out.gauge("sysinfo_k8s_container_cpu_usage_percent", "CPU usage of a pod container's processes, 100 per CPU.", &labels, f64::from(cpu));
+            out.gauge("sysinfo_k8s_container_memory_bytes", "Resident memory of a pod container's processes.", &labels, memory as f64);
+        }
+    }
+}
+
+impl ToMetrics for SensorReading {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let labels = [("chip", self.chip.as_str()), ("device", self.device.as_deref().unwrap_or_default()), ("sensor", self.label.as_str())];
+        match self.kind {
+            SensorKind::Temperature => out.gauge("sysinfo_sensor_temperature_celsius", "Temperature per hwmon sensor.", &labels, self.value),
+            SensorKind::Fan => out.gauge("sysinfo_sensor_fan_rpm", "Fan speed per hwmon sensor.", &labels, self.value),
+            SensorKind::Voltage => out.gauge("sysinfo_sensor_voltage_volts", "Voltage per hwmon sensor.", &labels, self.value),
+            SensorKind::Current => out.gauge("sysinfo_sensor_current_amperes", "Current per hwmon sensor.", &labels, self.value),
+            SensorKind::Power => out.gauge("sysinfo_sensor_power_watts", "Power per hwmon sensor.", &labels, self.value),
+        }
+    }
+}
+
+impl ToMetrics for ProcessInfo {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let pid = self.pid.to_string();
+        let labels = [("pid", pid.as_str()), ("name", self.name.as_str())];
+        out.gauge("sysinfo_process_cpu_usage_percent", "CPU usage per process.", &labels, f64::from(self.cpu_usage));
+        out.gauge("sysinfo_process_memory_bytes", "Resident memory per process.", &labels, self.memory as f64);
+        out.gauge("sysinfo_process_run_time_seconds", "Time since the process started.", &labels, self.run_time as f64);
+    }
+}
+
+impl ToMetrics for KernelResources {
+    fn write_metrics(&self, out: &mut Exposition) {
+        let optional = [
+            ("sysinfo_file_handles_allocated", "Allocated file handles.", self.file_handles_allocated),
+            ("sysinfo_file_handles_max", "Maximum file handles.", self.file_handles_max),
+            ("sysinfo_epoll_instances", "Open epoll instances.", self.epoll_instances),
+            ("sysinfo_inotify_instances", "Open inotify instances.", self.inotify_instances),
+        ];
+        for (name, help, value) in optional {
+            if let Some(value) = value {
+                out.gauge(name, help, &[], value as f64);
+            }
+        }
+        for socket in &self.sockets {
+            out.gauge("sysinfo_sockets", "Sockets by protocol and state.", &[("protocol", &socket.protocol), ("state", &socket.state)], socket.count as f64);
+        }
+    }
+}
+
+impl ToMetrics for ThermalSample {
+    fn write_metrics(&self, out: &mut Exposition) {
+        out.gauge("sysinfo_cpu_frequency_mhz", "Average CPU frequency.", &[], self.frequency_mhz as f64);
+        if let Some(t) = self.temperature {
+            out.gauge("sysinfo_thermal_temperature_celsius", "Hottest sensor temperature.", &[], f64::from(t));
+        }
+        out.gauge("sysinfo_thermal_throttling", "1 while the CPU appears to be thermally throttled.", &[], if self.throttling { 1.0 } else { 0.0 });
+    }
+}
+
+// Reports without meaningful gauges; `--format prometheus` is rejected for them up front.
+impl ToMetrics for DiskUsageReport {}
+impl ToMetrics for Capability {}
+impl ToMetrics for FetchInfo {}
+impl ToMetrics for ExampleInfo {}
+impl ToMetrics for FileHolder {}
+impl ToMetrics for Connection {}
+impl ToMetrics for PortOwner {}
+impl ToMetrics for AlertResult {}
+impl ToMetrics for HistorySample {}
+impl ToMetrics for SnapshotDiff {}
+impl ToMetrics for KillResult {}
+impl ToMetrics for ProcessDetail {}
+impl ToMetrics for UserInfo {}
+impl ToMetrics for SessionInfo {}
+impl ToMetrics for BenchResult {}
+impl ToMetrics for ThrottleSummary {}
+impl ToMetrics for WatchSummary {}
+impl ToMetrics for SelfStats {}
+impl ToMetrics for StageTiming {}
+impl ToMetrics for Summary<'_> {}
+impl ToMetrics for serde_json::Value {}
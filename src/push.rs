@@ -0,0 +1,126 @@
+//! `push`: sends metrics to an InfluxDB v2 or VictoriaMetrics write endpoint in
+//! InfluxDB line protocol, batching samples and retrying failed POSTs.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use crate::prometheus::Exposition;
+use crate::watch::sleep_unless_stopped;
+
+/// Samples kept while the endpoint is unreachable; older ones are dropped first.
+const MAX_PENDING: usize = 1000;
+/// Wait before the first retry; doubled after every failed attempt.
+const FIRST_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Renders `metrics` as line protocol with one point per sample: the `sysinfo`
+/// measurement, the labels (plus `host`) as tags, and the metric name without its
+/// `sysinfo_` prefix as the field. VictoriaMetrics joins the two back into the
+/// Prometheus name. `timestamp` is in nanoseconds, the protocol's default precision.
+pub fn line_protocol(metrics: &Exposition, host: &str, timestamp: u128) -> String {
+    let mut s = String::new();
+    for (name, labels, value) in metrics.samples() {
+        if !value.is_finite() {
+            continue;
+        }
+        s.push_str("sysinfo");
+        let host = ("host", host);
+        let tags = std::iter::once(host).chain(labels.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        // Empty tag values are invalid, so such labels are left out.
+        for (key, value) in tags.filter(|(_, v)| !v.is_empty()) {
+            s.push_str(&format!(",{}={}", escape_tag(key), escape_tag(value)));
+        }
+        let field = name.strip_prefix("sysinfo_").unwrap_or(name);
+        s.push_str(&format!(" {}={} {}\n", escape_tag(field), value, timestamp));
+    }
+    s
+}
+
+fn escape_tag(value: &str) -> String {
+    value.replace('\\', "\\\\").replace(',', "\\,").replace('=', "\\=").replace(' ', "\\ ").replace('\n', "\\n")
+}
+
+pub struct Pusher {
+    url: String,
+    token: Option<String>,
+    batch: usize,
+    retries: u32,
+    agent: ureq::Agent,
+    /// Line protocol per sample not yet accepted by the endpoint, oldest first.
+    pending: VecDeque<String>,
+}
+
+impl Pusher {
+    pub fn new(url: &str, token: Option<String>, batch: u32, retries: u32) -> Self {
+        let config = ureq::Agent::config_builder().timeout_global(Some(Duration::from_secs(10))).build();
+        Pusher {
+            url: url.to_string(),
+            token,
+            batch: batch as usize,
+            retries,
+            agent: config.into(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn add(&mut self, lines: String) {
+        if self.pending.len() == MAX_PENDING {
+            self.pending.pop_front();
+        }
+        self.pending.push_back(lines);
+    }
+
+    /// Whether a full batch is waiting.
+    pub fn ready(&self) -> bool {
+        self.pending.len() >= self.batch
+    }
+
+    /// POSTs every pending sample in one request and returns how many were sent.
+    /// Network errors and 5xx responses are retried with exponential backoff, until the
+    /// attempts run out or `stop` is set; the samples are then kept for the next flush.
+    /// Any other error response means the endpoint won't take them as they are, so
+    /// they're dropped, and the error says how many.
+    pub fn flush(&mut self, stop: &AtomicBool) -> Result<usize, String> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        let body: String = self.pending.iter().map(String::as_str).collect();
+        let mut backoff = FIRST_BACKOFF;
+        let mut attempt = 1;
+        loop {
+            match self.post(&body) {
+                Ok(()) => {
+                    let count = self.pending.len();
+                    self.pending.clear();
+                    return Ok(count);
+                }
+                Err((e, false)) => {
+                    let dropped = self.pending.len();
+                    self.pending.clear();
+                    return Err(format!("{}; dropped {} samples", e, dropped));
+                }
+                Err((e, true)) => {
+                    if attempt >= self.retries || stop.load(Ordering::SeqCst) {
+                        return Err(e);
+                    }
+                }
+            }
+            sleep_unless_stopped(backoff, stop);
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+            attempt += 1;
+        }
+    }
+
+    /// Sends `body`; on failure, returns the error and whether it's worth retrying.
+    fn post(&self, body: &str) -> Result<(), (String, bool)> {
+        let mut request = self.agent.post(&self.url).header("Content-Type", "text/plain; charset=utf-8");
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", &format!("Token {}", token));
+        }
+        match request.send(body) {
+            Ok(_) => Ok(()),
+            Err(ureq::Error::StatusCode(status)) => Err((format!("HTTP {}", status), status >= 500)),
+            Err(e) => Err((e.to_string(), true)),
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use regex::{Captures, Regex};
+use std::collections::HashMap;
+use std::env;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use sysinfo::{Motherboard, Product, System, Users};
+use crate::models::*;
+
+/// Replaces identifying values (host and user names, serial numbers, IP and MAC
+/// addresses) with pseudonyms such as `host-1` that stay the same for the whole run.
+pub struct Redactor {
+    literals: Vec<(Regex, &'static str)>,
+    mac: Regex,
+    ipv4: Regex,
+    ipv6: Regex,
+    pseudonyms: HashMap<String, String>,
+    counters: HashMap<&'static str, usize>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Redactor {
+    /// Builds a redactor for the identifiers of the current machine and user.
+    pub fn new() -> Self {
+        let mut literals = Vec::new();
+        if let Some(host) = System::host_name() {
+            literals.push((host, "host"));
+        }
+        for var in ["USER", "LOGNAME", "USERNAME"] {
+            if let Ok(user) = env::var(var) && !user.is_empty() && user != "root" {
+                literals.push((user, "user"));
+            }
+        }
+        literals.extend(login_users().into_iter().map(|user| (user, "user")));
+        let serials = [
+            Product::serial_number(),
+            Product::uuid(),
+            Motherboard::new().and_then(|m| m.serial_number()),
+        ];
+        literals.extend(serials.into_iter().flatten().map(|serial| (serial, "serial")));
+        Self::with_literals(literals)
+    }
+
+    /// Builds a redactor for an explicit list of `(value, kind)` identifiers.
+    pub fn with_literals(mut literals: Vec<(String, &'static str)>) -> Self {
+        literals.retain(|(value, _)| !value.trim().is_empty());
+        // Longest first so "myhost.example.com" wins over "myhost".
+        literals.sort_by_key(|(value, _)| std::cmp::Reverse(value.len()));
+        literals.dedup_by(|a, b| a.0.eq_ignore_ascii_case(&b.0));
+        let literals = literals.into_iter().map(|(value, kind)| {
+            let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            let start = if word(value.chars().next()) { r"\b" } else { "" };
+            let end = if word(value.chars().last()) { r"\b" } else { "" };
+            let pattern = format!("(?i){}{}{}", start, regex::escape(&value), end);
+            (Regex::new(&pattern).expect("escaped literal is a valid regex"), kind)
+        }).collect();
+
+        Redactor {
+            literals,
+            mac: Regex::new(r"\b[0-9A-Fa-f]{2}(?:[:-][0-9A-Fa-f]{2}){5}\b").unwrap(),
+            ipv4: Regex::new(r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b").unwrap(),
+            ipv6: Regex::new(r"[0-9A-Fa-f]{0,4}(?::[0-9A-Fa-f]{0,4}){2,7}").unwrap(),
+            pseudonyms: HashMap::new(),
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Redacts every identifier found in `text`.
+    pub fn text(&mut self, text: &str) -> String {
+        let Redactor { literals, mac, ipv4, ipv6, pseudonyms, counters } = self;
+        let mut pseudonym = |kind: &'static str, value: &str| {
+            pseudonyms.entry(value.to_lowercase()).or_insert_with(|| {
+                let n = counters.entry(kind).or_insert(0);
+                *n += 1;
+                format!("{}-{}", kind, n)
+            }).clone()
+        };
+
+        let mut out = text.to_string();
+        for (re, kind) in literals.iter() {
+            out = re.replace_all(&out, |c: &Captures| pseudonym(kind, &c[0])).into_owned();
+        }
+        out = mac.replace_all(&out, |c: &Captures| {
+            if c[0].chars().all(|ch| matches!(ch, '0' | ':' | '-')) { c[0].to_string() } else { pseudonym("mac", &c[0]) }
+        }).into_owned();
+        let replaced = ipv4.replace_all(&out, |c: &Captures| {
+            let m = c.get(0).unwrap();
+            match m.as_str().parse::<Ipv4Addr>() {
+                Ok(ip) if !ip.is_loopback() && !ip.is_unspecified() && !in_dotted_run(&out, m.start(), m.end()) => pseudonym("ip", m.as_str()),
+                _ => m.as_str().to_string(),
+            }
+        }).into_owned();
+        out = replaced;
+        out = ipv6.replace_all(&out, |c: &Captures| match c[0].parse::<Ipv6Addr>() {
+            Ok(ip) if !ip.is_loopback() && !ip.is_unspecified() => pseudonym("ip", &c[0]),
+            _ => c[0].to_string(),
+        }).into_owned();
+        out
+    }
+
+    fn opt(&mut self, value: &mut Option<String>) {
+        if let Some(v) = value {
+            *v = self.text(v);
+        }
+    }
+
+    fn string(&mut self, value: &mut String) {
+        *value = self.text(value);
+    }
+}
+
+/// Whether `text[start..end]` is four parts of a longer dotted number, like a
+/// `1.2.3.4.5` version string, rather than an address of its own.
+fn in_dotted_run(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().rev().take(2).collect::<Vec<_>>();
+    let after = text[end..].chars().take(2).collect::<Vec<_>>();
+    let dotted = |chars: &[char]| matches!(chars, ['.', c] if c.is_ascii_digit());
+    dotted(&before) || dotted(&after)
+}
+
+/// The names of accounts people log in with. System accounts such as `bin` or
+/// `daemon` are left out, since as literals they'd be replaced inside paths.
+fn login_users() -> Vec<String> {
+    let first = if cfg!(target_os = "macos") { 501 } else { 1000 };
+    Users::new_with_refreshed_list().iter()
+        .filter(|user| match user.id().to_string().parse::<u32>() {
+            Ok(uid) => (first..60000).contains(&uid),
+            // Windows ids are SIDs, and the accounts listed there are all logins.
+            Err(_) => true,
+        })
+        .map(|user| user.name().to_string())
+        .collect()
+}
+
+/// Models whose text fields can carry identifying information.
+pub trait Redact {
+    fn redact(&mut self, r: &mut Redactor);
+}
+
+/// Redacts `info` in place when `--redact` is active.
+pub fn redact<T: Redact>(info: &mut T, redactor: Option<&mut Redactor>) {
+    if let Some(r) = redactor {
+        info.redact(r);
+    }
+}
+
+impl<T: Redact> Redact for Vec<T> {
+    fn redact(&mut self, r: &mut Redactor) {
+        self.iter_mut().for_each(|item| item.redact(r));
+    }
+}
+
+impl Redact for SystemInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.opt(&mut self.name);
+        r.opt(&mut self.kernel_version);
+        r.opt(&mut self.os_version);
+        r.opt(&mut self.host_name);
+    }
+}
+
+impl Redact for FetchInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.opt(&mut self.user);
+        r.opt(&mut self.host_name);
+        r.opt(&mut self.os);
+        r.opt(&mut self.kernel_version);
+        r.opt(&mut self.cpu);
+        for disk in &mut self.disks {
+            r.string(&mut disk.mount_point);
+        }
+    }
+}
+
+impl Redact for CpuInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        for cpu in &mut self.cpus {
+            r.string(&mut cpu.vendor);
+            r.string(&mut cpu.brand);
+        }
+    }
+}
+
+impl Redact for MemoryInfo {
+    fn redact(&mut self, _: &mut Redactor) {}
+}
+
+impl Redact for ContainerInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.opt(&mut self.id);
+        r.opt(&mut self.cgroup_path);
+    }
+}
+
+impl Redact for DockerContainer {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.id);
+        r.string(&mut self.name);
+        r.string(&mut self.image);
+        for process in &mut self.processes {
+            r.string(&mut process.name);
+        }
+    }
+}
+
+impl Redact for K8sNodeReport {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        for process in &mut self.processes {
+            r.opt(&mut process.namespace);
+            r.opt(&mut process.pod);
+            r.string(&mut process.pod_uid);
+            r.opt(&mut process.container_id);
+            r.string(&mut process.name);
+        }
+    }
+}
+
+impl Redact for KernelResources {
+    fn redact(&mut self, _: &mut Redactor) {}
+}
+
+impl Redact for AllReport {
+    fn redact(&mut self, r: &mut Redactor) {
+        self.system.redact(r);
+        self.cpu.redact(r);
+        self.memory.redact(r);
+        self.disks.redact(r);
+        self.network.redact(r);
+        self.components.redact(r);
+        self.processes.redact(r);
+    }
+}
+
+impl Redact for Snapshot {
+    fn redact(&mut self, r: &mut Redactor) {
+        self.system.redact(r);
+        self.cpu.redact(r);
+        self.memory.redact(r);
+        self.disks.redact(r);
+        self.network.redact(r);
+        self.processes.redact(r);
+    }
+}
+
+impl Redact for HostStatus {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.address);
+        r.opt(&mut self.host_name);
+        for alert in &mut self.disk_alerts {
+            r.string(&mut alert.mount_point);
+        }
+    }
+}
+
+impl Redact for SnapshotDiff {
+    fn redact(&mut self, r: &mut Redactor) {
+        for disk in &mut self.disks {
+            r.string(&mut disk.name);
+        }
+        self.started.redact(r);
+        self.exited.redact(r);
+    }
+}
+
+impl Redact for HistorySample {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.opt(&mut self.target);
+    }
+}
+
+impl Redact for AlertResult {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.rule);
+        r.opt(&mut self.target);
+    }
+}
+
+impl Redact for DiskInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        r.string(&mut self.mount_point);
+        r.string(&mut self.kind);
+        r.string(&mut self.file_system);
+    }
+}
+
+impl Redact for DiskUsageReport {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.root);
+        self.directories.redact(r);
+    }
+}
+
+impl Redact for DirUsage {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.path);
+    }
+}
+
+impl Redact for NetworkInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.interface);
+        r.opt(&mut self.mac_address);
+        for address in &mut self.ip_addresses {
+            r.string(address);
+        }
+        r.opt(&mut self.namespace);
+    }
+}
+
+impl Redact for Connection {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.local_address);
+        r.string(&mut self.remote_address);
+        r.opt(&mut self.process);
+    }
+}
+
+impl Redact for PortOwner {
+    fn redact(&mut self, r: &mut Redactor) {
+        self.connection.redact(r);
+        r.opt(&mut self.user);
+        r.opt(&mut self.command);
+    }
+}
+
+impl Redact for ComponentInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.label);
+    }
+}
+
+impl Redact for SensorReading {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.label);
+    }
+}
+
+impl Redact for ProcessInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        self.cmd.iter_mut().for_each(|arg| r.string(arg));
+        r.opt(&mut self.user);
+    }
+}
+
+impl Redact for ProcessDetail {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        self.cmd.iter_mut().for_each(|arg| r.string(arg));
+        r.opt(&mut self.exe);
+        r.opt(&mut self.cwd);
+        r.opt(&mut self.user);
+        self.environ.iter_mut().for_each(|var| r.string(var));
+        for thread in self.thread_list.iter_mut().flatten() {
+            r.string(&mut thread.name);
+        }
+    }
+}
+
+impl Redact for ProcessEvent {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+    }
+}
+
+impl Redact for HardwareEvent {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.device);
+        r.opt(&mut self.detail);
+    }
+}
+
+impl Redact for FileHolder {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        r.string(&mut self.path);
+    }
+}
+
+impl Redact for KillResult {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+    }
+}
+
+impl Redact for UserInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.name);
+        self.groups.iter_mut().for_each(|group| r.string(group));
+    }
+}
+
+impl Redact for SessionInfo {
+    fn redact(&mut self, r: &mut Redactor) {
+        r.string(&mut self.user);
+        r.opt(&mut self.host);
+    }
+}
+
+impl Redact for BundleDiagnostics {
+    fn redact(&mut self, r: &mut Redactor) {
+        self.args.iter_mut().for_each(|arg| r.string(arg));
+        r.opt(&mut self.long_os_version);
+    }
+}
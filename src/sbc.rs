@@ -0,0 +1,79 @@
+//! Sensors found on single-board computers such as the Raspberry Pi: readings from
+//! `vcgencmd` when the firmware tools are installed, and SoC thermal zones otherwise.
+
+use std::fs;
+use std::process::Command;
+
+/// Throttling bits reported by `vcgencmd get_throttled`; bit `n + 16` records that
+/// the condition has occurred since boot.
+const THROTTLE_BITS: [(u32, &str); 4] = [
+    (0, "under_voltage"),
+    (1, "freq_capped"),
+    (2, "throttled"),
+    (3, "soft_temp_limit"),
+];
+
+/// Runs `vcgencmd` with `args`, returning its trimmed output on success.
+fn vcgencmd(args: &[&str]) -> Option<String> {
+    let output = Command::new("vcgencmd").args(args).output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Extracts the value from output like `temp=48.3'C` or `volt=1.2000V`.
+pub fn parse_vcgencmd_value(output: &str, key: &str) -> Option<f32> {
+    let value = output.strip_prefix(key)?.strip_prefix('=')?;
+    let end = value.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(value.len());
+    value[..end].parse().ok()
+}
+
+/// Names the throttling conditions set in `bits`; past conditions get a `_since_boot` suffix.
+pub fn throttle_flags(bits: u32) -> Vec<String> {
+    let mut flags = Vec::new();
+    for (bit, name) in THROTTLE_BITS {
+        if bits & (1 << bit) != 0 {
+            flags.push(name.to_string());
+        }
+    }
+    for (bit, name) in THROTTLE_BITS {
+        if bits & (1 << (bit + 16)) != 0 {
+            flags.push(format!("{}_since_boot", name));
+        }
+    }
+    flags
+}
+
+/// SoC temperature in °C.
+pub fn soc_temperature() -> Option<f32> {
+    vcgencmd(&["measure_temp"]).and_then(|out| parse_vcgencmd_value(&out, "temp"))
+}
+
+/// Core voltage in volts.
+pub fn core_voltage() -> Option<f32> {
+    vcgencmd(&["measure_volts", "core"]).and_then(|out| parse_vcgencmd_value(&out, "volt"))
+}
+
+/// Active and past throttling conditions, or `None` when `vcgencmd` is unavailable.
+pub fn throttling() -> Option<Vec<String>> {
+    let out = vcgencmd(&["get_throttled"])?;
+    let hex = out.strip_prefix("throttled=0x")?;
+    u32::from_str_radix(hex, 16).ok().map(throttle_flags)
+}
+
+/// SoC and CPU thermal zones from `/sys/class/thermal` as `(type, °C)` pairs.
+pub fn thermal_zones() -> Vec<(String, f32)> {
+    let Ok(entries) = fs::read_dir("/sys/class/thermal") else {
+        return Vec::new();
+    };
+    entries.flatten()
+        .filter(|e| e.file_name().to_string_lossy().starts_with("thermal_zone"))
+        .filter_map(|e| {
+            let kind = fs::read_to_string(e.path().join("type")).ok()?.trim().to_string();
+            let lower = kind.to_lowercase();
+            if !lower.contains("soc") && !lower.contains("cpu") {
+                return None;
+            }
+            let millis: f32 = fs::read_to_string(e.path().join("temp")).ok()?.trim().parse().ok()?;
+            Some((kind, millis / 1000.0))
+        })
+        .collect()
+}
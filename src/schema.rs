@@ -0,0 +1,101 @@
+//! JSON Schemas (draft 2020-12) for the JSON output of every command, generated from
+//! the models themselves so they can't drift from what's printed.
+
+use clap::ValueEnum;
+use schemars::{schema_for, Schema};
+use serde_json::{json, Map, Value};
+use crate::args::SchemaOutput;
+use crate::models::*;
+
+/// The schema of `output`, including the `schema_version` and `timestamp` fields
+/// [`crate::fmt::versioned`] adds to every object.
+pub fn schema(output: SchemaOutput) -> Value {
+    let schema: Schema = match output {
+        SchemaOutput::Summary => schema_for!(Summary<'static>),
+        SchemaOutput::System => schema_for!(SystemInfo),
+        SchemaOutput::Cpu => schema_for!(CpuInfo),
+        SchemaOutput::Uptime => schema_for!(UptimeInfo),
+        SchemaOutput::Fetch => schema_for!(FetchInfo),
+        SchemaOutput::Alert => schema_for!(Vec<AlertResult>),
+        SchemaOutput::Load => schema_for!(LoadReport),
+        SchemaOutput::Memory => schema_for!(MemoryInfo),
+        SchemaOutput::Disks => schema_for!(Vec<DiskInfo>),
+        SchemaOutput::DisksAnalyze => schema_for!(DiskUsageReport),
+        SchemaOutput::Network => schema_for!(Vec<NetworkInfo>),
+        SchemaOutput::Components => schema_for!(Vec<ComponentInfo>),
+        SchemaOutput::Container => schema_for!(ContainerInfo),
+        SchemaOutput::Sensors => schema_for!(Vec<SensorReading>),
+        SchemaOutput::Resources => schema_for!(KernelResources),
+        SchemaOutput::Processes => schema_for!(Vec<ProcessInfo>),
+        SchemaOutput::ProcessEvents => schema_for!(ProcessEvent),
+        SchemaOutput::Users => schema_for!(Vec<UserInfo>),
+        SchemaOutput::Sessions => schema_for!(Vec<SessionInfo>),
+        SchemaOutput::Process => schema_for!(ProcessDetail),
+        SchemaOutput::Whoholds => schema_for!(Vec<FileHolder>),
+        SchemaOutput::Connections => schema_for!(Vec<Connection>),
+        SchemaOutput::Docker => schema_for!(Vec<DockerContainer>),
+        SchemaOutput::K8sNode => schema_for!(K8sNodeReport),
+        SchemaOutput::Query => schema_for!(Snapshot),
+        SchemaOutput::Port => schema_for!(Vec<PortOwner>),
+        SchemaOutput::Events => schema_for!(HardwareEvent),
+        SchemaOutput::Capabilities => schema_for!(Vec<Capability>),
+        SchemaOutput::Thermal => schema_for!(ThermalSample),
+        SchemaOutput::ThermalSummary => schema_for!(ThrottleSummary),
+        SchemaOutput::WatchSummary => schema_for!(WatchSummary),
+        SchemaOutput::Bench => schema_for!(Vec<BenchResult>),
+        SchemaOutput::History => schema_for!(Vec<HistorySample>),
+        SchemaOutput::All => schema_for!(AllReport),
+        SchemaOutput::Snapshot => schema_for!(Snapshot),
+        SchemaOutput::Diff => schema_for!(SnapshotDiff),
+        SchemaOutput::Kill => schema_for!(Vec<KillResult>),
+        SchemaOutput::Examples => schema_for!(Vec<ExampleInfo>),
+        SchemaOutput::Statusbar => schema_for!(StatusbarLine),
+        SchemaOutput::SelfStats => schema_for!(SelfStats),
+        SchemaOutput::ProfileCollection => schema_for!(Vec<StageTiming>),
+    };
+    let mut schema = schema.to_value();
+    let name = name(output);
+    schema["title"] = json!(format!("sysinfo-cli {} output", name));
+    // A list's objects are stamped, not the list.
+    match schema.get_mut("items") {
+        Some(items) => add_stamp(items),
+        None => add_stamp(&mut schema),
+    }
+    schema
+}
+
+/// Every schema, keyed by output name.
+pub fn schemas() -> Value {
+    let schemas: Map<String, Value> = SchemaOutput::value_variants().iter().map(|o| (name(*o), schema(*o))).collect();
+    Value::Object(schemas)
+}
+
+fn name(output: SchemaOutput) -> String {
+    output.to_possible_value().map(|v| v.get_name().to_string()).unwrap_or_default()
+}
+
+/// Declares `schema_version` (required, and fixed to [`SCHEMA_VERSION`]) and the
+/// optional `--timestamp` field ahead of the object's own properties.
+fn add_stamp(object: &mut Value) {
+    let Some(object) = object.as_object_mut() else { return };
+    let mut properties = Map::new();
+    properties.insert("schema_version".to_string(), json!({
+        "description": "Raised whenever a field is removed, renamed, or changes type.",
+        "const": SCHEMA_VERSION,
+    }));
+    properties.insert("timestamp".to_string(), json!({
+        "description": "When the sample was taken, with `--timestamp`.",
+        "type": "string",
+        "format": "date-time",
+    }));
+    if let Some(Value::Object(own)) = object.remove("properties") {
+        properties.extend(own);
+    }
+    object.insert("properties".to_string(), Value::Object(properties));
+    match object.get_mut("required") {
+        Some(Value::Array(required)) => required.insert(0, json!("schema_version")),
+        _ => {
+            object.insert("required".to_string(), json!(["schema_version"]));
+        }
+    }
+}
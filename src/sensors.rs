@@ -0,0 +1,112 @@
+//! `sensors`: every hwmon reading (Linux), temperatures plus fans, voltages, currents,
+//! and power, grouped by the chip that reports it.
+
+use std::io;
+use crate::models::{SensorKind, SensorReading};
+
+/// Every sensor reading, sorted by chip and then by kind and channel.
+pub fn get_sensor_readings() -> io::Result<Vec<SensorReading>> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(linux::sensor_readings())
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "sensors is only available on Linux"))
+    }
+}
+
+/// The kind and channel of an hwmon attribute such as `fan2_input`, with the rest of
+/// its name (`input`, `average`, or `label`).
+fn attribute(file: &str) -> Option<(SensorKind, u32, &str)> {
+    let (channel, item) = file.split_once('_')?;
+    let digits = channel.find(|c: char| c.is_ascii_digit())?;
+    let kind = match &channel[..digits] {
+        "temp" => SensorKind::Temperature,
+        "fan" => SensorKind::Fan,
+        "in" => SensorKind::Voltage,
+        "curr" => SensorKind::Current,
+        "power" => SensorKind::Power,
+        _ => return None,
+    };
+    Some((kind, channel[digits..].parse().ok()?, item))
+}
+
+/// The readings in one hwmon chip's `(file name, contents)` attributes, in the kind's
+/// unit: hwmon gives millidegrees, millivolts, milliamperes, and microwatts. Channels
+/// without a label are named after the attribute, e.g. `fan1`. Power uses the
+/// average when there's no instantaneous reading.
+pub fn parse_hwmon_chip(chip: &str, device: Option<&str>, files: &[(String, String)]) -> Vec<SensorReading> {
+    let mut channels: Vec<(SensorKind, u32, Option<f64>, Option<String>)> = Vec::new();
+    for (file, contents) in files {
+        let Some((kind, index, item)) = attribute(file) else { continue };
+        let position = match channels.iter().position(|c| c.0 == kind && c.1 == index) {
+            Some(i) => i,
+            None => {
+                channels.push((kind, index, None, None));
+                channels.len() - 1
+            }
+        };
+        let channel = &mut channels[position];
+        match item {
+            "label" => channel.3 = Some(contents.trim().to_string()),
+            "input" => channel.2 = contents.trim().parse().ok().or(channel.2),
+            "average" if kind == SensorKind::Power => channel.2 = channel.2.or_else(|| contents.trim().parse().ok()),
+            _ => {}
+        }
+    }
+    channels.sort_by_key(|c| (c.0, c.1));
+    channels.into_iter().filter_map(|(kind, index, raw, label)| {
+        let scale = match kind {
+            SensorKind::Fan => 1.0,
+            SensorKind::Power => 1_000_000.0,
+            _ => 1000.0,
+        };
+        let prefix = match kind {
+            SensorKind::Temperature => "temp",
+            SensorKind::Fan => "fan",
+            SensorKind::Voltage => "in",
+            SensorKind::Current => "curr",
+            SensorKind::Power => "power",
+        };
+        Some(SensorReading {
+            chip: chip.to_string(),
+            device: device.map(str::to_string),
+            label: label.unwrap_or_else(|| format!("{}{}", prefix, index)),
+            kind,
+            value: raw? / scale,
+        })
+    }).collect()
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::fs;
+    use crate::models::SensorReading;
+    use super::{attribute, parse_hwmon_chip};
+
+    pub fn sensor_readings() -> Vec<SensorReading> {
+        let Ok(entries) = fs::read_dir("/sys/class/hwmon") else {
+            return Vec::new();
+        };
+        let mut readings = Vec::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(name) = fs::read_to_string(path.join("name")) else { continue };
+            // The device tells apart chips with the same name, such as two NVMe drives.
+            let device = fs::read_link(path.join("device")).ok()
+                .and_then(|target| target.file_name().map(|n| n.to_string_lossy().into_owned()));
+            let Ok(files) = fs::read_dir(&path) else { continue };
+            let files: Vec<(String, String)> = files.flatten().filter_map(|file| {
+                let file_name = file.file_name().to_string_lossy().into_owned();
+                attribute(&file_name)?;
+                // Unreadable attributes, such as a sensor that's powered down, are skipped.
+                let contents = fs::read_to_string(file.path()).ok()?;
+                Some((file_name, contents))
+            }).collect();
+            readings.extend(parse_hwmon_chip(name.trim(), device.as_deref(), &files));
+        }
+        readings.sort_by(|a, b| (&a.chip, &a.device, a.kind).cmp(&(&b.chip, &b.device, b.kind)));
+        readings
+    }
+}
@@ -0,0 +1,191 @@
+//! Minimal HTTP endpoint for Prometheus scrapes and JSON polling, plus the `/search`
+//! and `/query` endpoints of Grafana's JSON datasources over the `record` history.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+use rusqlite::Connection;
+use serde_json::json;
+use sysinfo::System;
+use crate::collector::{get_memory_info, get_summary, get_system_info, init_system, refresh_system};
+use crate::fmt::versioned;
+use crate::grafana;
+use crate::history;
+use crate::prometheus;
+use crate::redact::{redact, Redactor};
+
+/// Larger request bodies are cut short; a Grafana query is a few hundred bytes.
+const MAX_BODY: usize = 1 << 20;
+
+#[derive(Debug, PartialEq)]
+pub enum Route {
+    Index,
+    Metrics,
+    Json,
+    Search,
+    Query,
+    NotFound,
+    MethodNotAllowed,
+}
+
+/// Maps an HTTP request line such as `GET /metrics HTTP/1.1` to a route; the query
+/// string is ignored. Grafana POSTs to `/search` and `/query`; the rest are read with GET.
+pub fn route(request_line: &str) -> Route {
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Route::NotFound;
+    };
+    let read = method == "GET" || method == "HEAD";
+    if !read && method != "POST" {
+        return Route::MethodNotAllowed;
+    }
+    match target.split('?').next().unwrap_or_default() {
+        "/" | "/metrics" | "/json" if !read => Route::MethodNotAllowed,
+        "/search" | "/query" if method != "POST" => Route::MethodNotAllowed,
+        "/" => Route::Index,
+        "/metrics" => Route::Metrics,
+        "/json" => Route::Json,
+        "/search" => Route::Search,
+        "/query" => Route::Query,
+        _ => Route::NotFound,
+    }
+}
+
+/// The summary's `System`, sampled once when the server starts and refreshed in place
+/// for every scrape, so CPU usage covers the time since the previous one.
+struct Sampler {
+    sys: System,
+    refreshed: Instant,
+}
+
+impl Sampler {
+    fn new(sample: Option<Duration>) -> Self {
+        Sampler { sys: init_system(&None, sample), refreshed: Instant::now() }
+    }
+
+    /// Scrapes closer together than sysinfo's minimum interval wait out the rest of it,
+    /// as CPU usage isn't meaningful over less.
+    fn refresh(&mut self) -> &System {
+        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL.saturating_sub(self.refreshed.elapsed()));
+        refresh_system(&mut self.sys, &None);
+        self.refreshed = Instant::now();
+        &self.sys
+    }
+}
+
+/// Serves requests one at a time until the process is stopped. Only the first CPU
+/// sample waits out `sample`; after that a scrape costs a refresh. Grafana's queries
+/// read the history database at `db`.
+pub fn serve(addr: SocketAddr, sample: Option<Duration>, db: &Path, mut redactor: Option<&mut Redactor>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    let mut sampler = Sampler::new(sample);
+    eprintln!("Serving http://{}/metrics and /json", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| handle(stream, &mut sampler, db, redactor.as_deref_mut()));
+        if let Err(e) = result {
+            eprintln!("Error handling request: {}", e);
+        }
+    }
+    Ok(())
+}
+
+fn handle(mut stream: TcpStream, sampler: &mut Sampler, db: &Path, redactor: Option<&mut Redactor>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Read the headers and any body in full so closing the socket doesn't reset the connection.
+    let mut header = String::new();
+    let mut content_length = 0;
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':') && name.trim().eq_ignore_ascii_case("content-length") {
+            content_length = value.trim().parse().unwrap_or(0);
+        }
+        header.clear();
+    }
+    let mut body = vec![0; content_length.min(MAX_BODY)];
+    reader.read_exact(&mut body)?;
+
+    let route = route(&request_line);
+    let (status, content_type, body) = match route {
+        Route::Index => ("200 OK", "text/plain; charset=utf-8", "sysinfo-cli: see /metrics and /json\n".to_string()),
+        Route::Metrics | Route::Json => {
+            let sys = sampler.refresh();
+            let mut system = get_system_info();
+            redact(&mut system, redactor);
+            let mem = get_memory_info(sys);
+            if route == Route::Metrics {
+                ("200 OK", "text/plain; version=0.0.4; charset=utf-8", prometheus::scrape(sys, &system, &mem))
+            } else {
+                let summary = versioned(&get_summary(sys, &system, &mem), None).map_err(io::Error::other)?;
+                ("200 OK", "application/json", format!("{:#}", summary))
+            }
+        }
+        Route::Search | Route::Query => {
+            let answer = open_history(db).map_err(io::Error::other).and_then(|conn| match route {
+                Route::Search => search(&conn, redactor).map(|names| json!(names)),
+                _ => query(&conn, &body, redactor),
+            });
+            match answer {
+                Ok(answer) => ("200 OK", "application/json", answer.to_string()),
+                Err(e) if e.kind() == io::ErrorKind::InvalidInput => ("400 Bad Request", "text/plain; charset=utf-8", format!("{}\n", e)),
+                Err(e) => ("500 Internal Server Error", "text/plain; charset=utf-8", format!("{}\n", e)),
+            }
+        }
+        Route::NotFound => ("404 Not Found", "text/plain; charset=utf-8", "Not found\n".to_string()),
+        Route::MethodNotAllowed => ("405 Method Not Allowed", "text/plain; charset=utf-8", "Method not allowed\n".to_string()),
+    };
+
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, content_type, body.len())?;
+    if !request_line.starts_with("HEAD ") {
+        stream.write_all(body.as_bytes())?;
+    }
+    stream.flush()
+}
+
+/// The history database, or an empty one until `record` has created it.
+fn open_history(db: &Path) -> rusqlite::Result<Connection> {
+    if db.exists() {
+        return history::open(db);
+    }
+    let conn = Connection::open_in_memory()?;
+    history::init(&conn)?;
+    Ok(conn)
+}
+
+/// How Grafana names a series: the metric, then any mount point or interface, e.g.
+/// `disk_used_pct /home`.
+fn series_name(metric: &str, target: &str, redactor: Option<&mut Redactor>) -> String {
+    let name = if target.is_empty() { metric.to_string() } else { format!("{} {}", metric, target) };
+    match redactor {
+        Some(r) => r.text(&name),
+        None => name,
+    }
+}
+
+/// `/search`: the name of every recorded series, for Grafana's metric picker.
+pub fn search(conn: &Connection, mut redactor: Option<&mut Redactor>) -> io::Result<Vec<String>> {
+    let series = history::series(conn).map_err(io::Error::other)?;
+    Ok(series.iter().map(|(metric, target)| series_name(metric, target, redactor.as_deref_mut())).collect())
+}
+
+/// `/query`: the samples of each requested series within the request's time range, as
+/// [`grafana::timeserie`]s.
+pub fn query(conn: &Connection, body: &[u8], mut redactor: Option<&mut Redactor>) -> io::Result<serde_json::Value> {
+    let request = grafana::parse_query(body)?;
+    // Names may be pseudonyms, so requests are matched against the names `/search` gave.
+    let series = history::series(conn).map_err(io::Error::other)?;
+    let mut answer = Vec::new();
+    for wanted in &request.targets {
+        for (metric, target) in &series {
+            if series_name(metric, target, redactor.as_deref_mut()) != *wanted {
+                continue;
+            }
+            let values = history::values(conn, metric, target, request.from, request.to).map_err(io::Error::other)?;
+            answer.push(grafana::timeserie(wanted, &values));
+        }
+    }
+    Ok(json!(answer))
+}
@@ -0,0 +1,252 @@
+//! Sinks for `--sink`, `--otlp-endpoint`, and `daemon`: metrics over UDP in StatsD or
+//! Graphite plaintext format, JSON over MQTT, OTLP to an OpenTelemetry collector, or
+//! JSON records appended to a file or POSTed over HTTP, for monitoring and
+//! home-automation stacks that already listen for them.
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::net::UdpSocket;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use serde_json::json;
+use crate::args::{SinkProtocol, SinkUrl};
+use crate::models::SCHEMA_VERSION;
+use crate::mqtt::MqttClient;
+use crate::otlp::Exporter;
+use crate::prometheus::{Exposition, ToMetrics};
+
+/// Keeps datagrams under the usual Ethernet MTU so they aren't fragmented.
+const MAX_PACKET: usize = 1432;
+
+thread_local! {
+    /// The `--sink`, with the subcommand that names its MQTT topics and records.
+    static SINK: RefCell<Option<(Sink, String)>> = const { RefCell::new(None) };
+}
+
+pub struct Sink {
+    transport: Transport,
+    /// Path prefix for StatsD and Graphite, topic prefix for MQTT.
+    prefix: String,
+    /// Publish Home Assistant discovery messages (MQTT only).
+    discovery: bool,
+    /// Metric keys already announced to Home Assistant.
+    announced: HashSet<String>,
+}
+
+enum Transport {
+    Udp(UdpSocket, SinkProtocol),
+    Mqtt(MqttClient),
+    File(PathBuf),
+    Http(ureq::Agent, String),
+    Otlp(Exporter),
+}
+
+impl Sink {
+    pub fn connect(url: &SinkUrl, prefix: &str, discovery: bool) -> io::Result<Self> {
+        let udp = |host: &str, port: u16, protocol| -> io::Result<Transport> {
+            let socket = UdpSocket::bind(if host.contains(':') { "[::]:0" } else { "0.0.0.0:0" })?;
+            socket.connect((host, port))?;
+            Ok(Transport::Udp(socket, protocol))
+        };
+        let transport = match url {
+            SinkUrl::Statsd { host, port } => udp(host, *port, SinkProtocol::Statsd)?,
+            SinkUrl::Graphite { host, port } => udp(host, *port, SinkProtocol::Graphite)?,
+            SinkUrl::Mqtt { host, port, user, password } => Transport::Mqtt(MqttClient::connect(host, *port, user.clone(), password.clone())?),
+            SinkUrl::File(path) => Transport::File(path.clone()),
+            SinkUrl::Http(url) => {
+                let config = ureq::Agent::config_builder().timeout_global(Some(Duration::from_secs(10))).build();
+                Transport::Http(config.into(), url.clone())
+            }
+            SinkUrl::Otlp(endpoint) => Transport::Otlp(Exporter::new(endpoint)),
+        };
+        Ok(Sink { transport, prefix: prefix.trim_end_matches('/').to_string(), discovery, announced: HashSet::new() })
+    }
+
+    /// Sends one sample of `group`. StatsD and Graphite get `metrics`; MQTT gets both,
+    /// `report` as JSON on `<prefix>/<group>` and the metrics on `<prefix>/<group>/metrics`;
+    /// OTLP collectors get `metrics`; files and HTTP endpoints get `report` as a [`record`].
+    pub fn publish(&mut self, group: &str, report: Option<&serde_json::Value>, metrics: &Exposition) -> io::Result<()> {
+        match &mut self.transport {
+            Transport::Udp(socket, protocol) => {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+                for mut packet in packets(&lines(*protocol, &self.prefix, metrics, now)) {
+                    if *protocol == SinkProtocol::Graphite {
+                        packet.push('\n');
+                    }
+                    socket.send(packet.as_bytes())?;
+                }
+            }
+            Transport::Mqtt(client) => {
+                if let Some(report) = report {
+                    client.publish(&format!("{}/{}", self.prefix, group), report.to_string().as_bytes(), false)?;
+                }
+                let values = metric_values(metrics);
+                if values.is_empty() {
+                    return Ok(());
+                }
+                let state_topic = format!("{}/{}/metrics", self.prefix, group);
+                if self.discovery {
+                    for key in values.keys().filter(|k| !self.announced.contains(*k)) {
+                        let (topic, config) = discovery(&self.prefix, &state_topic, key);
+                        client.publish(&topic, config.as_bytes(), true)?;
+                    }
+                    self.announced.extend(values.keys().cloned());
+                }
+                client.publish(&state_topic, serde_json::Value::Object(values).to_string().as_bytes(), false)?;
+            }
+            Transport::File(path) => {
+                if let Some(report) = report {
+                    let mut file = OpenOptions::new().create(true).append(true).open(&*path)?;
+                    writeln!(file, "{}", record(group, report, SystemTime::now()))?;
+                }
+            }
+            Transport::Http(agent, url) => {
+                if let Some(report) = report {
+                    agent.post(url.as_str())
+                        .header("Content-Type", "application/json")
+                        .send(record(group, report, SystemTime::now()).to_string())
+                        .map_err(io::Error::other)?;
+                }
+            }
+            Transport::Otlp(exporter) => exporter.export(metrics)?,
+        }
+        Ok(())
+    }
+}
+
+/// One sample as written to files and HTTP endpoints:
+/// `{"schema_version": 1, "timestamp": "2026-10-16T13:05:24Z", "group": "cpu", "data": {...}}`.
+pub fn record(group: &str, report: &serde_json::Value, now: SystemTime) -> serde_json::Value {
+    json!({
+        "schema_version": SCHEMA_VERSION,
+        "timestamp": humantime::format_rfc3339_seconds(now).to_string(),
+        "group": group,
+        "data": report,
+    })
+}
+
+/// Sets where [`send`] writes for the rest of the run; `group` names the samples.
+pub fn set_sink(sink: Option<Sink>, group: &str) {
+    SINK.with(|s| *s.borrow_mut() = sink.map(|sink| (sink, group.to_string())));
+}
+
+/// Sends `info` and its metrics, if a sink is set.
+pub fn send<T: Serialize + ToMetrics + ?Sized>(info: &T) {
+    let mut out = Exposition::default();
+    info.write_metrics(&mut out);
+    send_with_metrics(info, &out);
+}
+
+/// Sends `report` with metrics collected apart from it, such as the summary's.
+pub fn send_with_metrics<T: Serialize + ?Sized>(report: &T, metrics: &Exposition) {
+    SINK.with(|s| {
+        let Some((sink, group)) = &mut *s.borrow_mut() else { return };
+        // UDP gives no delivery guarantee anyway; only what fails locally is reported.
+        let sent = serde_json::to_value(report).map_err(io::Error::other).and_then(|report| sink.publish(group, Some(&report), metrics));
+        if let Err(e) = sent {
+            eprintln!("Error sending metrics to the sink: {}", e);
+        }
+    });
+}
+
+/// One line per sample, named `<prefix>.<metric>[.<label value>...]` with the metric
+/// name's `sysinfo_` prefix dropped, e.g. `web1.cpu_usage_percent.3:12.5|g`. Totals are
+/// sent as StatsD gauges too, since StatsD counters add up increments instead.
+pub fn lines(protocol: SinkProtocol, prefix: &str, metrics: &Exposition, timestamp: u64) -> Vec<String> {
+    metrics.samples()
+        // Its value is always 1; the details are only in the labels.
+        .filter(|(name, _, value)| *name != "sysinfo_system_info" && value.is_finite())
+        .map(|(name, labels, value)| {
+            let mut path = format!("{}.{}", prefix, name.strip_prefix("sysinfo_").unwrap_or(name));
+            for label in labels.iter().map(|(_, v)| sanitize(v)).filter(|v| !v.is_empty()) {
+                path.push('.');
+                path.push_str(&label);
+            }
+            match protocol {
+                SinkProtocol::Statsd => format!("{}:{}|g", path, value),
+                SinkProtocol::Graphite => format!("{} {} {}", path, value, timestamp),
+            }
+        })
+        .collect()
+}
+
+/// Every sample keyed by its metric name (without `sysinfo_`) and label values joined
+/// with `_`, e.g. `{"cpu_usage_percent_3": 12.5}`.
+pub fn metric_values(metrics: &Exposition) -> serde_json::Map<String, serde_json::Value> {
+    metrics.samples()
+        .filter(|(name, _, value)| *name != "sysinfo_system_info" && value.is_finite())
+        .map(|(name, labels, value)| {
+            let mut key = name.strip_prefix("sysinfo_").unwrap_or(name).to_string();
+            for label in labels.iter().map(|(_, v)| sanitize(v)).filter(|v| !v.is_empty()) {
+                key.push('_');
+                key.push_str(&label);
+            }
+            (key, json!(value))
+        })
+        .collect()
+}
+
+/// The retained Home Assistant discovery message announcing `key` as a sensor read
+/// from the JSON on `state_topic`, grouped into one device per topic prefix. The unit
+/// comes from the metric name's suffix.
+pub fn discovery(prefix: &str, state_topic: &str, key: &str) -> (String, String) {
+    let node = sanitize(prefix);
+    let (unit, device_class) = if key.contains("_percent") {
+        (Some("%"), None)
+    } else if key.contains("_bytes") {
+        (Some("B"), Some("data_size"))
+    } else if key.contains("_seconds") {
+        (Some("s"), Some("duration"))
+    } else if key.contains("_celsius") {
+        (Some("°C"), Some("temperature"))
+    } else if key.contains("_mhz") {
+        (Some("MHz"), Some("frequency"))
+    } else if key.contains("_volts") {
+        (Some("V"), Some("voltage"))
+    } else {
+        (None, None)
+    };
+    let mut config = json!({
+        "name": key.replace('_', " "),
+        "unique_id": format!("{}_{}", node, key),
+        "state_topic": state_topic,
+        // Subscripted, since keys may hold dashes (`br-abc`) that Jinja would take for
+        // subtraction after a dot.
+        "value_template": format!("{{{{ value_json['{}'] }}}}", key),
+        "state_class": if key.contains("_total") { "total_increasing" } else { "measurement" },
+        "device": { "identifiers": [node], "name": prefix, "model": "sysinfo-cli" },
+    });
+    if let Some(unit) = unit {
+        config["unit_of_measurement"] = json!(unit);
+    }
+    if let Some(device_class) = device_class {
+        config["device_class"] = json!(device_class);
+    }
+    (format!("homeassistant/sensor/{}/{}/config", node, key), config.to_string())
+}
+
+/// Turns a label value into a path component: everything but letters, digits, `-`,
+/// and `_` (including the `.` separator) becomes `_`, trimmed at both ends, so
+/// `/dev/sda1` becomes `dev_sda1`.
+fn sanitize(value: &str) -> String {
+    let component: String = value.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    component.trim_matches('_').to_string()
+}
+
+/// Joins `lines` into newline-separated datagrams of at most `MAX_PACKET` bytes.
+pub fn packets(lines: &[String]) -> Vec<String> {
+    let mut packets: Vec<String> = Vec::new();
+    for line in lines {
+        match packets.last_mut() {
+            Some(packet) if packet.len() + 1 + line.len() <= MAX_PACKET => {
+                packet.push('\n');
+                packet.push_str(line);
+            }
+            _ => packets.push(line.clone()),
+        }
+    }
+    packets
+}
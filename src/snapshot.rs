@@ -0,0 +1,68 @@
+//! Machine state saved to JSON by `snapshot`, and compared by `diff`.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+use sysinfo::System;
+use crate::args::SortBy;
+use crate::collector::*;
+use crate::models::{Change, DiskChange, ProcessInfo, Snapshot, SnapshotDiff};
+
+pub fn take(sys: &System) -> Snapshot {
+    Snapshot {
+        taken_at: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+        system: get_system_info(),
+        cpu: get_cpu_info(sys),
+        memory: get_memory_info(sys),
+        disks: get_disks_info(false),
+        network: get_network_info(),
+        processes: get_processes_info(sys, None, None, &[SortBy::Pid], None, false, ProcessExtras { io: false, ..ProcessExtras::ALL }),
+    }
+}
+
+pub fn read(path: &Path) -> Result<Snapshot, String> {
+    let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn change(before: u64, after: u64) -> Change {
+    Change { before, after, change: after as i64 - before as i64 }
+}
+
+/// What changed from `a` to `b`. Processes are matched by PID and name, so a reused
+/// PID counts as one process exiting and another starting.
+pub fn diff(a: &Snapshot, b: &Snapshot) -> SnapshotDiff {
+    let names: BTreeSet<&str> = a.disks.iter().chain(&b.disks).map(|d| d.name.as_str()).collect();
+    let available = |snapshot: &Snapshot, name: &str| snapshot.disks.iter().find(|d| d.name == name).map(|d| d.available_space);
+    let disks = names.into_iter()
+        .map(|name| {
+            let (before, after) = (available(a, name), available(b, name));
+            let change = after.unwrap_or(0) as i64 - before.unwrap_or(0) as i64;
+            DiskChange { name: name.to_string(), before, after, change }
+        })
+        .filter(|d| d.change != 0 || d.before.is_none() || d.after.is_none())
+        .collect();
+
+    let keys = |processes: &[ProcessInfo]| -> HashSet<(u32, String)> {
+        processes.iter().map(|p| (p.pid, p.name.clone())).collect()
+    };
+    let (before, after) = (keys(&a.processes), keys(&b.processes));
+    let only_in = |processes: &[ProcessInfo], other: &HashSet<(u32, String)>| -> Vec<ProcessInfo> {
+        let mut only: Vec<ProcessInfo> = processes.iter().filter(|p| !other.contains(&(p.pid, p.name.clone()))).cloned().collect();
+        only.sort_by_key(|p| p.pid);
+        only
+    };
+
+    SnapshotDiff {
+        from: a.taken_at.clone(),
+        to: b.taken_at.clone(),
+        // Some platforms derive the boot time from the uptime, so it can drift a little.
+        rebooted: a.system.boot_time.abs_diff(b.system.boot_time) > 60,
+        used_memory: change(a.memory.used_memory, b.memory.used_memory),
+        used_swap: change(a.memory.used_swap, b.memory.used_swap),
+        disks,
+        started: only_in(&b.processes, &before),
+        exited: only_in(&a.processes, &after),
+    }
+}
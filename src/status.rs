@@ -0,0 +1,15 @@
+//! Exit statuses, documented in the README so scripts can tell outcomes apart.
+
+/// Something failed at run time: a file couldn't be read or written, a connection
+/// failed, or nothing matched.
+pub const ERROR: i32 = 1;
+/// A threshold was crossed: an `alert` rule fired, `load --threshold` was exceeded, a
+/// disk reached `disks --warn-pct` or `--crit-pct`, or a sensor reached
+/// `components --warn` or `--crit`.
+pub const THRESHOLD: i32 = 2;
+/// The requested data isn't available here, e.g. no sensors or no load average. Also
+/// `alert`'s status for invalid rules, as Nagios' UNKNOWN.
+pub const UNAVAILABLE: i32 = 3;
+/// The command line was invalid (`EX_USAGE` from sysexits.h), so it can't be mistaken
+/// for a threshold breach.
+pub const USAGE: i32 = 64;
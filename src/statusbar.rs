@@ -0,0 +1,119 @@
+//! `statusbar`: one line per refresh in the JSON a Waybar custom module reads,
+//! `{"text", "tooltip", "class", "percentage"}`, filled in from `alert`'s metrics. The
+//! class turns `warning` or `critical` while any of those levels' rules fires, for the
+//! bar's stylesheet to pick up.
+
+use std::str::FromStr;
+use crate::alert::{self, parse_metric, Readings, Rule};
+use crate::fmt::format_bytes;
+use crate::models::{AlertStatus, StatusbarLine, UsageLevel};
+
+/// One of `alert`'s metrics, with a mount point for the disk ones, as in `cpu` or
+/// `disk_used_pct@/home`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metric {
+    pub name: &'static str,
+    pub mount: Option<String>,
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let (name, mount) = parse_metric(text)?;
+        Ok(Metric { name, mount })
+    }
+}
+
+impl Metric {
+    pub fn value(&self, readings: &Readings) -> Option<f64> {
+        readings.value(self.name, self.mount.as_deref())
+    }
+
+    /// The value as shown in a template: whole percent and °C, loads to two decimals,
+    /// sizes scaled, and `-` when it can't be read.
+    fn format(&self, readings: &Readings) -> String {
+        match self.value(readings) {
+            None => "-".to_string(),
+            Some(value) if alert::is_bytes(self.name) => format_bytes(value as u64),
+            Some(value) if self.name.starts_with("load") => format!("{:.2}", value),
+            Some(value) => format!("{:.0}", value),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Text(String),
+    Metric(Metric),
+}
+
+/// A `--text` or `--tooltip` template: text with `{metric}` placeholders, `\n` for a
+/// line break, and `{{` and `}}` for literal braces.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template(Vec<Segment>);
+
+impl FromStr for Template {
+    type Err = String;
+
+    fn from_str(template: &str) -> Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.next_if_eq(&'{').is_some() => text.push('{'),
+                '}' if chars.next_if_eq(&'}').is_some() => text.push('}'),
+                '\\' if chars.next_if_eq(&'n').is_some() => text.push('\n'),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed `{{` in `{}`", template)),
+                        }
+                    }
+                    let metric = name.parse().map_err(|e| format!("`{{{}}}` in `{}`: {}", name, template, e))?;
+                    if !text.is_empty() {
+                        segments.push(Segment::Text(std::mem::take(&mut text)));
+                    }
+                    segments.push(Segment::Metric(metric));
+                }
+                c => text.push(c),
+            }
+        }
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        Ok(Template(segments))
+    }
+}
+
+impl Template {
+    pub fn render(&self, readings: &Readings) -> String {
+        self.0.iter().map(|segment| match segment {
+            Segment::Text(text) => text.clone(),
+            Segment::Metric(metric) => metric.format(readings),
+        }).collect()
+    }
+}
+
+/// What `statusbar` prints for `readings`. `percentage` is the metric's value clamped
+/// to 0–100, for Waybar's `format-icons`.
+pub fn line(text: &Template, tooltip: &Template, percentage: &Metric, warning: &[Rule], critical: &[Rule], readings: &Readings) -> StatusbarLine {
+    let fires = |rules: &[Rule]| alert::evaluate(rules, readings).iter().any(|r| r.status == AlertStatus::Fired);
+    let class = if fires(critical) {
+        UsageLevel::Critical
+    } else if fires(warning) {
+        UsageLevel::Warning
+    } else {
+        UsageLevel::Ok
+    };
+    StatusbarLine {
+        text: text.render(readings),
+        tooltip: tooltip.render(readings),
+        class,
+        percentage: percentage.value(readings).map(|value| value.clamp(0.0, 100.0).round() as u8),
+    }
+}
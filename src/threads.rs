@@ -0,0 +1,103 @@
+//! The threads of a process from `/proc/<pid>/task` (Linux), for `process --threads`:
+//! which of a service's threads are busy, where sysinfo only sums up the process.
+
+use std::fs;
+use std::time::Instant;
+use sysinfo::ProcessStatus;
+use crate::models::ThreadInfo;
+
+/// One thread's state and cumulative CPU time when it was read.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TaskStat {
+    pub tid: u32,
+    pub name: String,
+    pub state: char,
+    /// User plus system time, in clock ticks.
+    pub ticks: u64,
+}
+
+/// Parses a `/proc/<pid>/task/<tid>/stat` line. The name is in parentheses and may
+/// itself hold spaces and parentheses, so the other fields are counted from the last `)`.
+pub fn parse_task_stat(text: &str) -> Option<TaskStat> {
+    let (tid, rest) = text.split_once(" (")?;
+    let (name, fields) = rest.rsplit_once(')')?;
+    let fields: Vec<&str> = fields.split_whitespace().collect();
+    // Fields 3, 14, and 15 of proc(5): state, utime, and stime.
+    let state = fields.first()?.chars().next()?;
+    let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+    Some(TaskStat { tid: tid.trim().parse().ok()?, name: name.to_string(), state, ticks })
+}
+
+/// Every thread of `pid`; none where there's no `/proc` or the process is gone.
+pub fn read_tasks(pid: u32) -> Vec<TaskStat> {
+    let Ok(entries) = fs::read_dir(format!("/proc/{}/task", pid)) else { return Vec::new() };
+    entries.filter_map(|entry| {
+        let text = fs::read_to_string(entry.ok()?.path().join("stat")).ok()?;
+        parse_task_stat(&text)
+    }).collect()
+}
+
+/// The state letters of proc(5), named as sysinfo names a process's status.
+pub fn task_status(state: char) -> ProcessStatus {
+    match state {
+        'R' => ProcessStatus::Run,
+        'S' => ProcessStatus::Sleep,
+        'I' => ProcessStatus::Idle,
+        'D' => ProcessStatus::UninterruptibleDiskSleep,
+        'Z' => ProcessStatus::Zombie,
+        'T' => ProcessStatus::Stop,
+        't' => ProcessStatus::Tracing,
+        'X' | 'x' => ProcessStatus::Dead,
+        'K' => ProcessStatus::Wakekill,
+        'W' => ProcessStatus::Waking,
+        'P' => ProcessStatus::Parked,
+        other => ProcessStatus::Unknown(other as u32),
+    }
+}
+
+fn clock_ticks_per_second() -> f64 {
+    #[cfg(target_os = "linux")]
+    // SAFETY: sysconf only reads a configuration value.
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    #[cfg(not(target_os = "linux"))]
+    let ticks = 100;
+    if ticks > 0 { ticks as f64 } else { 100.0 }
+}
+
+/// Per-thread CPU usage between readings of one process's threads.
+#[derive(Default)]
+pub struct ThreadSampler {
+    previous: Vec<TaskStat>,
+    read_at: Option<Instant>,
+}
+
+impl ThreadSampler {
+    /// Takes the first reading without reporting anything.
+    pub fn start(&mut self, pid: u32) {
+        self.previous = read_tasks(pid);
+        self.read_at = Some(Instant::now());
+    }
+
+    /// The threads of `pid`, busiest first, with their CPU usage since the previous
+    /// reading (100% is one core). A thread started since then counts from its start;
+    /// without a previous reading, every thread shows 0%.
+    pub fn sample(&mut self, pid: u32) -> Vec<ThreadInfo> {
+        let current = read_tasks(pid);
+        let now = Instant::now();
+        let seconds = self.read_at.map(|at| now.duration_since(at).as_secs_f64()).filter(|s| *s > 0.0);
+        let hz = clock_ticks_per_second();
+        let mut threads: Vec<ThreadInfo> = current.iter().map(|task| {
+            let before = self.previous.iter().find(|p| p.tid == task.tid).map_or(0, |p| p.ticks);
+            ThreadInfo {
+                tid: task.tid,
+                name: task.name.clone(),
+                status: task_status(task.state).to_string(),
+                cpu_usage: seconds.map_or(0.0, |s| (task.ticks.saturating_sub(before) as f64 / hz / s * 100.0) as f32),
+            }
+        }).collect();
+        threads.sort_by(|a, b| b.cpu_usage.total_cmp(&a.cpu_usage).then(a.tid.cmp(&b.tid)));
+        self.previous = current;
+        self.read_at = Some(now);
+        threads
+    }
+}
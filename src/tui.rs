@@ -0,0 +1,298 @@
+//! Interactive, top-like view: a CPU usage graph, memory gauges, and a sortable,
+//! scrollable process table.
+
+use std::collections::VecDeque;
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::widgets::{Block, Gauge, LineGauge, Paragraph, Row, Sparkline, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use sysinfo::{CpuRefreshKind, MemoryRefreshKind, Pid, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, Signal, System, Users};
+use crate::args::SortBy;
+use crate::collector::{process_info_from, sort_processes, ProcessFilter};
+use crate::fmt::{format_bytes, format_duration, format_float};
+use crate::i18n::tr;
+use crate::models::ProcessInfo;
+
+/// Samples of total CPU usage kept for the graph; more than any terminal is wide.
+const HISTORY: usize = 512;
+const PAGE: u16 = 10;
+
+#[derive(Clone)]
+enum Mode {
+    Browse,
+    Filter,
+    ConfirmKill { pid: u32, name: String },
+}
+
+struct App {
+    sys: System,
+    users: Users,
+    sort: SortBy,
+    filter: String,
+    mode: Mode,
+    /// Every process as of the last refresh, in `sort` order.
+    all_processes: Vec<ProcessInfo>,
+    /// The rows shown: `all_processes` matching `filter`.
+    processes: Vec<ProcessInfo>,
+    table: TableState,
+    cpu_history: VecDeque<u64>,
+    status: Option<String>,
+}
+
+/// Takes over the terminal until the user quits, refreshing every `interval`.
+pub fn run(interval: Duration, sort: SortBy) -> io::Result<()> {
+    let mut app = App::new(sort);
+    let mut terminal = ratatui::init();
+    let result = app.run(&mut terminal, interval);
+    ratatui::restore();
+    result
+}
+
+fn process_refresh_kind() -> ProcessRefreshKind {
+    // Disk usage is only for sorting by `disk-read` or `disk-write`.
+    ProcessRefreshKind::nothing().with_cpu().with_memory().with_disk_usage()
+}
+
+impl App {
+    fn new(sort: SortBy) -> Self {
+        let sys = System::new_with_specifics(
+            RefreshKind::nothing()
+                .with_processes(process_refresh_kind())
+                .with_cpu(CpuRefreshKind::nothing().with_cpu_usage())
+                .with_memory(MemoryRefreshKind::nothing().with_ram().with_swap())
+        );
+        let mut app = App {
+            sys,
+            users: Users::new_with_refreshed_list(),
+            sort,
+            filter: String::new(),
+            mode: Mode::Browse,
+            all_processes: Vec::new(),
+            processes: Vec::new(),
+            table: TableState::default().with_selected(0),
+            cpu_history: VecDeque::with_capacity(HISTORY),
+            status: None,
+        };
+        // CPU usage needs two samples.
+        thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        app.refresh();
+        app
+    }
+
+    fn refresh(&mut self) {
+        self.sys.refresh_cpu_usage();
+        self.sys.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram().with_swap());
+        self.sys.refresh_processes_specifics(ProcessesToUpdate::All, true, process_refresh_kind());
+        if self.cpu_history.len() == HISTORY {
+            self.cpu_history.pop_front();
+        }
+        self.cpu_history.push_back(self.sys.global_cpu_usage().round() as u64);
+        self.users.refresh();
+        self.all_processes = process_info_from(&self.sys, &self.users, None, true);
+        sort_processes(&mut self.all_processes, &[self.sort], None);
+        self.update_rows();
+    }
+
+    /// Filters the rows from the last refresh, so typing doesn't read every process again.
+    fn update_rows(&mut self) {
+        let filter = (!self.filter.is_empty()).then(|| ProcessFilter::substring(&self.filter));
+        self.processes = self.all_processes.iter()
+            .filter(|p| filter.as_ref().is_none_or(|f| f.matches(&p.name, &[])))
+            .cloned()
+            .collect();
+        if self.processes.is_empty() {
+            self.table.select(None);
+        } else {
+            let selected = self.table.selected().unwrap_or(0);
+            self.table.select(Some(selected.min(self.processes.len() - 1)));
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal, interval: Duration) -> io::Result<()> {
+        let mut last_refresh = Instant::now();
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            if event::poll(interval.saturating_sub(last_refresh.elapsed()))?
+                && let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+                && !self.handle_key(key.code)
+            {
+                return Ok(());
+            }
+            if last_refresh.elapsed() >= interval {
+                self.refresh();
+                last_refresh = Instant::now();
+            }
+        }
+    }
+
+    /// Applies one key press; returns `false` when the user quits.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match self.mode.clone() {
+            Mode::Filter => match code {
+                KeyCode::Enter => self.mode = Mode::Browse,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.mode = Mode::Browse;
+                    self.update_rows();
+                }
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                    self.update_rows();
+                }
+                KeyCode::Char(c) => {
+                    self.filter.push(c);
+                    self.update_rows();
+                }
+                _ => {}
+            },
+            Mode::ConfirmKill { pid, name } => {
+                if code == KeyCode::Char('y') {
+                    self.status = Some(self.kill(pid, &name));
+                }
+                self.mode = Mode::Browse;
+            }
+            Mode::Browse => {
+                self.status = None;
+                match code {
+                    KeyCode::Char('q') | KeyCode::Esc => return false,
+                    KeyCode::Up => self.table.select_previous(),
+                    KeyCode::Down => self.table.select_next(),
+                    KeyCode::PageUp => self.table.scroll_up_by(PAGE),
+                    KeyCode::PageDown => self.table.scroll_down_by(PAGE),
+                    KeyCode::Home => self.table.select_first(),
+                    KeyCode::End => self.table.select_last(),
+                    KeyCode::Char('c') => self.set_sort(SortBy::Cpu),
+                    KeyCode::Char('m') => self.set_sort(SortBy::Memory),
+                    KeyCode::Char('p') => self.set_sort(SortBy::Pid),
+                    KeyCode::Char('n') => self.set_sort(SortBy::Name),
+                    KeyCode::Char('/') => self.mode = Mode::Filter,
+                    KeyCode::Char('k') => {
+                        let selected = self.table.selected().and_then(|i| self.processes.get(i));
+                        if let Some(p) = selected {
+                            self.mode = Mode::ConfirmKill { pid: p.pid, name: p.name.clone() };
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        true
+    }
+
+    fn set_sort(&mut self, sort: SortBy) {
+        self.sort = sort;
+        sort_processes(&mut self.all_processes, &[sort], None);
+        self.update_rows();
+    }
+
+    /// Asks the process to terminate, falling back to a hard kill where signals don't exist.
+    fn kill(&self, pid: u32, name: &str) -> String {
+        let process = self.sys.process(Pid::from_u32(pid));
+        let sent = process.is_some_and(|p| p.kill_with(Signal::Term).unwrap_or_else(|| p.kill()));
+        let message = if sent { tr("tui-signal-sent") } else { tr("tui-signal-failed") };
+        format!("{} {} ({})", message, pid, name)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [cpu_area, memory_area, table_area, footer_area] = Layout::vertical([
+            Constraint::Length(8),
+            Constraint::Length(3),
+            Constraint::Fill(1),
+            Constraint::Length(1),
+        ]).areas(frame.area());
+        self.draw_cpu(frame, cpu_area);
+        self.draw_memory(frame, memory_area);
+        self.draw_processes(frame, table_area);
+
+        let footer = match &self.mode {
+            Mode::Filter => format!("{} {}_", tr("tui-filter"), self.filter),
+            Mode::ConfirmKill { pid, name } => format!("{} {} ({})? [y/N]", tr("tui-confirm-kill"), pid, name),
+            Mode::Browse => self.status.clone().unwrap_or_else(|| tr("tui-help")),
+        };
+        frame.render_widget(Paragraph::new(footer).reversed(), footer_area);
+    }
+
+    fn draw_cpu(&self, frame: &mut Frame, area: Rect) {
+        let [graph_area, cores_area] = Layout::horizontal([Constraint::Fill(2), Constraint::Fill(1)]).areas(area);
+
+        let width = graph_area.width.saturating_sub(2) as usize;
+        let history: Vec<u64> = self.cpu_history.iter().skip(self.cpu_history.len().saturating_sub(width)).copied().collect();
+        let title = format!(" {} {}% ", tr("tui-cpu"), format_float(self.sys.global_cpu_usage(), 1));
+        frame.render_widget(Sparkline::default().block(Block::bordered().title(title)).data(&history).max(100).cyan(), graph_area);
+
+        let block = Block::bordered().title(format!(" {} ", tr("section-cpus")));
+        let inner = block.inner(cores_area);
+        frame.render_widget(block, cores_area);
+        let cpus = self.sys.cpus();
+        let per_column = (inner.height as usize).max(1);
+        let columns = Layout::horizontal(vec![Constraint::Fill(1); cpus.len().div_ceil(per_column).max(1)]).split(inner);
+        for (i, cpu) in cpus.iter().enumerate() {
+            let column = columns[i / per_column];
+            let row = Rect { y: column.y + (i % per_column) as u16, height: 1, ..column };
+            let gauge = LineGauge::default()
+                .ratio(f64::from(cpu.cpu_usage() / 100.0).clamp(0.0, 1.0))
+                .label(format!("{:>3}", i))
+                .filled_style(Style::new().cyan());
+            frame.render_widget(gauge, row);
+        }
+    }
+
+    fn draw_memory(&self, frame: &mut Frame, area: Rect) {
+        let [ram_area, swap_area] = Layout::horizontal([Constraint::Fill(1), Constraint::Fill(1)]).areas(area);
+        let gauges = [
+            (tr("tui-memory"), self.sys.used_memory(), self.sys.total_memory(), ram_area),
+            (tr("tui-swap"), self.sys.used_swap(), self.sys.total_swap(), swap_area),
+        ];
+        for (title, used, total, area) in gauges {
+            let ratio = if total == 0 { 0.0 } else { used as f64 / total as f64 };
+            let gauge = Gauge::default()
+                .block(Block::bordered().title(format!(" {} ", title)))
+                .ratio(ratio.clamp(0.0, 1.0))
+                .label(format!("{} / {}", format_bytes(used), format_bytes(total)))
+                .gauge_style(Style::new().green());
+            frame.render_widget(gauge, area);
+        }
+    }
+
+    fn draw_processes(&mut self, frame: &mut Frame, area: Rect) {
+        let sort_column = match self.sort {
+            SortBy::Pid => Some(0),
+            SortBy::Name => Some(1),
+            SortBy::Cpu => Some(2),
+            SortBy::Memory => Some(3),
+            SortBy::DiskRead | SortBy::DiskWrite => None,
+        };
+        let header = ["col-pid", "col-name", "col-cpu-pct", "col-memory", "col-run-time"]
+            .iter()
+            .enumerate()
+            .map(|(i, key)| if Some(i) == sort_column { format!("{} ▼", tr(key)) } else { tr(key) });
+        let rows = self.processes.iter().map(|p| Row::new([
+            p.pid.to_string(),
+            p.name.clone(),
+            format_float(p.cpu_usage, 1),
+            format_bytes(p.memory),
+            format_duration(p.run_time),
+        ]));
+        let widths = [
+            Constraint::Length(8),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Length(16),
+        ];
+        let mut title = format!(" {} ({}) ", tr("section-processes"), self.processes.len());
+        if !self.filter.is_empty() {
+            title.push_str(&format!("{} {} ", tr("tui-filter"), self.filter));
+        }
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).bold().yellow())
+            .block(Block::bordered().title(title))
+            .row_highlight_style(Style::new().reversed());
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+}
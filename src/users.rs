@@ -0,0 +1,72 @@
+//! User accounts and login sessions.
+
+use sysinfo::Users;
+use crate::collector::natural_cmp;
+use crate::models::{SessionInfo, UserInfo};
+
+/// Every account on the system with its groups and how many sessions it has open.
+pub fn get_users_info(sessions: &[SessionInfo]) -> Vec<UserInfo> {
+    let users = Users::new_with_refreshed_list();
+    let mut info: Vec<UserInfo> = users.iter().map(|user| {
+        let mut groups: Vec<String> = user.groups().iter().map(|g| g.name().to_string()).collect();
+        groups.sort_by(|a, b| natural_cmp(a, b));
+        UserInfo {
+            name: user.name().to_string(),
+            uid: user.id().to_string(),
+            gid: user.group_id().to_string(),
+            groups,
+            sessions: sessions.iter().filter(|s| s.user == user.name()).count(),
+        }
+    }).collect();
+    info.sort_by(|a, b| natural_cmp(&a.name, &b.name));
+    info
+}
+
+/// Active login sessions from utmp (Linux); empty elsewhere.
+pub fn get_sessions() -> Vec<SessionInfo> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::sessions()
+    }
+    #[cfg(not(target_os = "linux"))]
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::time::{Duration, UNIX_EPOCH};
+    use crate::models::SessionInfo;
+
+    pub fn sessions() -> Vec<SessionInfo> {
+        let mut sessions = Vec::new();
+        // SAFETY: the utmpx functions are only called from this thread, and each record
+        // is copied out before the next call overwrites it.
+        unsafe {
+            libc::setutxent();
+            loop {
+                let entry = libc::getutxent();
+                if entry.is_null() {
+                    break;
+                }
+                let entry = &*entry;
+                if entry.ut_type != libc::USER_PROCESS {
+                    continue;
+                }
+                let field = |chars: &[libc::c_char]| {
+                    let bytes: Vec<u8> = chars.iter().take_while(|&&c| c != 0).map(|&c| c as u8).collect();
+                    String::from_utf8_lossy(&bytes).into_owned()
+                };
+                let host = field(&entry.ut_host);
+                let login = UNIX_EPOCH + Duration::from_secs(entry.ut_tv.tv_sec.max(0) as u64);
+                sessions.push(SessionInfo {
+                    user: field(&entry.ut_user),
+                    tty: field(&entry.ut_line),
+                    host: (!host.is_empty()).then_some(host),
+                    login_time: humantime::format_rfc3339_seconds(login).to_string(),
+                });
+            }
+            libc::endutxent();
+        }
+        sessions
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use sysinfo::{
+    Components, CpuRefreshKind, Disks, MemoryRefreshKind, Networks, ProcessRefreshKind, ProcessesToUpdate, RefreshKind, System, UpdateKind
+};
+use crate::collector::{disk_info_from, network_info_from};
+use crate::cpustat;
+use crate::models::*;
+
+/// Rolling per-core usage samples kept across watch iterations.
+pub struct CpuHistory {
+    len: usize,
+    samples: Vec<VecDeque<f32>>,
+}
+
+impl CpuHistory {
+    pub fn new(len: usize) -> Self {
+        CpuHistory { len: len.max(1), samples: Vec::new() }
+    }
+
+    /// Records the current sample and fills in each core's recent average and peak.
+    pub fn record(&mut self, info: &mut CpuInfo) {
+        self.samples.resize_with(info.cpus.len(), VecDeque::new);
+        for (cpu, history) in info.cpus.iter_mut().zip(self.samples.iter_mut()) {
+            history.push_back(cpu.usage);
+            if history.len() > self.len {
+                history.pop_front();
+            }
+            cpu.avg_usage = Some(history.iter().sum::<f32>() / history.len() as f32);
+            cpu.peak_usage = history.iter().copied().reduce(f32::max);
+        }
+    }
+}
+
+/// The previous `/proc/stat` counters, so `cpu --detailed` covers the time since then.
+#[derive(Default)]
+pub struct CpuTimeSampler {
+    previous: Vec<(Option<usize>, cpustat::CpuTimes)>,
+}
+
+impl CpuTimeSampler {
+    /// Takes the first reading without reporting anything.
+    pub fn start(&mut self) {
+        self.previous = cpustat::read_proc_stat();
+    }
+
+    /// Fills in where `info`'s time went, overall and per CPU, since the previous reading.
+    pub fn record(&mut self, info: &mut CpuInfo) {
+        let current = cpustat::read_proc_stat();
+        for (id, after) in &current {
+            let Some((_, before)) = self.previous.iter().find(|(i, _)| i == id) else { continue };
+            let breakdown = cpustat::breakdown(before, after);
+            match id {
+                None => info.breakdown = breakdown,
+                Some(id) => {
+                    if let Some(cpu) = info.cpus.iter_mut().find(|cpu| cpu.id == *id) {
+                        cpu.breakdown = breakdown;
+                    }
+                }
+            }
+        }
+        self.previous = current;
+    }
+}
+
+/// Previous available space and I/O counters per disk, used to derive fill/free and
+/// throughput rates between watch iterations.
+#[derive(Default)]
+pub struct DiskRates {
+    disks: Option<Disks>,
+    /// Keyed by mount point: several file systems can share one device name.
+    previous: HashMap<String, DiskSample>,
+}
+
+struct DiskSample {
+    available: u64,
+    read: Option<u64>,
+    written: Option<u64>,
+    at: Instant,
+}
+
+impl DiskRates {
+    /// Refreshes the disk list kept since the first call and records the new sample.
+    pub fn sample(&mut self, io: bool) -> Vec<DiskInfo> {
+        let disks = match &mut self.disks {
+            Some(disks) => {
+                disks.refresh(true);
+                disks
+            }
+            None => self.disks.insert(Disks::new_with_refreshed_list()),
+        };
+        let mut info = disk_info_from(disks, io);
+        self.record(&mut info, Instant::now());
+        info
+    }
+
+    /// Fills in each disk's change rate and, when it is filling up, the estimated time until full,
+    /// plus read and write throughput for disks with I/O counters.
+    pub fn record(&mut self, disks: &mut [DiskInfo], now: Instant) {
+        for disk in disks.iter_mut() {
+            if let Some(prev) = self.previous.get(&disk.mount_point) {
+                let elapsed = now.duration_since(prev.at).as_secs_f64();
+                if elapsed > 0.0 {
+                    let rate = (disk.available_space as f64 - prev.available as f64) / elapsed;
+                    disk.available_rate = Some(rate);
+                    disk.time_to_full = (rate < 0.0).then(|| (disk.available_space as f64 / -rate) as u64);
+                    // Counters reset when a device is re-attached; skip that sample.
+                    let throughput = |now: Option<u64>, prev: Option<u64>| match (now, prev) {
+                        (Some(now), Some(prev)) if now >= prev => Some((now - prev) as f64 / elapsed),
+                        _ => None,
+                    };
+                    disk.read_rate = throughput(disk.read_bytes, prev.read);
+                    disk.write_rate = throughput(disk.written_bytes, prev.written);
+                }
+            }
+            self.previous.insert(disk.mount_point.clone(), DiskSample {
+                available: disk.available_space,
+                read: disk.read_bytes,
+                written: disk.written_bytes,
+                at: now,
+            });
+        }
+    }
+}
+
+/// Previous disk I/O totals per process, for `processes --io` rates in watch mode.
+#[derive(Default)]
+pub struct ProcessIoRates {
+    previous: HashMap<u32, (Option<u64>, Option<u64>, Instant)>,
+}
+
+impl ProcessIoRates {
+    /// Fills in read and write rates for processes seen in the previous sample.
+    pub fn record(&mut self, processes: &mut [ProcessInfo], now: Instant) {
+        let mut previous = HashMap::with_capacity(processes.len());
+        for p in processes.iter_mut() {
+            if let Some((read, written, at)) = self.previous.get(&p.pid) {
+                let elapsed = now.duration_since(*at).as_secs_f64();
+                // A reused PID starts again from zero; skip that sample.
+                let rate = |now: Option<u64>, prev: Option<u64>| match (now, prev) {
+                    (Some(now), Some(prev)) if now >= prev && elapsed > 0.0 => Some((now - prev) as f64 / elapsed),
+                    _ => None,
+                };
+                p.read_rate = rate(p.read_bytes, *read);
+                p.write_rate = rate(p.written_bytes, *written);
+            }
+            previous.insert(p.pid, (p.read_bytes, p.written_bytes, now));
+        }
+        // Exited processes are dropped here.
+        self.previous = previous;
+    }
+}
+
+/// Keeps one `Networks` across watch iterations so sysinfo's per-refresh counters give
+/// each interface's throughput since the previous iteration.
+#[derive(Default)]
+pub struct NetworkRates {
+    state: Option<(Networks, Instant)>,
+    /// Whether `sample` refreshed the interfaces since the last `traffic` call.
+    sampled: bool,
+}
+
+impl NetworkRates {
+    /// Current interfaces; rates, and with `delta` the counters since the previous
+    /// call, are filled in from the second call on.
+    pub fn sample(&mut self, delta: bool) -> Vec<NetworkInfo> {
+        self.sampled = true;
+        match &mut self.state {
+            Some((networks, refreshed)) => {
+                networks.refresh(true);
+                let elapsed = refreshed.elapsed();
+                *refreshed = Instant::now();
+                network_info_from(networks, Some(elapsed), delta)
+            }
+            None => {
+                let networks = Networks::new_with_refreshed_list();
+                let info = network_info_from(&networks, None, delta);
+                self.state = Some((networks, Instant::now()));
+                info
+            }
+        }
+    }
+
+    /// Bytes received and transmitted on all interfaces since the previous refresh,
+    /// refreshing them first unless `sample` already did since the last call.
+    pub fn traffic(&mut self) -> (u64, u64) {
+        if let Some((networks, refreshed)) = &mut self.state && !std::mem::take(&mut self.sampled) {
+            networks.refresh(true);
+            *refreshed = Instant::now();
+        }
+        let (networks, _) = self.state.get_or_insert_with(|| (Networks::new_with_refreshed_list(), Instant::now()));
+        (networks.values().map(|n| n.received()).sum(), networks.values().map(|n| n.transmitted()).sum())
+    }
+}
+
+/// Sleeps for `duration`, waking early once `stop` is set.
+pub fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::SeqCst) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        thread::sleep(remaining.min(Duration::from_millis(100)));
+    }
+}
+
+/// One process as seen by a `ProcessFollower` refresh.
+pub struct SeenProcess {
+    pub pid: u32,
+    /// Start time since the epoch, so a reused pid counts as a new process.
+    pub start_time: u64,
+    pub name: String,
+    pub parent: Option<u32>,
+    pub run_time: u64,
+}
+
+/// Turns successive process lists into start and exit events.
+#[derive(Default)]
+pub struct ProcessFollower {
+    known: Option<HashMap<(u32, u64), SeenProcess>>,
+}
+
+impl ProcessFollower {
+    /// Returns the processes that appeared or disappeared since the last call, starts
+    /// first and each group ordered by pid. The first call only records the baseline.
+    pub fn record(&mut self, current: Vec<SeenProcess>, timestamp: &str) -> Vec<ProcessEvent> {
+        let current: HashMap<(u32, u64), SeenProcess> = current.into_iter().map(|p| ((p.pid, p.start_time), p)).collect();
+        let Some(previous) = self.known.replace(current) else {
+            return Vec::new();
+        };
+        let current = self.known.as_ref().expect("just stored");
+        let event = |kind, p: &SeenProcess| ProcessEvent {
+            timestamp: timestamp.to_string(),
+            event: kind,
+            pid: p.pid,
+            name: p.name.clone(),
+            parent: p.parent,
+            run_time: (kind == ProcessEventKind::Exit).then_some(p.run_time),
+        };
+        let mut started: Vec<_> = current.iter().filter(|(k, _)| !previous.contains_key(k)).map(|(_, p)| event(ProcessEventKind::Start, p)).collect();
+        let mut exited: Vec<_> = previous.iter().filter(|(k, _)| !current.contains_key(k)).map(|(_, p)| event(ProcessEventKind::Exit, p)).collect();
+        started.sort_by_key(|e| e.pid);
+        exited.sort_by_key(|e| e.pid);
+        started.extend(exited);
+        started
+    }
+}
+
+/// Remembers processes between watch iterations so `--highlight` can mark what changed.
+#[derive(Default)]
+pub struct ProcessChanges {
+    follower: ProcessFollower,
+    /// CPU usage and resident memory of the rows shown last time, by PID.
+    pub previous: HashMap<u32, (f32, u64)>,
+    /// Processes that started since the previous iteration.
+    pub started: HashSet<u32>,
+    pub exited: Vec<ProcessEvent>,
+}
+
+impl ProcessChanges {
+    /// Compares every process matching the filter, not just the rows shown, with the
+    /// previous call, so a process leaving a `--limit` doesn't count as exited.
+    pub fn record(&mut self, all: Vec<SeenProcess>, timestamp: &str) {
+        let (started, exited): (Vec<_>, Vec<_>) = self.follower.record(all, timestamp)
+            .into_iter()
+            .partition(|e| e.event == ProcessEventKind::Start);
+        self.started = started.iter().map(|e| e.pid).collect();
+        self.exited = exited;
+    }
+
+    /// Keeps the rows just shown for the next comparison.
+    pub fn remember(&mut self, shown: &[ProcessInfo]) {
+        self.previous = shown.iter().map(|p| (p.pid, (p.cpu_usage, p.memory))).collect();
+    }
+}
+
+/// Attached hardware as seen by one `HardwareWatcher` poll.
+#[derive(Default, Clone)]
+pub struct HardwareSnapshot {
+    /// Disk name to mount point and size.
+    pub disks: BTreeMap<String, String>,
+    pub interfaces: BTreeSet<String>,
+    /// Power supply name to state.
+    pub power: BTreeMap<String, String>,
+}
+
+/// Turns successive hardware snapshots into attach/detach and power events.
+#[derive(Default)]
+pub struct HardwareWatcher {
+    previous: Option<HardwareSnapshot>,
+}
+
+impl HardwareWatcher {
+    /// Returns what changed since the last call; the first call only records the baseline.
+    pub fn record(&mut self, current: HardwareSnapshot, timestamp: &str) -> Vec<HardwareEvent> {
+        let Some(previous) = self.previous.replace(current.clone()) else {
+            return Vec::new();
+        };
+        let event = |event, device: &str, detail: Option<&String>| HardwareEvent {
+            timestamp: timestamp.to_string(),
+            event,
+            device: device.to_string(),
+            detail: detail.cloned(),
+        };
+        let mut events = Vec::new();
+        for (disk, detail) in &previous.disks {
+            if !current.disks.contains_key(disk) {
+                events.push(event(HardwareEventKind::DiskDetached, disk, Some(detail)));
+            }
+        }
+        for (disk, detail) in &current.disks {
+            if !previous.disks.contains_key(disk) {
+                events.push(event(HardwareEventKind::DiskAttached, disk, Some(detail)));
+            }
+        }
+        for interface in previous.interfaces.difference(&current.interfaces) {
+            events.push(event(HardwareEventKind::InterfaceRemoved, interface, None));
+        }
+        for interface in current.interfaces.difference(&previous.interfaces) {
+            events.push(event(HardwareEventKind::InterfaceAdded, interface, None));
+        }
+        for (supply, state) in &current.power {
+            if previous.power.get(supply) != Some(state) {
+                events.push(event(HardwareEventKind::PowerChanged, supply, Some(state)));
+            }
+        }
+        events
+    }
+}
+
+/// Per-section latencies of the tool itself, for `--self-stats`.
+#[derive(Default)]
+pub struct SelfStatsTracker {
+    iterations: usize,
+    /// Section name and every recorded duration, in first-seen order.
+    sections: Vec<(&'static str, Vec<Duration>)>,
+}
+
+impl SelfStatsTracker {
+    pub fn record(&mut self, section: &'static str, elapsed: Duration) {
+        match self.sections.iter_mut().find(|(name, _)| *name == section) {
+            Some((_, samples)) => samples.push(elapsed),
+            None => self.sections.push((section, vec![elapsed])),
+        }
+    }
+
+    pub fn finish_iteration(&mut self) {
+        self.iterations += 1;
+    }
+
+    pub fn summary(&self, cpu_time_ms: Option<u64>, peak_rss: Option<u64>) -> SelfStats {
+        SelfStats {
+            iterations: self.iterations,
+            cpu_time_ms,
+            peak_rss,
+            sections: self.sections.iter().map(|(name, samples)| {
+                let ms: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+                SectionLatency {
+                    section: name.to_string(),
+                    samples: ms.len(),
+                    avg_ms: ms.iter().sum::<f64>() / ms.len() as f64,
+                    max_ms: ms.iter().copied().fold(0.0, f64::max),
+                }
+            }).collect(),
+        }
+    }
+}
+
+/// How long each of sysinfo's refreshes takes here, for `--profile-collection`. The
+/// first sample lists and reads everything from scratch, as a one-off run does; later
+/// ones refresh in place, as every watch tick does.
+pub struct CollectionProfiler {
+    sys: System,
+    disks: Disks,
+    networks: Networks,
+    components: Components,
+    stages: Vec<ProfiledStage>,
+}
+
+struct ProfiledStage {
+    name: &'static str,
+    items: Option<usize>,
+    first: Duration,
+    refreshes: Vec<Duration>,
+}
+
+impl Default for CollectionProfiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CollectionProfiler {
+    /// Takes the first, from-scratch sample.
+    pub fn new() -> Self {
+        let mut profiler = CollectionProfiler {
+            sys: System::new(),
+            disks: Disks::new(),
+            networks: Networks::new(),
+            components: Components::new(),
+            stages: Vec::new(),
+        };
+        profiler.sample();
+        profiler
+    }
+
+    /// Runs every stage once, in the order `all` reads them.
+    pub fn sample(&mut self) {
+        // What `processes` reads by default.
+        let processes = ProcessRefreshKind::nothing().with_cpu().with_memory()
+            .with_cmd(UpdateKind::OnlyIfNotSet).with_user(UpdateKind::OnlyIfNotSet);
+        let timings = [
+            ("cpu", timed(|| {
+                self.sys.refresh_cpu_specifics(CpuRefreshKind::everything());
+                Some(self.sys.cpus().len())
+            })),
+            ("memory", timed(|| {
+                self.sys.refresh_memory();
+                None
+            })),
+            ("processes", timed(|| {
+                self.sys.refresh_processes_specifics(ProcessesToUpdate::All, true, processes);
+                Some(self.sys.processes().len())
+            })),
+            ("disks", timed(|| {
+                self.disks.refresh(true);
+                Some(self.disks.len())
+            })),
+            ("networks", timed(|| {
+                self.networks.refresh(true);
+                Some(self.networks.len())
+            })),
+            ("components", timed(|| {
+                self.components.refresh(true);
+                Some(self.components.len())
+            })),
+        ];
+        for (name, (items, elapsed)) in timings {
+            match self.stages.iter_mut().find(|stage| stage.name == name) {
+                Some(stage) => {
+                    stage.items = items;
+                    stage.refreshes.push(elapsed);
+                }
+                None => self.stages.push(ProfiledStage { name, items, first: elapsed, refreshes: Vec::new() }),
+            }
+        }
+    }
+
+    /// The timings so far; a stage not yet refreshed in place shows zeros for it.
+    pub fn report(&self) -> Vec<StageTiming> {
+        self.stages.iter().map(|stage| {
+            let ms: Vec<f64> = stage.refreshes.iter().map(|d| d.as_secs_f64() * 1000.0).collect();
+            StageTiming {
+                stage: stage.name.to_string(),
+                items: stage.items,
+                first_ms: stage.first.as_secs_f64() * 1000.0,
+                refreshes: ms.len(),
+                avg_ms: if ms.is_empty() { 0.0 } else { ms.iter().sum::<f64>() / ms.len() as f64 },
+                max_ms: ms.iter().copied().fold(0.0, f64::max),
+            }
+        }).collect()
+    }
+}
+
+fn timed<T>(stage: impl FnOnce() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let result = stage();
+    (result, started.elapsed())
+}
+
+impl MetricStats {
+    /// The stats of `values`, or `None` without any. The percentile is the nearest rank:
+    /// the smallest value at least 95% of the values don't exceed.
+    pub fn of(values: &[f64]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let rank = (sorted.len() as f64 * 0.95).ceil() as usize;
+        Some(MetricStats {
+            min: sorted[0],
+            avg: values.iter().sum::<f64>() / values.len() as f64,
+            p95: sorted[rank.saturating_sub(1)],
+            max: sorted[sorted.len() - 1],
+        })
+    }
+}
+
+/// CPU and memory usage at every watch refresh, and network traffic in between,
+/// summarized when the run ends. It reads the watch loop's `System` and `NetworkRates`,
+/// refreshing only what the subcommand doesn't.
+pub struct RunStats {
+    /// What the watch loop refreshes in its `System` every tick.
+    kind: RefreshKind,
+    started: Instant,
+    /// When CPU usage was last refreshed.
+    cpu_refreshed: Instant,
+    cpu_usage: Vec<f64>,
+    memory_used: Vec<f64>,
+    received: u64,
+    transmitted: u64,
+}
+
+impl RunStats {
+    /// Starts the clock, the network counters, and the first CPU sample unless `kind`,
+    /// what the watch loop refreshes in `sys`, takes it.
+    pub fn start(kind: RefreshKind, sys: &mut System, networks: &mut NetworkRates) -> Self {
+        if !kind.cpu().is_some_and(|cpu| cpu.cpu_usage()) {
+            sys.refresh_cpu_usage();
+        }
+        networks.traffic();
+        let now = Instant::now();
+        RunStats {
+            kind,
+            started: now,
+            cpu_refreshed: now,
+            cpu_usage: Vec::new(),
+            memory_used: Vec::new(),
+            received: 0,
+            transmitted: 0,
+        }
+    }
+
+    /// Records memory in use, CPU usage, and the traffic since the previous sample. When
+    /// the loop doesn't refresh CPU usage, it's refreshed here unless the previous refresh
+    /// was too recent for a meaningful figure (as it can be on the first sample).
+    pub fn sample(&mut self, sys: &mut System, networks: &mut NetworkRates) {
+        let cpu_usage = if self.kind.cpu().is_some_and(|cpu| cpu.cpu_usage()) {
+            Some(sys.global_cpu_usage())
+        } else {
+            (self.cpu_refreshed.elapsed() >= sysinfo::MINIMUM_CPU_UPDATE_INTERVAL).then(|| {
+                sys.refresh_cpu_usage();
+                self.cpu_refreshed = Instant::now();
+                sys.global_cpu_usage()
+            })
+        };
+        if !self.kind.memory().is_some_and(|memory| memory.ram()) {
+            sys.refresh_memory_specifics(MemoryRefreshKind::nothing().with_ram());
+        }
+        self.record(cpu_usage, sys.used_memory());
+        let (received, transmitted) = networks.traffic();
+        self.add_traffic(received, transmitted);
+    }
+
+    pub fn add_traffic(&mut self, received: u64, transmitted: u64) {
+        self.received += received;
+        self.transmitted += transmitted;
+    }
+
+    pub fn record(&mut self, cpu_usage: Option<f32>, memory_used: u64) {
+        self.cpu_usage.extend(cpu_usage.map(f64::from));
+        self.memory_used.push(memory_used as f64);
+    }
+
+    pub fn summary(&self) -> WatchSummary {
+        WatchSummary {
+            samples: self.memory_used.len(),
+            elapsed_seconds: self.started.elapsed().as_secs_f64(),
+            cpu_usage: MetricStats::of(&self.cpu_usage),
+            memory_used: MetricStats::of(&self.memory_used),
+            network_received: self.received,
+            network_transmitted: self.transmitted,
+        }
+    }
+}
+
+/// Flags samples where the CPU frequency fell below the session peak while the
+/// hottest sensor was near its critical temperature.
+pub struct ThrottleTracker {
+    margin: f32,
+    drop_pct: f32,
+    samples: usize,
+    throttled: usize,
+    peak_frequency: u64,
+    min_frequency: Option<u64>,
+    max_temperature: Option<f32>,
+}
+
+impl ThrottleTracker {
+    /// `margin` is how many °C below critical counts as "near"; `drop_pct` is how far
+    /// below the peak frequency counts as a drop.
+    pub fn new(margin: f32, drop_pct: f32) -> Self {
+        ThrottleTracker {
+            margin,
+            drop_pct,
+            samples: 0,
+            throttled: 0,
+            peak_frequency: 0,
+            min_frequency: None,
+            max_temperature: None,
+        }
+    }
+
+    pub fn record(&mut self, sample: &mut ThermalSample) {
+        self.peak_frequency = self.peak_frequency.max(sample.frequency_mhz);
+        self.min_frequency = Some(self.min_frequency.map_or(sample.frequency_mhz, |f| f.min(sample.frequency_mhz)));
+        if let Some(t) = sample.temperature {
+            self.max_temperature = Some(self.max_temperature.map_or(t, |m| m.max(t)));
+        }
+
+        let dropped = (sample.frequency_mhz as f32) < self.peak_frequency as f32 * (1.0 - self.drop_pct / 100.0);
+        let hot = sample.temperature.is_some_and(|t| t >= sample.critical - self.margin);
+        sample.peak_frequency_mhz = Some(self.peak_frequency);
+        sample.throttling = dropped && hot;
+
+        self.samples += 1;
+        if sample.throttling {
+            self.throttled += 1;
+        }
+    }
+
+    pub fn summary(&self) -> ThrottleSummary {
+        ThrottleSummary {
+            samples: self.samples,
+            throttled_samples: self.throttled,
+            throttled_pct: if self.samples == 0 { 0.0 } else { self.throttled as f32 * 100.0 / self.samples as f32 },
+            peak_frequency_mhz: self.peak_frequency,
+            min_frequency_mhz: self.min_frequency.unwrap_or(0),
+            max_temperature: self.max_temperature,
+        }
+    }
+}
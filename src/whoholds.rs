@@ -0,0 +1,96 @@
+//! Finds the processes holding a file or directory open by walking `/proc/<pid>`
+//! (Linux): file descriptors, working and root directories, executables, and mappings.
+
+use std::io;
+use std::path::Path;
+use sysinfo::System;
+use crate::models::FileHolder;
+
+/// Processes with `target` open. For a directory, anything open below it counts too,
+/// which is what keeps a filesystem from unmounting.
+pub fn find_holders(sys: &System, target: &Path) -> io::Result<Vec<FileHolder>> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::find_holders(sys, target)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = (sys, target);
+        Err(io::Error::new(io::ErrorKind::Unsupported, "whoholds is only available on Linux"))
+    }
+}
+
+/// Splits the ` (deleted)` marker the kernel appends to unlinked files.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn strip_deleted(link: &str) -> (&str, bool) {
+    match link.strip_suffix(" (deleted)") {
+        Some(path) => (path, true),
+        None => (link, false),
+    }
+}
+
+/// Whether `path` is `target` or lies below it when `target` is a directory.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+pub fn is_held(path: &str, target: &str, directory: bool) -> bool {
+    path == target || (directory && Path::new(path).starts_with(target))
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use std::collections::BTreeSet;
+    use std::fs;
+    use std::io;
+    use std::path::Path;
+    use sysinfo::{Pid, System};
+    use crate::collector::natural_cmp;
+    use crate::models::FileHolder;
+    use super::{is_held, strip_deleted};
+
+    pub fn find_holders(sys: &System, target: &Path) -> io::Result<Vec<FileHolder>> {
+        let directory = fs::metadata(target)?.is_dir();
+        let target = fs::canonicalize(target)?;
+        let target = target.to_string_lossy();
+
+        let mut holders = Vec::new();
+        for entry in fs::read_dir("/proc")?.flatten() {
+            let Some(pid) = entry.file_name().to_str().and_then(|s| s.parse::<u32>().ok()) else { continue };
+            let dir = entry.path();
+            let name = sys.process(Pid::from_u32(pid))
+                .map(|p| p.name().to_string_lossy().into_owned())
+                .or_else(|| fs::read_to_string(dir.join("comm")).ok().map(|c| c.trim().to_string()))
+                .unwrap_or_default();
+            let mut hold = |usage: String, link: &str| {
+                let (path, deleted) = strip_deleted(link);
+                if is_held(path, &target, directory) {
+                    holders.push(FileHolder { pid, name: name.clone(), usage, path: path.to_string(), deleted });
+                }
+            };
+
+            for usage in ["cwd", "root", "exe"] {
+                if let Ok(link) = fs::read_link(dir.join(usage)) {
+                    hold(usage.to_string(), &link.to_string_lossy());
+                }
+            }
+            if let Ok(fds) = fs::read_dir(dir.join("fd")) {
+                for fd in fds.flatten() {
+                    if let Ok(link) = fs::read_link(fd.path()) {
+                        hold(format!("fd {}", fd.file_name().to_string_lossy()), &link.to_string_lossy());
+                    }
+                }
+            }
+            // Shared libraries and other mapped files, once per path.
+            if let Ok(maps) = fs::read_to_string(dir.join("maps")) {
+                let mapped: BTreeSet<&str> = maps.lines()
+                    .filter_map(|line| line.splitn(6, char::is_whitespace).nth(5))
+                    .map(str::trim)
+                    .filter(|path| path.starts_with('/'))
+                    .collect();
+                for path in mapped {
+                    hold("mmap".to_string(), path);
+                }
+            }
+        }
+        holders.sort_by(|a, b| a.pid.cmp(&b.pid).then_with(|| natural_cmp(&a.usage, &b.usage)));
+        Ok(holders)
+    }
+}
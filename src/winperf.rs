@@ -0,0 +1,108 @@
+//! Windows performance data that sysinfo doesn't expose (commit charge, disk queue
+//! length, handle counts). Every function returns `None` on other platforms.
+
+use std::path::Path;
+
+/// Committed memory and commit limit in bytes.
+pub fn commit_charge() -> Option<(u64, u64)> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::ProcessStatus::{GetPerformanceInfo, PERFORMANCE_INFORMATION};
+        let mut info = PERFORMANCE_INFORMATION {
+            cb: std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: `info` is a properly sized, writable PERFORMANCE_INFORMATION.
+        if unsafe { GetPerformanceInfo(&mut info, info.cb) } == 0 {
+            return None;
+        }
+        let page = info.PageSize as u64;
+        Some((info.CommitTotal as u64 * page, info.CommitLimit as u64 * page))
+    }
+    #[cfg(not(windows))]
+    None
+}
+
+/// Current disk queue length of the logical disk mounted at `mount_point` (e.g. `C:\`).
+pub fn disk_queue_length(mount_point: &Path) -> Option<f64> {
+    #[cfg(windows)]
+    {
+        let volume = mount_point.to_string_lossy();
+        let volume = volume.trim_end_matches('\\');
+        if volume.is_empty() {
+            return None;
+        }
+        pdh_counter(&format!("\\LogicalDisk({})\\Current Disk Queue Length", volume))
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = mount_point;
+        None
+    }
+}
+
+/// Number of open handles held by process `pid`.
+pub fn process_handle_count(pid: u32) -> Option<u32> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::Foundation::CloseHandle;
+        use windows_sys::Win32::System::Threading::{GetProcessHandleCount, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+        // SAFETY: the handle is checked for null and closed before returning.
+        unsafe {
+            let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if handle.is_null() {
+                return None;
+            }
+            let mut count = 0u32;
+            let ok = GetProcessHandleCount(handle, &mut count);
+            CloseHandle(handle);
+            (ok != 0).then_some(count)
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Peak working set of this process in bytes.
+pub fn peak_working_set() -> Option<u64> {
+    #[cfg(windows)]
+    {
+        use windows_sys::Win32::System::ProcessStatus::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+        use windows_sys::Win32::System::Threading::GetCurrentProcess;
+        let mut counters = PROCESS_MEMORY_COUNTERS {
+            cb: std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+            ..Default::default()
+        };
+        // SAFETY: the pseudo handle needs no closing and `counters` is properly sized.
+        if unsafe { GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, counters.cb) } == 0 {
+            return None;
+        }
+        Some(counters.PeakWorkingSetSize as u64)
+    }
+    #[cfg(not(windows))]
+    None
+}
+
+/// Reads a single instantaneous PDH counter by its English path.
+#[cfg(windows)]
+fn pdh_counter(path: &str) -> Option<f64> {
+    use windows_sys::Win32::System::Performance::*;
+    let wide: Vec<u16> = path.encode_utf16().chain(std::iter::once(0)).collect();
+    // SAFETY: the query is opened, used, and closed locally; out-pointers are valid locals.
+    unsafe {
+        let mut query: PDH_HQUERY = std::ptr::null_mut();
+        if PdhOpenQueryW(std::ptr::null(), 0, &mut query) != 0 {
+            return None;
+        }
+        let mut counter: PDH_HCOUNTER = std::ptr::null_mut();
+        let mut value = PDH_FMT_COUNTERVALUE::default();
+        let ok = PdhAddEnglishCounterW(query, wide.as_ptr(), 0, &mut counter) == 0
+            && PdhCollectQueryData(query) == 0
+            && PdhGetFormattedCounterValue(counter, PDH_FMT_DOUBLE, std::ptr::null_mut(), &mut value) == 0;
+        PdhCloseQuery(query);
+        ok.then_some(value.Anonymous.doubleValue)
+    }
+}